@@ -0,0 +1,858 @@
+//! Generates the command methods of the `redis` crate from the server's
+//! command spec.
+//!
+//! The input is `commands.json` in the format published by the Redis
+//! project.  Every command in it is either already implemented by hand in
+//! `redis/src/commands/mod.rs`, explicitly left out (see [`SKIPPED`]), or
+//! turned into an `implement_commands!` entry in
+//! `redis/src/commands/generated.rs`.  Run `cargo run -p redis-codegen` after
+//! updating the spec or the hand-written commands.
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+/// Path of the command spec, relative to this crate.
+pub const SPEC_PATH: &str = "commands.json";
+
+/// Paths of the files holding the hand-written commands, relative to this
+/// crate.
+pub const HAND_WRITTEN_PATHS: &[&str] = &[
+    "../redis/src/commands/mod.rs",
+    "../redis/src/commands/macros.rs",
+];
+
+/// Path of the generated module, relative to this crate.
+pub const GENERATED_PATH: &str = "../redis/src/commands/generated.rs";
+
+/// Commands that are deliberately not exposed as methods, with the reason.
+pub const SKIPPED: &[(&str, &str)] = &[
+    ("AUTH", "sent by the connection setup"),
+    ("HELLO", "sent by the connection setup"),
+    ("SELECT", "the database is part of the connection info"),
+    ("RESET", "resets connection state the client relies on"),
+    ("QUIT", "closes the connection"),
+    ("CLIENT REPLY", "replies have to match requests"),
+    ("MULTI", "use an atomic pipeline"),
+    ("EXEC", "use an atomic pipeline"),
+    ("DISCARD", "use an atomic pipeline"),
+    ("SUBSCRIBE", "use a pubsub connection"),
+    ("UNSUBSCRIBE", "use a pubsub connection"),
+    ("PSUBSCRIBE", "use a pubsub connection"),
+    ("PUNSUBSCRIBE", "use a pubsub connection"),
+    ("SSUBSCRIBE", "use a pubsub connection"),
+    ("SUNSUBSCRIBE", "use a pubsub connection"),
+    ("MONITOR", "turns the connection into a stream of events"),
+    ("SYNC", "part of the replication protocol"),
+    ("PSYNC", "part of the replication protocol"),
+];
+
+/// Commands covered by a hand-written method that sends a more general
+/// command, keyed by command name.
+pub const ALIASES: &[(&str, &str)] = &[("DECR", "decr"), ("INCR", "incr")];
+
+/// Inherent methods of `Cmd`, `Pipeline` and `ClusterPipeline` and methods
+/// of `ConnectionLike`, which generated methods must not shadow.
+const RESERVED: &[&str] = &[
+    "add_command",
+    "arg",
+    "arg_idx",
+    "args_iter",
+    "atomic",
+    "check_connection",
+    "clear",
+    "cmd",
+    "cmd_iter",
+    "command_name",
+    "commands",
+    "cursor_arg",
+    "execute",
+    "get_db",
+    "get_packed_command",
+    "get_packed_pipeline",
+    "ignore",
+    "in_scan_mode",
+    "is_empty",
+    "is_open",
+    "iter",
+    "len",
+    "new",
+    "query",
+    "query_async",
+    "req_command",
+    "req_packed_command",
+    "req_packed_commands",
+    "with_capacity",
+];
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while",
+];
+
+/// How a command of the spec is exposed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Coverage {
+    /// Sent by a hand-written method.
+    HandWritten,
+    /// Covered by the named hand-written method, see [`ALIASES`].
+    Alias(&'static str),
+    /// Sent by the named generated method.
+    Generated(String),
+    /// Left out for the given reason.
+    Skipped(&'static str),
+}
+
+/// The result of a generator run.
+#[derive(Debug)]
+pub struct Output {
+    /// The contents of the generated module.
+    pub source: String,
+    /// How every command of the spec is covered, by command name.
+    pub coverage: BTreeMap<String, Coverage>,
+}
+
+/// Reads the spec and the hand-written commands relative to `dir`, the
+/// directory of this crate, and generates the module.
+pub fn generate_from(dir: &Path) -> Result<Output, String> {
+    let read = |path: &str| {
+        fs::read_to_string(dir.join(path)).map_err(|err| format!("cannot read {path}: {err}"))
+    };
+    let spec = read(SPEC_PATH)?;
+    let hand_written = HAND_WRITTEN_PATHS
+        .iter()
+        .map(|path| read(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    generate(&spec, &hand_written)
+}
+
+/// Generates the module from the spec and the sources of the hand-written
+/// commands.
+pub fn generate(spec: &str, hand_written: &[String]) -> Result<Output, String> {
+    let commands = parse_spec(spec)?;
+    let known = HandWritten::scan(hand_written);
+
+    for (name, _) in SKIPPED.iter().chain(ALIASES) {
+        if !commands.iter().any(|command| command.name == *name) {
+            return Err(format!(
+                "{name} is listed in the generator but not in the spec"
+            ));
+        }
+    }
+
+    let containers: BTreeSet<&str> = commands
+        .iter()
+        .filter_map(|command| command.container.as_deref())
+        .collect();
+
+    let mut coverage = BTreeMap::new();
+    let mut entries: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for command in &commands {
+        let covered = if containers.contains(command.name.as_str()) && command.arity == -2 {
+            Coverage::Skipped("container of subcommands")
+        } else if command.doc_flags.iter().any(|flag| flag == "syscmd") {
+            Coverage::Skipped("internal server command")
+        } else if let Some((_, reason)) = SKIPPED.iter().find(|(name, _)| *name == command.name) {
+            Coverage::Skipped(reason)
+        } else if known.commands.contains(&command.name) {
+            Coverage::HandWritten
+        } else if let Some((_, method)) = ALIASES.iter().find(|(name, _)| *name == command.name) {
+            if !known.methods.contains(*method) {
+                return Err(format!(
+                    "{} is aliased to missing method {method}",
+                    command.name
+                ));
+            }
+            Coverage::Alias(method)
+        } else {
+            let method = method_name(&command.name);
+            if known.methods.contains(&method) {
+                return Err(format!(
+                    "{} would generate {method}, which is already a hand-written method",
+                    command.name
+                ));
+            }
+            if RESERVED.contains(&method.as_str()) || KEYWORDS.contains(&method.as_str()) {
+                return Err(format!(
+                    "{} would generate the reserved name {method}",
+                    command.name
+                ));
+            }
+            entries
+                .entry(command.group.as_str())
+                .or_default()
+                .push(render_command(command, &method)?);
+            Coverage::Generated(method)
+        };
+        coverage.insert(command.name.clone(), covered);
+    }
+
+    Ok(Output {
+        source: render_module(&entries),
+        coverage,
+    })
+}
+
+#[derive(Debug)]
+struct Command {
+    name: String,
+    summary: String,
+    group: String,
+    container: Option<String>,
+    arity: i64,
+    arguments: Vec<Arg>,
+    doc_flags: Vec<String>,
+    deprecated_since: Option<String>,
+    replaced_by: Option<String>,
+}
+
+#[derive(Debug)]
+struct Arg {
+    name: String,
+    kind: Kind,
+    token: Option<String>,
+    optional: bool,
+    multiple: bool,
+    multiple_token: bool,
+    arguments: Vec<Arg>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Key,
+    String,
+    Pattern,
+    Integer,
+    Double,
+    UnixTime,
+    PureToken,
+    OneOf,
+    Block,
+}
+
+impl Arg {
+    fn is_leaf(&self) -> bool {
+        !matches!(self.kind, Kind::PureToken | Kind::OneOf | Kind::Block)
+    }
+}
+
+fn parse_spec(spec: &str) -> Result<Vec<Command>, String> {
+    let spec: Value = serde_json::from_str(spec).map_err(|err| format!("invalid spec: {err}"))?;
+    let spec = spec.as_object().ok_or("the spec is not an object")?;
+    let mut commands = spec
+        .iter()
+        .map(|(name, command)| parse_command(name, command).map_err(|err| format!("{name}: {err}")))
+        .collect::<Result<Vec<_>, _>>()?;
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(commands)
+}
+
+fn string_field(value: &Value, field: &str) -> Option<String> {
+    value.get(field).and_then(Value::as_str).map(str::to_owned)
+}
+
+fn bool_field(value: &Value, field: &str) -> bool {
+    value.get(field).and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn arguments_field(value: &Value) -> Result<Vec<Arg>, String> {
+    match value.get("arguments") {
+        None => Ok(vec![]),
+        Some(Value::Array(arguments)) => arguments.iter().map(parse_arg).collect(),
+        Some(_) => Err("arguments is not an array".into()),
+    }
+}
+
+fn parse_command(name: &str, value: &Value) -> Result<Command, String> {
+    Ok(Command {
+        name: name.to_owned(),
+        summary: string_field(value, "summary").unwrap_or_default(),
+        group: string_field(value, "group").ok_or("missing group")?,
+        container: string_field(value, "container"),
+        arity: value
+            .get("arity")
+            .and_then(Value::as_i64)
+            .ok_or("missing arity")?,
+        arguments: arguments_field(value)?,
+        doc_flags: value
+            .get("doc_flags")
+            .and_then(Value::as_array)
+            .map(|flags| {
+                flags
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        deprecated_since: string_field(value, "deprecated_since"),
+        replaced_by: string_field(value, "replaced_by"),
+    })
+}
+
+fn parse_arg(value: &Value) -> Result<Arg, String> {
+    let name = string_field(value, "name").ok_or("argument without a name")?;
+    let kind = match value.get("type").and_then(Value::as_str) {
+        Some("key") => Kind::Key,
+        Some("string") => Kind::String,
+        Some("pattern") => Kind::Pattern,
+        Some("integer") => Kind::Integer,
+        Some("double") => Kind::Double,
+        Some("unix-time") => Kind::UnixTime,
+        Some("pure-token") => Kind::PureToken,
+        Some("oneof") => Kind::OneOf,
+        Some("block") => Kind::Block,
+        other => return Err(format!("argument {name} has unknown type {other:?}")),
+    };
+    let arg = Arg {
+        token: string_field(value, "token"),
+        optional: bool_field(value, "optional"),
+        multiple: bool_field(value, "multiple"),
+        multiple_token: bool_field(value, "multiple_token"),
+        arguments: arguments_field(value)?,
+        name,
+        kind,
+    };
+    if arg.kind == Kind::PureToken && arg.token.is_none() {
+        return Err(format!("pure-token argument {} has no token", arg.name));
+    }
+    Ok(arg)
+}
+
+/// The commands and method names found in the hand-written sources.
+struct HandWritten {
+    commands: BTreeSet<String>,
+    methods: BTreeSet<String>,
+}
+
+impl HandWritten {
+    fn scan(sources: &[String]) -> HandWritten {
+        let mut found = HandWritten {
+            commands: BTreeSet::new(),
+            methods: BTreeSet::new(),
+        };
+        for source in sources {
+            // Doc examples build commands too, which does not make them
+            // covered.
+            let code: String = source
+                .lines()
+                .filter(|line| !line.trim_start().starts_with("//"))
+                .flat_map(|line| [line, "\n"])
+                .collect();
+            found.scan_commands(&code);
+            found.scan_methods(&code);
+        }
+        found
+    }
+
+    fn scan_commands(&mut self, code: &str) {
+        let mut rest = code;
+        while let Some(start) = rest.find("cmd(") {
+            if rest[..start].ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                rest = &rest[start + 4..];
+                continue;
+            }
+            rest = &rest[start + 4..];
+            if let Some((name, after)) = string_literal(rest) {
+                if let Some(sub) = after
+                    .strip_prefix(')')
+                    .map(str::trim_start)
+                    .and_then(|after| after.strip_prefix(".arg("))
+                    .and_then(string_literal)
+                    .map(|(sub, _)| sub)
+                {
+                    self.commands.insert(format!("{name} {sub}"));
+                }
+                self.commands.insert(name.to_owned());
+                continue;
+            }
+            // A computed name like `cmd(if .. { "GET" } else { "MGET" })`
+            // sends any of the literals in it.
+            let mut depth = 1;
+            let mut end = rest.len();
+            for (i, c) in rest.char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    end = i;
+                    break;
+                }
+            }
+            let mut expr = &rest[..end];
+            while let Some(quote) = expr.find('"') {
+                match string_literal(&expr[quote..]) {
+                    Some((name, after)) => {
+                        if is_command_name(name) {
+                            self.commands.insert(name.to_owned());
+                        }
+                        expr = after;
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    fn scan_methods(&mut self, code: &str) {
+        let mut rest = code;
+        while let Some(start) = rest.find("fn ") {
+            let boundary = rest[..start].ends_with(|c: char| c.is_whitespace()) || start == 0;
+            rest = &rest[start + 3..];
+            let name: String = rest
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            if boundary && !name.is_empty() {
+                self.methods.insert(name);
+            }
+        }
+    }
+}
+
+/// Splits a leading string literal without escapes off `s`.
+fn string_literal(s: &str) -> Option<(&str, &str)> {
+    let s = s.strip_prefix('"')?;
+    let end = s.find('"')?;
+    Some((&s[..end], &s[end + 1..]))
+}
+
+fn is_command_name(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_uppercase())
+        && s.chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+fn method_name(command: &str) -> String {
+    command
+        .chars()
+        .map(|c| match c {
+            ' ' | '-' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+fn ident(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    ident
+}
+
+fn literal(token: &str) -> String {
+    format!("{token:?}")
+}
+
+/// Renders the syntax of an argument the way the command docs do.
+fn syntax(arg: &Arg, with_token: bool, with_optional: bool) -> String {
+    let mut base = match arg.kind {
+        Kind::PureToken => {
+            return wrap_optional(arg, arg.token.clone().unwrap_or_default(), with_optional)
+        }
+        Kind::OneOf => format!(
+            "<{}>",
+            arg.arguments
+                .iter()
+                .map(|arg| syntax(arg, true, true))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ),
+        Kind::Block => arg
+            .arguments
+            .iter()
+            .map(|arg| syntax(arg, true, true))
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => arg.name.clone(),
+    };
+    let token = arg.token.as_deref().filter(|_| with_token);
+    if arg.multiple {
+        let single = match (token, arg.multiple_token) {
+            (Some(token), true) => format!("{token} {base}"),
+            _ => base.clone(),
+        };
+        base = format!("{base} [{single} ...]");
+    }
+    if let Some(token) = token {
+        base = format!("{token} {base}");
+    }
+    wrap_optional(arg, base, with_optional)
+}
+
+fn wrap_optional(arg: &Arg, syntax: String, with_optional: bool) -> String {
+    if arg.optional && with_optional {
+        format!("[{syntax}]")
+    } else {
+        syntax
+    }
+}
+
+/// A generated method under construction.
+#[derive(Default)]
+struct Method {
+    type_params: Vec<String>,
+    params: Vec<(String, String)>,
+    chain: Vec<String>,
+    raw: Vec<(String, String)>,
+    names: BTreeSet<String>,
+}
+
+impl Method {
+    fn fresh(&mut self, base: &str) -> String {
+        let mut name = base.to_owned();
+        let mut n = 2;
+        while self.names.contains(&name) {
+            name = format!("{base}{n}");
+            n += 1;
+        }
+        self.names.insert(name.clone());
+        name
+    }
+
+    fn type_param(&mut self, base: &str) -> String {
+        let name = self.fresh(base);
+        self.type_params.push(name.clone());
+        name
+    }
+
+    fn leaf_type(&mut self, arg: &Arg) -> String {
+        match arg.kind {
+            Kind::Key => self.type_param("K"),
+            Kind::String => self.type_param("V"),
+            Kind::Pattern => self.type_param("P"),
+            _ if arg.multiple => self.type_param("T"),
+            Kind::Integer | Kind::UnixTime => "i64".into(),
+            Kind::Double => "f64".into(),
+            _ => unreachable!("not a leaf argument"),
+        }
+    }
+
+    /// Adds a parameter of type `ty` for `arg` along with the arguments it
+    /// writes.
+    fn add_value(&mut self, arg: &Arg, ty: String) -> String {
+        let name = self.fresh(&ident(&arg.name));
+        let token = arg.token.as_deref().map(literal);
+        let repeated = arg.multiple_token && arg.is_leaf();
+        if arg.optional {
+            self.params.push((name.clone(), format!("Option<{ty}>")));
+            self.chain.push(match token {
+                Some(token) if repeated => {
+                    format!("{name}.map(|v| crate::commands::generated::Repeated({token}, v))")
+                }
+                Some(token) => format!("{name}.map(|v| ({token}, v))"),
+                None => name.clone(),
+            });
+        } else {
+            self.params.push((name.clone(), ty));
+            match token {
+                Some(token) if repeated => self.chain.push(format!(
+                    "crate::commands::generated::Repeated({token}, {name})"
+                )),
+                Some(token) => {
+                    self.chain.push(token);
+                    self.chain.push(name.clone());
+                }
+                None => self.chain.push(name.clone()),
+            }
+        }
+        name
+    }
+
+    fn add_arg(&mut self, arg: &Arg) {
+        match arg.kind {
+            Kind::PureToken if arg.optional => {
+                let name = self.fresh(&ident(&arg.name));
+                let token = literal(arg.token.as_deref().unwrap_or_default());
+                self.params.push((name.clone(), "bool".into()));
+                self.chain.push(format!("{name}.then_some({token})"));
+            }
+            Kind::PureToken => self
+                .chain
+                .push(literal(arg.token.as_deref().unwrap_or_default())),
+            _ if arg.is_leaf() => {
+                let ty = self.leaf_type(arg);
+                self.add_value(arg, ty);
+            }
+            Kind::OneOf
+                if !arg.multiple && arg.arguments.iter().all(|arg| arg.kind == Kind::PureToken) =>
+            {
+                self.add_value(arg, "&$lifetime str".into());
+            }
+            Kind::Block if !arg.multiple && is_flat_block(arg) => self.add_block(arg),
+            _ => {
+                // Anything else is written as given.  Repeated tokens are
+                // part of the value, as they belong to each group.
+                let ty = self.type_param("T");
+                let name = if arg.multiple_token {
+                    let name = self.fresh(&ident(&arg.name));
+                    self.params.push((
+                        name.clone(),
+                        if arg.optional {
+                            format!("Option<{ty}>")
+                        } else {
+                            ty
+                        },
+                    ));
+                    self.chain.push(name.clone());
+                    name
+                } else {
+                    self.add_value(arg, ty)
+                };
+                let with_token = arg.multiple_token;
+                self.raw.push((name, syntax(arg, with_token, false)));
+            }
+        }
+    }
+
+    /// Adds a block of plain arguments as one tuple parameter.
+    fn add_block(&mut self, arg: &Arg) {
+        let mut names = BTreeSet::new();
+        let mut types = vec![];
+        let mut bindings = vec![];
+        let mut parts: Vec<String> = arg.token.iter().map(|token| literal(token)).collect();
+        for child in &arg.arguments {
+            let token = child.token.as_deref().map(literal);
+            if child.kind == Kind::PureToken && !child.optional {
+                parts.extend(token);
+                continue;
+            }
+            let mut binding = ident(&child.name);
+            let base = binding.clone();
+            let mut n = 2;
+            while !names.insert(binding.clone()) {
+                binding = format!("{base}{n}");
+                n += 1;
+            }
+            if child.kind == Kind::PureToken {
+                types.push("bool".to_owned());
+                parts.push(format!(
+                    "{binding}.then_some({})",
+                    token.unwrap_or_default()
+                ));
+            } else {
+                let ty = self.leaf_type(child);
+                match token {
+                    Some(token) if child.optional => {
+                        types.push(format!("Option<{ty}>"));
+                        parts.push(format!("{binding}.map(|v| ({token}, v))"));
+                    }
+                    None if child.optional => {
+                        types.push(format!("Option<{ty}>"));
+                        parts.push(binding.clone());
+                    }
+                    token => {
+                        types.push(ty);
+                        parts.extend(token);
+                        parts.push(binding.clone());
+                    }
+                }
+            }
+            bindings.push(binding);
+        }
+
+        let (ty, pattern) = if types.len() == 1 {
+            (types.remove(0), bindings.remove(0))
+        } else {
+            (
+                format!("({})", types.join(", ")),
+                format!("({})", bindings.join(", ")),
+            )
+        };
+        let value = if parts.len() == 1 {
+            parts.remove(0)
+        } else {
+            format!("({})", parts.join(", "))
+        };
+        let name = self.fresh(&ident(&arg.name));
+        if arg.optional {
+            self.params.push((name.clone(), format!("Option<{ty}>")));
+            if value == pattern {
+                self.chain.push(name);
+            } else {
+                self.chain.push(format!("{name}.map(|{pattern}| {value})"));
+            }
+        } else {
+            self.params.push((name.clone(), ty));
+            if value == pattern {
+                self.chain.push(name);
+            } else {
+                self.chain
+                    .push(format!("{{ let {pattern} = {name}; {value} }}"));
+            }
+        }
+    }
+}
+
+/// Whether a block only holds single arguments, so it can be a tuple.
+fn is_flat_block(arg: &Arg) -> bool {
+    arg.arguments
+        .iter()
+        .all(|arg| !arg.multiple && (arg.is_leaf() || arg.kind == Kind::PureToken))
+        && arg
+            .arguments
+            .iter()
+            .any(|arg| arg.is_leaf() || arg.optional)
+}
+
+fn render_command(command: &Command, method_name: &str) -> Result<String, String> {
+    let mut method = Method::default();
+    let mut words = command.name.split(' ');
+    let head = words.next().unwrap_or_default();
+    for word in words {
+        method.chain.push(literal(word));
+    }
+    for arg in &command.arguments {
+        method.add_arg(arg);
+    }
+
+    let mut out = String::new();
+    let mut summary = command
+        .summary
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !summary.ends_with('.') {
+        summary.push('.');
+    }
+    let usage = std::iter::once(command.name.clone())
+        .chain(command.arguments.iter().map(|arg| syntax(arg, true, true)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let _ = writeln!(out, "            /// {summary}");
+    let _ = writeln!(out, "            ///");
+    let _ = writeln!(out, "            /// `{usage}`");
+    if !method.raw.is_empty() {
+        let _ = writeln!(out, "            ///");
+        let _ = writeln!(
+            out,
+            "            /// These parameters are written as given and have to follow the syntax:"
+        );
+        for (name, syntax) in &method.raw {
+            let _ = writeln!(out, "            /// - `{name}`: `{syntax}`");
+        }
+    }
+    if command.doc_flags.iter().any(|flag| flag == "deprecated") {
+        let since = command.deprecated_since.as_deref().unwrap_or("unknown");
+        let _ = writeln!(out, "            ///");
+        match &command.replaced_by {
+            Some(replacement) => {
+                let _ = writeln!(
+                    out,
+                    "            /// Deprecated by the server since {since}, replaced by {replacement}."
+                );
+            }
+            None => {
+                let _ = writeln!(
+                    out,
+                    "            /// Deprecated by the server since {since}."
+                );
+            }
+        }
+    }
+
+    let type_params = method
+        .type_params
+        .iter()
+        .map(|name| format!("{name}: ToRedisArgs"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let params = method
+        .params
+        .iter()
+        .map(|(name, ty)| format!("{name}: {ty}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if method.params.len() > 6 {
+        let _ = writeln!(out, "            #[allow(clippy::too_many_arguments)]");
+    }
+    let _ = writeln!(
+        out,
+        "            fn {method_name}<{type_params}>({params}) {{"
+    );
+    let head = if method.chain.is_empty() {
+        format!("&mut cmd({})", literal(head))
+    } else {
+        format!("cmd({})", literal(head))
+    };
+    let call = std::iter::once(head)
+        .chain(method.chain.iter().map(|arg| format!(".arg({arg})")))
+        .collect::<Vec<_>>();
+    let one_line = call.concat();
+    if one_line.len() <= 80 {
+        let _ = writeln!(out, "                {one_line}");
+    } else {
+        let _ = writeln!(out, "                {}", call[0]);
+        for arg in &call[1..] {
+            let _ = writeln!(out, "                    {arg}");
+        }
+    }
+    let _ = writeln!(out, "            }}");
+    if method.params.len() > 12 {
+        return Err(format!("{} has too many parameters", command.name));
+    }
+    Ok(out)
+}
+
+fn render_module(entries: &BTreeMap<&str, Vec<String>>) -> String {
+    let mut out = String::from(
+        r#"// This file is generated by `cargo run -p redis-codegen` from
+// `redis-codegen/commands.json`.  Do not edit it by hand.
+#![cfg_attr(rustfmt, rustfmt_skip)]
+use crate::types::{RedisWrite, ToRedisArgs};
+
+/// Writes a token in front of every argument of the wrapped value, as
+/// needed by arguments like `PREFIX prefix [PREFIX prefix ...]`.
+pub(crate) struct Repeated<T>(pub(crate) &'static str, pub(crate) T);
+
+impl<T: ToRedisArgs> ToRedisArgs for Repeated<T> {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        for arg in self.1.to_redis_args() {
+            out.write_arg(self.0.as_bytes());
+            out.write_arg(&arg);
+        }
+    }
+}
+
+/// Passes the hand-written command entries on to `implement_commands!`,
+/// followed by the entries generated for the remaining commands of the
+/// spec.
+macro_rules! implement_commands_with_generated {
+    (
+        $lifetime: lifetime
+        $($entries:tt)*
+    ) => (
+        implement_commands! {
+            $lifetime
+            $($entries)*
+"#,
+    );
+    for (group, entries) in entries {
+        let _ = write!(out, "\n            // generated: {group}\n");
+        for entry in entries {
+            out.push('\n');
+            out.push_str(entry);
+        }
+    }
+    out.push_str("        }\n    )\n}\n");
+    out
+}
@@ -0,0 +1,30 @@
+use std::path::Path;
+use std::process::ExitCode;
+
+use redis_codegen::{generate_from, Coverage, GENERATED_PATH};
+
+fn main() -> ExitCode {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let output = match generate_from(dir) {
+        Ok(output) => output,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(err) = std::fs::write(dir.join(GENERATED_PATH), &output.source) {
+        eprintln!("error: cannot write {GENERATED_PATH}: {err}");
+        return ExitCode::FAILURE;
+    }
+
+    let count = |f: fn(&Coverage) -> bool| output.coverage.values().filter(|c| f(c)).count();
+    println!(
+        "{} commands: {} hand-written, {} aliased, {} generated, {} skipped",
+        output.coverage.len(),
+        count(|c| matches!(c, Coverage::HandWritten)),
+        count(|c| matches!(c, Coverage::Alias(_))),
+        count(|c| matches!(c, Coverage::Generated(_))),
+        count(|c| matches!(c, Coverage::Skipped(_))),
+    );
+    ExitCode::SUCCESS
+}
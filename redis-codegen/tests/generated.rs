@@ -0,0 +1,68 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use redis_codegen::{generate_from, Coverage, GENERATED_PATH, SKIPPED, SPEC_PATH};
+
+fn dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+fn generated_module_is_up_to_date() {
+    let output = generate_from(dir()).unwrap();
+    let checked_in = fs::read_to_string(dir().join(GENERATED_PATH)).unwrap();
+    assert!(
+        output.source == checked_in,
+        "{GENERATED_PATH} is out of date, run `cargo run -p redis-codegen`"
+    );
+}
+
+#[test]
+fn every_command_is_covered() {
+    let output = generate_from(dir()).unwrap();
+    let spec: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir().join(SPEC_PATH)).unwrap()).unwrap();
+    let names: BTreeSet<&String> = spec.as_object().unwrap().keys().collect();
+    assert_eq!(names, output.coverage.keys().collect());
+
+    // Only containers, internal commands and the listed ones are left out.
+    for (name, coverage) in &output.coverage {
+        if let Coverage::Skipped(reason) = coverage {
+            let listed = SKIPPED.iter().any(|(skipped, _)| skipped == name);
+            let container = name != "COMMAND"
+                && output.coverage.keys().any(|other| {
+                    other
+                        .strip_prefix(name.as_str())
+                        .map_or(false, |sub| sub.starts_with(' '))
+                });
+            let internal = spec[name]["doc_flags"]
+                .as_array()
+                .map_or(false, |flags| flags.iter().any(|flag| flag == "syscmd"));
+            assert!(
+                listed || container || internal,
+                "{name} is skipped: {reason}"
+            );
+        }
+    }
+
+    for name in [
+        "GETRANGE",
+        "SUBSTR",
+        "HRANDFIELD",
+        "HSTRLEN",
+        "SMISMEMBER",
+        "SINTERCARD",
+        "LCS",
+        "OBJECT ENCODING",
+        "OBJECT FREQ",
+        "OBJECT HELP",
+        "OBJECT IDLETIME",
+        "OBJECT REFCOUNT",
+    ] {
+        assert!(
+            !matches!(output.coverage[name], Coverage::Skipped(_)),
+            "{name} is not covered"
+        );
+    }
+}
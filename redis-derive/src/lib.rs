@@ -0,0 +1,539 @@
+//! Derive macros for the `FromRedisValue` and `ToRedisArgs` traits of the
+//! `redis` crate.
+//!
+//! These are re-exported by `redis` when its `derive` feature is enabled,
+//! so you normally don't depend on this crate directly.
+//!
+//! # Structs
+//!
+//! Structs with named fields are mapped to redis hashes.  `ToRedisArgs`
+//! writes alternating field names and values, which is the format `HSET`
+//! expects, and `FromRedisValue` reads the field/value pairs returned by
+//! `HGETALL` (or a RESP3 map).
+//!
+//! ```rust,ignore
+//! #[derive(redis::FromRedisValue, redis::ToRedisArgs)]
+//! struct User {
+//!     #[redis(rename = "user_name")]
+//!     name: String,
+//!     // Missing fields are `None`, `None` fields are not written.
+//!     email: Option<String>,
+//!     // Missing fields fall back to `Default::default()`.
+//!     #[redis(default)]
+//!     visits: u64,
+//!     // Never written nor read.
+//!     #[redis(skip)]
+//!     cache: Vec<u8>,
+//!     // Stored as a JSON string, requires the `json` feature of `redis`.
+//!     #[redis(json)]
+//!     tags: Vec<String>,
+//! }
+//!
+//! redis::cmd("HSET").arg("user:1").arg(&user).query(&mut con)?;
+//! let user: User = con.hgetall("user:1")?;
+//! ```
+//!
+//! Field values have to be written as a single argument, so fields holding
+//! standard collections, arrays, slices or tuples are rejected unless they
+//! are marked `#[redis(json)]`.  Collections hidden behind a type parameter
+//! or a type alias can't be detected and break the field/value alternation.
+//!
+//! `#[redis(json)]` fields need to implement `serde::Serialize` and
+//! `serde::Deserialize`.  Since `ToRedisArgs` can't fail, the derived
+//! implementation panics if serializing the field fails, for instance for
+//! a map with non-string keys.  When that is possible, keep the field as a
+//! `String` and fill it with the fallible `serde_json::to_string` instead.
+//!
+//! Newtype structs such as `struct UserId(u64)` are converted like the type
+//! they wrap.
+//!
+//! # Enums
+//!
+//! Enums with only unit variants are represented by the name of the
+//! variant.  Both variants and structs accept `#[redis(rename = "...")]`
+//! and the container accepts `#[redis(rename_all = "...")]` with one of
+//! `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`,
+//! `SCREAMING_SNAKE_CASE` and `kebab-case`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, GenericArgument, LitStr,
+    PathArguments, Result, Type,
+};
+
+/// Derives `redis::FromRedisValue`.  See the crate documentation for the
+/// supported attributes.
+#[proc_macro_derive(FromRedisValue, attributes(redis))]
+pub fn derive_from_redis_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_redis_value(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derives `redis::ToRedisArgs`.  See the crate documentation for the
+/// supported attributes.
+#[proc_macro_derive(ToRedisArgs, attributes(redis))]
+pub fn derive_to_redis_args(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_to_redis_args(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct ContainerAttrs {
+    rename_all: Option<RenameRule>,
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    skip: bool,
+    default: bool,
+    json: bool,
+}
+
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+}
+
+impl RenameRule {
+    fn from_str(rule: &LitStr) -> Result<RenameRule> {
+        Ok(match rule.value().as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            _ => return Err(Error::new(rule.span(), "unknown rename rule")),
+        })
+    }
+
+    fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+        let capitalize = |word: &String| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        };
+        match self {
+            RenameRule::Lower => words.concat(),
+            RenameRule::Upper => words.concat().to_ascii_uppercase(),
+            RenameRule::Pascal => words.iter().map(capitalize).collect(),
+            RenameRule::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.clone()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            RenameRule::Snake => words.join("_"),
+            RenameRule::ScreamingSnake => words.join("_").to_ascii_uppercase(),
+            RenameRule::Kebab => words.join("-"),
+        }
+    }
+}
+
+/// Splits a `snake_case` or `PascalCase` identifier into lowercase words.
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for c in name.trim_start_matches("r#").chars() {
+        if c == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else if c.is_ascii_uppercase() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            current.push(c.to_ascii_lowercase());
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn container_attrs(attrs: &[Attribute]) -> Result<ContainerAttrs> {
+    let mut rv = ContainerAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("redis")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                rv.rename_all = Some(RenameRule::from_str(&meta.value()?.parse()?)?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported redis container attribute"))
+            }
+        })?;
+    }
+    Ok(rv)
+}
+
+fn field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs> {
+    let mut rv = FieldAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("redis")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let name: LitStr = meta.value()?.parse()?;
+                rv.rename = Some(name.value());
+            } else if meta.path.is_ident("skip") {
+                rv.skip = true;
+            } else if meta.path.is_ident("default") {
+                rv.default = true;
+            } else if meta.path.is_ident("json") {
+                rv.json = true;
+            } else {
+                return Err(meta.error("unsupported redis field attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(rv)
+}
+
+/// Returns the inner type if `ty` is an `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns true if `ty` is written as several arguments, as far as can be
+/// told from its syntax.  Byte buffers are single arguments.
+fn is_collection(ty: &Type) -> bool {
+    let is_u8 = |ty: &Type| matches!(ty, Type::Path(path) if path.path.is_ident("u8"));
+    match ty {
+        Type::Array(array) => !is_u8(&array.elem),
+        Type::Slice(slice) => !is_u8(&slice.elem),
+        Type::Tuple(tuple) => !tuple.elems.is_empty(),
+        Type::Reference(reference) => is_collection(&reference.elem),
+        Type::Group(group) => is_collection(&group.elem),
+        Type::Paren(paren) => is_collection(&paren.elem),
+        Type::Path(path) => {
+            let Some(segment) = path.path.segments.last() else {
+                return false;
+            };
+            let first_arg = match &segment.arguments {
+                PathArguments::AngleBracketed(args) => match args.args.first() {
+                    Some(GenericArgument::Type(ty)) => Some(ty),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match segment.ident.to_string().as_str() {
+                "Vec" | "VecDeque" => !first_arg.map_or(false, is_u8),
+                "LinkedList" | "BinaryHeap" | "HashMap" | "BTreeMap" | "HashSet" | "BTreeSet"
+                | "IndexMap" | "IndexSet" => true,
+                "Option" | "Box" | "Rc" | "Arc" => first_arg.map_or(false, is_collection),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+struct NamedField<'a> {
+    ident: &'a syn::Ident,
+    ty: &'a Type,
+    name: String,
+    attrs: FieldAttrs,
+}
+
+fn named_fields<'a>(
+    fields: &'a syn::FieldsNamed,
+    container: &ContainerAttrs,
+) -> Result<Vec<NamedField<'a>>> {
+    fields
+        .named
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            let attrs = field_attrs(&field.attrs)?;
+            if !attrs.skip && !attrs.json && is_collection(&field.ty) {
+                return Err(Error::new_spanned(
+                    &field.ty,
+                    "fields holding collections must be marked `#[redis(json)]`",
+                ));
+            }
+            let name = match (&attrs.rename, container.rename_all) {
+                (Some(name), _) => name.clone(),
+                (None, Some(rule)) => rule.apply(&ident.to_string()),
+                (None, None) => ident.to_string().trim_start_matches("r#").to_string(),
+            };
+            Ok(NamedField {
+                ident,
+                ty: &field.ty,
+                name,
+                attrs,
+            })
+        })
+        .collect()
+}
+
+fn variant_names(
+    data: &syn::DataEnum,
+    container: &ContainerAttrs,
+) -> Result<Vec<(syn::Ident, String)>> {
+    data.variants
+        .iter()
+        .map(|variant| {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(Error::new_spanned(
+                    variant,
+                    "only enums with unit variants can be derived",
+                ));
+            }
+            let attrs = field_attrs(&variant.attrs)?;
+            let name = match (attrs.rename, container.rename_all) {
+                (Some(name), _) => name,
+                (None, Some(rule)) => rule.apply(&variant.ident.to_string()),
+                (None, None) => variant.ident.to_string(),
+            };
+            Ok((variant.ident.clone(), name))
+        })
+        .collect()
+}
+
+fn type_error(msg: TokenStream2) -> TokenStream2 {
+    quote! {
+        ::redis::RedisError::from((
+            ::redis::ErrorKind::TypeError,
+            "Response was of incompatible type",
+            #msg,
+        ))
+    }
+}
+
+fn expand_from_redis_value(input: &DeriveInput) -> Result<TokenStream2> {
+    let container = container_attrs(&input.attrs)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let fields = named_fields(fields, &container)?;
+                let slots = fields.iter().filter(|f| !f.attrs.skip).map(|f| {
+                    let slot = slot_ident(f.ident);
+                    quote! { let mut #slot = ::std::option::Option::None; }
+                });
+                let arms = fields.iter().filter(|f| !f.attrs.skip).map(|f| {
+                    let slot = slot_ident(f.ident);
+                    let name = &f.name;
+                    let ty = option_inner(f.ty).unwrap_or(f.ty);
+                    let segment = quote! {
+                        |err: ::redis::RedisError| err.with_conversion_segment(
+                            ::redis::ConversionPathSegment::Key(#name.to_string()),
+                        )
+                    };
+                    let convert = if f.attrs.json {
+                        quote! {{
+                            let json: ::std::string::String =
+                                ::redis::from_redis_value(value).map_err(#segment)?;
+                            ::redis::__private::serde_json::from_str::<#ty>(&json)?
+                        }}
+                    } else {
+                        quote! { ::redis::from_redis_value::<#ty>(value).map_err(#segment)? }
+                    };
+                    quote! { #name => #slot = ::std::option::Option::Some(#convert), }
+                });
+                let inits = fields.iter().map(|f| {
+                    let field = f.ident;
+                    let slot = slot_ident(f.ident);
+                    let name = &f.name;
+                    if f.attrs.skip {
+                        quote! { #field: ::std::default::Default::default() }
+                    } else if option_inner(f.ty).is_some() {
+                        quote! { #field: #slot }
+                    } else if f.attrs.default {
+                        quote! { #field: #slot.unwrap_or_default() }
+                    } else {
+                        let err = type_error(quote! { format!("missing field {:?}", #name) });
+                        quote! { #field: #slot.ok_or_else(|| #err)? }
+                    }
+                });
+                let not_a_map = type_error(quote! {
+                    format!("Response type not map compatible (response was {:?})", v)
+                });
+                quote! {
+                    #(#slots)*
+                    let iter = v.as_map_iter().ok_or_else(|| #not_a_map)?;
+                    for (key, value) in iter {
+                        let key: ::std::string::String = ::redis::from_redis_value(key)?;
+                        match key.as_str() {
+                            #(#arms)*
+                            _ => {}
+                        }
+                    }
+                    ::std::result::Result::Ok(#ident { #(#inits,)* })
+                }
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                quote! { ::std::result::Result::Ok(#ident(::redis::from_redis_value(v)?)) }
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    input,
+                    "only structs with named fields or newtype structs can be derived",
+                ))
+            }
+        },
+        Data::Enum(data) => {
+            let arms = variant_names(data, &container)?.into_iter().map(
+                |(variant, name)| quote! { #name => ::std::result::Result::Ok(#ident::#variant), },
+            );
+            let err = type_error(quote! {
+                format!("Unknown variant {:?} of {}", name, stringify!(#ident))
+            });
+            quote! {
+                let name: ::std::string::String = ::redis::from_redis_value(v)?;
+                match name.as_str() {
+                    #(#arms)*
+                    _ => ::std::result::Result::Err(#err),
+                }
+            }
+        }
+        Data::Union(_) => return Err(Error::new_spanned(input, "unions can't be derived")),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::redis::FromRedisValue for #ident #ty_generics #where_clause {
+            fn from_redis_value(v: &::redis::Value) -> ::redis::RedisResult<Self> {
+                #body
+            }
+        }
+    })
+}
+
+fn expand_to_redis_args(input: &DeriveInput) -> Result<TokenStream2> {
+    let container = container_attrs(&input.attrs)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // `None` keeps the default `is_single_arg`, which is true
+    let (body, single_arg) = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let fields = named_fields(fields, &container)?;
+                let writes = fields.iter().filter(|f| !f.attrs.skip).map(|f| {
+                    let field = f.ident;
+                    let name = &f.name;
+                    let write = |value: TokenStream2| {
+                        if f.attrs.json {
+                            let expect = format!("failed to serialize field {name:?} as JSON");
+                            // Naming the field type points missing `Serialize`
+                            // implementations at the field
+                            let ty = option_inner(f.ty).unwrap_or(f.ty);
+                            let json = quote! {
+                                ::redis::__private::serde_json::to_string::<#ty>(#value)
+                            };
+                            quote! {
+                                out.write_arg(#name.as_bytes());
+                                out.write_arg(#json.expect(#expect).as_bytes());
+                            }
+                        } else {
+                            quote! {
+                                out.write_arg(#name.as_bytes());
+                                ::redis::ToRedisArgs::write_redis_args(#value, out);
+                            }
+                        }
+                    };
+                    if option_inner(f.ty).is_some() {
+                        let write = write(quote! { value });
+                        quote! {
+                            if let ::std::option::Option::Some(value) = &self.#field {
+                                #write
+                            }
+                        }
+                    } else {
+                        write(quote! { &self.#field })
+                    }
+                });
+                (quote! { #(#writes)* }, Some(quote! { false }))
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => (
+                quote! { ::redis::ToRedisArgs::write_redis_args(&self.0, out) },
+                Some(quote! { ::redis::ToRedisArgs::is_single_arg(&self.0) }),
+            ),
+            _ => {
+                return Err(Error::new_spanned(
+                    input,
+                    "only structs with named fields or newtype structs can be derived",
+                ))
+            }
+        },
+        Data::Enum(data) => {
+            let arms = variant_names(data, &container)?
+                .into_iter()
+                .map(|(variant, name)| quote! { #ident::#variant => #name, });
+            (
+                quote! {
+                    out.write_arg(match self { #(#arms)* }.as_bytes());
+                },
+                None,
+            )
+        }
+        Data::Union(_) => return Err(Error::new_spanned(input, "unions can't be derived")),
+    };
+
+    let is_single_arg = single_arg.map(|single_arg| {
+        quote! {
+            fn is_single_arg(&self) -> bool {
+                #single_arg
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::redis::ToRedisArgs for #ident #ty_generics #where_clause {
+            fn write_redis_args<W>(&self, out: &mut W)
+            where
+                W: ?Sized + ::redis::RedisWrite,
+            {
+                #body
+            }
+
+            #is_single_arg
+        }
+    })
+}
+
+fn slot_ident(field: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(
+        &format!("__field_{}", field.to_string().trim_start_matches("r#")),
+        Span::call_site(),
+    )
+}
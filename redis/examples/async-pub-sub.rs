@@ -10,7 +10,9 @@ async fn main() -> redis::RedisResult<()> {
     pubsub_conn.subscribe("wavephone").await?;
     let mut pubsub_stream = pubsub_conn.on_message();
 
-    publish_conn.publish("wavephone", "banana").await?;
+    publish_conn
+        .publish::<_, _, ()>("wavephone", "banana")
+        .await?;
 
     let pubsub_msg: String = pubsub_stream.next().await.unwrap().get_payload()?;
     assert_eq!(&pubsub_msg, "banana");
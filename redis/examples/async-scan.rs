@@ -6,8 +6,8 @@ async fn main() -> redis::RedisResult<()> {
     let client = redis::Client::open("redis://127.0.0.1/").unwrap();
     let mut con = client.get_async_connection().await?;
 
-    con.set("async-key1", b"foo").await?;
-    con.set("async-key2", b"foo").await?;
+    con.set::<_, _, ()>("async-key1", b"foo").await?;
+    con.set::<_, _, ()>("async-key2", b"foo").await?;
 
     let iter: AsyncIter<String> = con.scan().await?;
     let mut keys: Vec<_> = iter.collect().await;
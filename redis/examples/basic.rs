@@ -52,7 +52,7 @@ fn do_show_scanning(con: &mut redis::Connection) -> redis::RedisResult<()> {
 
     // since we don't care about the return value of the pipeline we can
     // just cast it into the unit type.
-    pipe.query(con)?;
+    pipe.query::<()>(con)?;
 
     // since rust currently does not track temporaries for us, we need to
     // store it in a local variable.
@@ -75,12 +75,12 @@ fn do_atomic_increment_lowlevel(con: &mut redis::Connection) -> redis::RedisResu
     println!("Run low-level atomic increment:");
 
     // set the initial value so we have something to test with.
-    redis::cmd("SET").arg(key).arg(42).query(con)?;
+    redis::cmd("SET").arg(key).arg(42).query::<()>(con)?;
 
     loop {
         // we need to start watching the key we care about, so that our
         // exec fails if the key changes.
-        redis::cmd("WATCH").arg(key).query(con)?;
+        redis::cmd("WATCH").arg(key).query::<()>(con)?;
 
         // load the old value, so we know what to increment.
         let val: isize = redis::cmd("GET").arg(key).query(con)?;
@@ -118,7 +118,7 @@ fn do_atomic_increment(con: &mut redis::Connection) -> redis::RedisResult<()> {
     println!("Run high-level atomic increment:");
 
     // set the initial value so we have something to test with.
-    con.set(key, 42)?;
+    con.set::<_, _, ()>(key, 42)?;
 
     // run the transaction block.
     let (new_val,): (isize,) = transaction(con, &[key], |con, pipe| {
@@ -0,0 +1,512 @@
+//! Server-assisted client side caching, based on `CLIENT TRACKING`.
+//!
+//! Replies of read-only commands sent through a connection with a cache are
+//! stored locally and served from memory until the server announces, through
+//! a RESP3 `invalidate` push message, that one of the keys they depend on
+//! changed.
+use super::PushSender;
+use crate::cmd::{cmd, Arg, Cmd};
+use crate::types::{ErrorKind, PushInfo, PushKind, RedisResult, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+/// The tracking mode used by `CLIENT TRACKING`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum TrackingMode {
+    /// The server remembers the keys read by the connection and only sends
+    /// invalidations for those.
+    #[default]
+    Default,
+    /// The server sends invalidations for every modified key starting with
+    /// one of the prefixes, whether it was read or not.  No prefix means all
+    /// keys.  Only replies whose keys all match a prefix are cached.
+    Broadcast(Vec<String>),
+    /// Only the keys of cached commands are tracked, by sending
+    /// `CLIENT CACHING YES` right before each of them.
+    OptIn,
+}
+
+/// The configuration of a client side cache.
+#[derive(Clone, Debug)]
+pub struct CacheConfig {
+    /// The tracking mode the connection enables.
+    pub mode: TrackingMode,
+    /// The maximum number of cached replies.  The oldest replies are evicted
+    /// first once it is reached.
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            mode: TrackingMode::Default,
+            max_entries: 10_000,
+        }
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    // packed command -> (reply, keys the reply depends on)
+    entries: HashMap<Vec<u8>, (Value, Vec<Vec<u8>>)>,
+    // key -> packed commands depending on it
+    keys: HashMap<Vec<u8>, HashSet<Vec<u8>>>,
+    // packed commands in insertion order, used to enforce `max_entries`
+    order: VecDeque<Vec<u8>>,
+    // bumped on every invalidation, so that replies racing with an
+    // invalidation are never stored
+    epoch: u64,
+    invalidations: Option<mpsc::UnboundedReceiver<PushInfo>>,
+}
+
+impl CacheState {
+    fn remove_entry(&mut self, packed: &[u8]) {
+        if let Some((_, keys)) = self.entries.remove(packed) {
+            for key in keys {
+                if let Some(commands) = self.keys.get_mut(&key) {
+                    commands.remove(packed);
+                    if commands.is_empty() {
+                        self.keys.remove(&key);
+                    }
+                }
+            }
+        }
+    }
+
+    fn invalidate_key(&mut self, key: &[u8]) {
+        self.epoch += 1;
+        if let Some(commands) = self.keys.remove(key) {
+            for packed in commands {
+                self.remove_entry(&packed);
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        self.epoch += 1;
+        self.entries.clear();
+        self.keys.clear();
+        self.order.clear();
+    }
+
+    fn drain_invalidations(&mut self) {
+        let mut pending = Vec::new();
+        if let Some(invalidations) = &mut self.invalidations {
+            while let Ok(push) = invalidations.try_recv() {
+                pending.push(push);
+            }
+        }
+        for push in pending {
+            if push.kind != PushKind::Invalidate {
+                continue;
+            }
+            match push.data.into_iter().next() {
+                Some(Value::Bulk(keys)) => {
+                    for key in keys {
+                        if let Value::Data(key) = key {
+                            self.invalidate_key(&key);
+                        }
+                    }
+                }
+                // A null key list means that the whole cache must be flushed,
+                // e.g. after FLUSHALL.
+                _ => self.flush(),
+            }
+        }
+    }
+}
+
+/// A cache shared by all clones of a connection, and by the successive
+/// connections of a `ConnectionManager`.
+#[derive(Clone)]
+pub(crate) struct LocalCache {
+    config: Arc<CacheConfig>,
+    state: Arc<Mutex<CacheState>>,
+}
+
+/// A cacheable command, prepared by [`LocalCache::lookup`].
+pub(crate) struct CacheMiss {
+    packed: Vec<u8>,
+    keys: Vec<Vec<u8>>,
+    epoch: u64,
+}
+
+impl LocalCache {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        LocalCache {
+            config: Arc::new(config),
+            state: Default::default(),
+        }
+    }
+
+    /// Prepares the cache for a new connection, returning the sender its
+    /// push messages should be routed to.  Everything cached so far is
+    /// dropped since the server does not track it for the new connection.
+    pub(crate) fn connect(&self) -> PushSender {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut state = self.state.lock().unwrap();
+        state.flush();
+        state.invalidations = Some(receiver);
+        sender
+    }
+
+    /// The command enabling tracking on a new connection.
+    pub(crate) fn tracking_cmd(&self) -> Cmd {
+        let mut command = cmd("CLIENT");
+        command.arg("TRACKING").arg("ON");
+        match &self.config.mode {
+            TrackingMode::Default => {}
+            TrackingMode::Broadcast(prefixes) => {
+                command.arg("BCAST");
+                for prefix in prefixes {
+                    command.arg("PREFIX").arg(prefix);
+                }
+            }
+            TrackingMode::OptIn => {
+                command.arg("OPTIN");
+            }
+        }
+        command
+    }
+
+    /// Returns true if `CLIENT CACHING YES` must be sent before the command.
+    pub(crate) fn is_opt_in(&self) -> bool {
+        self.config.mode == TrackingMode::OptIn
+    }
+
+    /// Returns the cached reply of `cmd`, or what is needed to store its reply
+    /// if it is cacheable.
+    pub(crate) fn lookup(&self, cmd: &Cmd) -> Result<Value, Option<CacheMiss>> {
+        let keys = match self.cacheable_keys(cmd) {
+            Some(keys) => keys,
+            None => return Err(None),
+        };
+        let packed = cmd.get_packed_command();
+        let mut state = self.state.lock().unwrap();
+        state.drain_invalidations();
+        match state.entries.get(&packed) {
+            Some((value, _)) => Ok(value.clone()),
+            None => Err(Some(CacheMiss {
+                packed,
+                keys,
+                epoch: state.epoch,
+            })),
+        }
+    }
+
+    /// Stores the reply of a command which missed the cache.
+    pub(crate) fn insert(&self, miss: CacheMiss, value: &Value) {
+        let mut state = self.state.lock().unwrap();
+        state.drain_invalidations();
+        if state.epoch != miss.epoch || state.entries.contains_key(&miss.packed) {
+            return;
+        }
+        while state.entries.len() >= self.config.max_entries {
+            match state.order.pop_front() {
+                Some(oldest) => state.remove_entry(&oldest),
+                None => break,
+            }
+        }
+        if self.config.max_entries == 0 {
+            return;
+        }
+        for key in &miss.keys {
+            state
+                .keys
+                .entry(key.clone())
+                .or_default()
+                .insert(miss.packed.clone());
+        }
+        state.order.push_back(miss.packed.clone());
+        state
+            .entries
+            .insert(miss.packed, (value.clone(), miss.keys));
+
+        // invalidated entries are not removed from `order` right away
+        if state.order.len() > 2 * self.config.max_entries {
+            let CacheState { order, entries, .. } = &mut *state;
+            order.retain(|packed| entries.contains_key(packed));
+        }
+    }
+
+    /// Evicts the replies which might depend on the arguments of a command
+    /// that is not cacheable.
+    ///
+    /// Invalidations caused by the connection itself are only sent after the
+    /// reply of the command, this makes sure the connection reads its own
+    /// writes.
+    pub(crate) fn evict_args(&self, cmd: &Cmd) {
+        let mut state = self.state.lock().unwrap();
+        if state.keys.is_empty() {
+            return;
+        }
+        for arg in cmd.args_iter().skip(1) {
+            if let Arg::Simple(arg) = arg {
+                if state.keys.contains_key(arg) {
+                    state.invalidate_key(arg);
+                }
+            }
+        }
+    }
+
+    fn cacheable_keys(&self, cmd: &Cmd) -> Option<Vec<Vec<u8>>> {
+        let mut args = Vec::with_capacity(cmd.args_iter().len());
+        for arg in cmd.args_iter() {
+            match arg {
+                Arg::Simple(arg) => args.push(arg),
+                Arg::Cursor => return None,
+            }
+        }
+        let (name, args) = args.split_first()?;
+        let name = name.to_ascii_uppercase();
+        // Only commands whose reply is fully determined by the contents of
+        // their keys are cached.  Anything else, such as `TTL` or `SCAN`,
+        // could be served stale since no invalidation is sent for it.
+        let keys: &[&[u8]] = match &name[..] {
+            b"MGET" | b"EXISTS" | b"SDIFF" | b"SINTER" | b"SUNION" | b"PFCOUNT" => args,
+            b"LCS" => args.get(..2)?,
+            b"SINTERCARD" | b"ZDIFF" | b"ZINTER" | b"ZUNION" | b"ZINTERCARD" => {
+                let numkeys: usize = std::str::from_utf8(args.first()?).ok()?.parse().ok()?;
+                args.get(1..1 + numkeys)?
+            }
+            b"BITCOUNT"
+            | b"BITFIELD_RO"
+            | b"BITPOS"
+            | b"EXPIRETIME"
+            | b"GEODIST"
+            | b"GEOHASH"
+            | b"GEOPOS"
+            | b"GEORADIUSBYMEMBER_RO"
+            | b"GEORADIUS_RO"
+            | b"GEOSEARCH"
+            | b"GET"
+            | b"GETBIT"
+            | b"GETRANGE"
+            | b"HEXISTS"
+            | b"HEXPIRETIME"
+            | b"HGET"
+            | b"HGETALL"
+            | b"HKEYS"
+            | b"HLEN"
+            | b"HMGET"
+            | b"HPEXPIRETIME"
+            | b"HSTRLEN"
+            | b"HVALS"
+            | b"LINDEX"
+            | b"LLEN"
+            | b"LPOS"
+            | b"LRANGE"
+            | b"PEXPIRETIME"
+            | b"SCARD"
+            | b"SISMEMBER"
+            | b"SMEMBERS"
+            | b"SMISMEMBER"
+            | b"STRLEN"
+            | b"SUBSTR"
+            | b"TYPE"
+            | b"XLEN"
+            | b"XRANGE"
+            | b"XREVRANGE"
+            | b"ZCARD"
+            | b"ZCOUNT"
+            | b"ZLEXCOUNT"
+            | b"ZMSCORE"
+            | b"ZRANGE"
+            | b"ZRANGEBYLEX"
+            | b"ZRANGEBYSCORE"
+            | b"ZRANK"
+            | b"ZREVRANGE"
+            | b"ZREVRANGEBYLEX"
+            | b"ZREVRANGEBYSCORE"
+            | b"ZREVRANK"
+            | b"ZSCORE" => args.get(..1)?,
+            _ => return None,
+        };
+        if let TrackingMode::Broadcast(prefixes) = &self.config.mode {
+            let tracked = |key: &&[u8]| {
+                prefixes.is_empty()
+                    || prefixes
+                        .iter()
+                        .any(|prefix| key.starts_with(prefix.as_bytes()))
+            };
+            if !keys.iter().all(tracked) {
+                return None;
+            }
+        }
+        Some(keys.iter().map(|key| key.to_vec()).collect())
+    }
+}
+
+pub(crate) fn check_protocol(protocol: crate::ProtocolVersion) -> RedisResult<()> {
+    if protocol != crate::ProtocolVersion::RESP3 {
+        fail!((
+            ErrorKind::InvalidClientConfig,
+            "Client side caching requires the RESP3 protocol"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalidate(sender: &PushSender, keys: Option<&[&str]>) {
+        let data = match keys {
+            Some(keys) => Value::Bulk(
+                keys.iter()
+                    .map(|key| Value::Data(key.as_bytes().to_vec()))
+                    .collect(),
+            ),
+            None => Value::Nil,
+        };
+        sender
+            .send(PushInfo {
+                kind: PushKind::Invalidate,
+                data: vec![data],
+            })
+            .unwrap();
+    }
+
+    fn cache_reply(cache: &LocalCache, command: &Cmd, value: Value) {
+        match cache.lookup(command) {
+            Err(Some(miss)) => cache.insert(miss, &value),
+            _ => panic!("expected a cacheable miss"),
+        }
+    }
+
+    #[test]
+    fn caches_until_invalidated() {
+        let cache = LocalCache::new(CacheConfig::default());
+        let sender = cache.connect();
+        let get = cmd("GET").arg("foo").clone();
+        cache_reply(&cache, &get, Value::Int(1));
+        assert_eq!(cache.lookup(&get).ok(), Some(Value::Int(1)));
+
+        invalidate(&sender, Some(&["foo"]));
+        assert!(matches!(cache.lookup(&get), Err(Some(_))));
+    }
+
+    #[test]
+    fn multi_key_commands_depend_on_every_key() {
+        let cache = LocalCache::new(CacheConfig::default());
+        let sender = cache.connect();
+        let mget = cmd("MGET").arg("a").arg("b").clone();
+        cache_reply(&cache, &mget, Value::Bulk(vec![Value::Nil, Value::Nil]));
+
+        invalidate(&sender, Some(&["b"]));
+        assert!(matches!(cache.lookup(&mget), Err(Some(_))));
+    }
+
+    #[test]
+    fn null_invalidation_flushes() {
+        let cache = LocalCache::new(CacheConfig::default());
+        let sender = cache.connect();
+        let get = cmd("GET").arg("foo").clone();
+        cache_reply(&cache, &get, Value::Int(1));
+
+        invalidate(&sender, None);
+        assert!(matches!(cache.lookup(&get), Err(Some(_))));
+    }
+
+    #[test]
+    fn replies_racing_with_invalidations_are_not_stored() {
+        let cache = LocalCache::new(CacheConfig::default());
+        let sender = cache.connect();
+        let get = cmd("GET").arg("foo").clone();
+        let miss = match cache.lookup(&get) {
+            Err(Some(miss)) => miss,
+            _ => panic!("expected a cacheable miss"),
+        };
+        invalidate(&sender, Some(&["foo"]));
+        cache.insert(miss, &Value::Int(1));
+        assert!(matches!(cache.lookup(&get), Err(Some(_))));
+    }
+
+    #[test]
+    fn writes_evict_their_keys() {
+        let cache = LocalCache::new(CacheConfig::default());
+        let _sender = cache.connect();
+        let get = cmd("GET").arg("foo").clone();
+        cache_reply(&cache, &get, Value::Int(1));
+
+        cache.evict_args(cmd("SET").arg("foo").arg(2));
+        assert!(matches!(cache.lookup(&get), Err(Some(_))));
+    }
+
+    #[test]
+    fn only_cacheable_commands_are_cached() {
+        let cache = LocalCache::new(CacheConfig {
+            mode: TrackingMode::Broadcast(vec!["user:".to_string()]),
+            ..Default::default()
+        });
+        let _sender = cache.connect();
+        assert!(matches!(cache.lookup(cmd("SET").arg("user:1")), Err(None)));
+        assert!(matches!(cache.lookup(cmd("TTL").arg("user:1")), Err(None)));
+        // the remaining time to live of hash fields changes on its own too
+        for name in ["HTTL", "HPTTL"] {
+            let command = cmd(name)
+                .arg("user:1")
+                .arg("FIELDS")
+                .arg(1)
+                .arg("f")
+                .clone();
+            assert!(matches!(cache.lookup(&command), Err(None)));
+        }
+        assert!(matches!(
+            cache.lookup(
+                cmd("HEXPIRETIME")
+                    .arg("user:1")
+                    .arg("FIELDS")
+                    .arg(1)
+                    .arg("f")
+            ),
+            Err(Some(_))
+        ));
+        assert!(matches!(
+            cache.lookup(cmd("OBJECT").arg("FREQ").arg("user:1")),
+            Err(None)
+        ));
+        assert!(matches!(cache.lookup(cmd("GET").arg("other")), Err(None)));
+        assert!(matches!(
+            cache.lookup(cmd("GET").arg("user:1")),
+            Err(Some(_))
+        ));
+    }
+
+    #[test]
+    fn max_entries_evicts_oldest() {
+        let cache = LocalCache::new(CacheConfig {
+            max_entries: 1,
+            ..Default::default()
+        });
+        let _sender = cache.connect();
+        let first = cmd("GET").arg("a").clone();
+        let second = cmd("GET").arg("b").clone();
+        cache_reply(&cache, &first, Value::Int(1));
+        cache_reply(&cache, &second, Value::Int(2));
+        assert!(matches!(cache.lookup(&first), Err(Some(_))));
+        assert_eq!(cache.lookup(&second).ok(), Some(Value::Int(2)));
+    }
+
+    #[test]
+    fn tracking_command() {
+        let cache = LocalCache::new(CacheConfig {
+            mode: TrackingMode::Broadcast(vec!["a".to_string(), "b".to_string()]),
+            ..Default::default()
+        });
+        assert_eq!(
+            cache.tracking_cmd().get_packed_command(),
+            cmd("CLIENT")
+                .arg("TRACKING")
+                .arg("ON")
+                .arg("BCAST")
+                .arg("PREFIX")
+                .arg("a")
+                .arg("PREFIX")
+                .arg("b")
+                .get_packed_command()
+        );
+    }
+}
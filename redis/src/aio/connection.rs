@@ -6,8 +6,10 @@ use crate::cmd::{cmd, Cmd};
 use crate::connection::{ConnectionAddr, ConnectionInfo, Msg, RedisConnectionInfo};
 #[cfg(any(feature = "tokio-comp", feature = "async-std-comp"))]
 use crate::parser::ValueCodec;
-use crate::types::{ErrorKind, FromRedisValue, RedisError, RedisFuture, RedisResult, Value};
-use crate::{from_redis_value, ToRedisArgs};
+use crate::types::{
+    ErrorKind, FromRedisValue, ParserLimits, RedisError, RedisFuture, RedisResult, Value,
+};
+use crate::ToRedisArgs;
 #[cfg(all(not(feature = "tokio-comp"), feature = "async-std-comp"))]
 use ::async_std::net::ToSocketAddrs;
 use ::tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
@@ -29,6 +31,7 @@ pub struct Connection<C = Pin<Box<dyn AsyncStream + Send + Sync>>> {
     con: C,
     buf: Vec<u8>,
     decoder: combine::stream::Decoder<AnySendSyncPartialState, PointerOffset<[u8]>>,
+    parser_limits: ParserLimits,
     db: i64,
 
     // Flag indicating whether the connection was left in the PubSub state after dropping `PubSub`.
@@ -51,6 +54,7 @@ impl<C> Connection<C> {
             con,
             buf,
             decoder,
+            parser_limits,
             db,
             pubsub,
         } = self;
@@ -58,6 +62,7 @@ impl<C> Connection<C> {
             con: f(con),
             buf,
             decoder,
+            parser_limits,
             db,
             pubsub,
         }
@@ -75,6 +80,7 @@ where
             con,
             buf: Vec::new(),
             decoder: combine::stream::Decoder::new(),
+            parser_limits: connection_info.parser_limits,
             db: connection_info.db,
             pubsub: false,
         };
@@ -94,7 +100,12 @@ where
 
     /// Fetches a single response from the connection.
     async fn read_response(&mut self) -> RedisResult<Value> {
-        crate::parser::parse_redis_value_async(&mut self.decoder, &mut self.con).await
+        crate::parser::parse_redis_value_async_with_limits(
+            &mut self.decoder,
+            &mut self.con,
+            self.parser_limits,
+        )
+        .await
     }
 
     /// Brings [`Connection`] out of `PubSub` mode.
@@ -144,7 +155,7 @@ where
         let mut received_unsub = false;
         let mut received_punsub = false;
         loop {
-            let res: (Vec<u8>, (), isize) = from_redis_value(&self.read_response().await?)?;
+            let res = crate::connection::parse_unsubscribe_reply(self.read_response().await?)?;
 
             match res.0.first() {
                 Some(&b'u') => received_unsub = true,
@@ -218,17 +229,17 @@ where
 
             let mut first_err = None;
 
-            for _ in 0..offset {
+            for idx in 0..offset {
                 let response = self.read_response().await;
                 if let Err(err) = response {
                     if first_err.is_none() {
-                        first_err = Some(err);
+                        first_err = Some(err.with_pipeline_index(idx));
                     }
                 }
             }
 
             let mut rv = Vec::with_capacity(count);
-            for _ in 0..count {
+            for idx in offset..offset + count {
                 let response = self.read_response().await;
                 match response {
                     Ok(item) => {
@@ -236,7 +247,7 @@ where
                     }
                     Err(err) => {
                         if first_err.is_none() {
-                            first_err = Some(err);
+                            first_err = Some(err.with_pipeline_index(idx));
                         }
                     }
                 }
@@ -304,7 +315,7 @@ where
     /// The message itself is still generic and can be converted into an appropriate type through
     /// the helper methods on it.
     pub fn on_message(&mut self) -> impl Stream<Item = Msg> + '_ {
-        ValueCodec::default()
+        ValueCodec::with_limits(self.0.parser_limits)
             .framed(&mut self.0.con)
             .filter_map(|msg| Box::pin(async move { Msg::from_value(&msg.ok()?.ok()?) }))
     }
@@ -316,7 +327,7 @@ where
     /// This can be useful in cases where the stream needs to be returned or held by something other
     /// than the [`PubSub`].
     pub fn into_on_message(self) -> impl Stream<Item = Msg> {
-        ValueCodec::default()
+        ValueCodec::with_limits(self.0.parser_limits)
             .framed(self.0.con)
             .filter_map(|msg| Box::pin(async move { Msg::from_value(&msg.ok()?.ok()?) }))
     }
@@ -345,7 +356,7 @@ where
 
     /// Returns [`Stream`] of [`FromRedisValue`] values from this [`Monitor`]ing connection
     pub fn on_message<T: FromRedisValue>(&mut self) -> impl Stream<Item = T> + '_ {
-        ValueCodec::default()
+        ValueCodec::with_limits(self.0.parser_limits)
             .framed(&mut self.0.con)
             .filter_map(|value| {
                 Box::pin(async move { T::from_redis_value(&value.ok()?.ok()?).ok() })
@@ -354,7 +365,7 @@ where
 
     /// Returns [`Stream`] of [`FromRedisValue`] values from this [`Monitor`]ing connection
     pub fn into_on_message<T: FromRedisValue>(self) -> impl Stream<Item = T> {
-        ValueCodec::default()
+        ValueCodec::with_limits(self.0.parser_limits)
             .framed(self.0.con)
             .filter_map(|value| {
                 Box::pin(async move { T::from_redis_value(&value.ok()?.ok()?).ok() })
@@ -1,4 +1,6 @@
 use super::RedisFuture;
+#[cfg(feature = "cache-aio")]
+use crate::aio::{cache::LocalCache, CacheConfig};
 use crate::cmd::Cmd;
 use crate::types::{RedisError, RedisResult, Value};
 use crate::{
@@ -55,6 +57,8 @@ pub struct ConnectionManager {
     runtime: Runtime,
     retry_strategy: ExponentialBackoff,
     number_of_retries: usize,
+    #[cfg(feature = "cache-aio")]
+    cache: Option<LocalCache>,
 }
 
 /// A `RedisResult` that can be cloned because `RedisError` is behind an `Arc`.
@@ -119,13 +123,56 @@ impl ConnectionManager {
         exponent_base: u64,
         factor: u64,
         number_of_retries: usize,
+    ) -> RedisResult<Self> {
+        Self::new_inner(
+            client,
+            exponent_base,
+            factor,
+            number_of_retries,
+            #[cfg(feature = "cache-aio")]
+            None,
+        )
+        .await
+    }
+
+    /// Connect to the server and store the connection inside the returned `ConnectionManager`.
+    ///
+    /// The replies of read-only commands are served from a local cache, kept up to date
+    /// through `CLIENT TRACKING`.  The cache is flushed whenever the manager reconnects.
+    /// The connection must use [`ProtocolVersion::RESP3`](crate::ProtocolVersion).
+    #[cfg(feature = "cache-aio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cache-aio")))]
+    pub async fn new_with_cache(client: Client, config: CacheConfig) -> RedisResult<Self> {
+        crate::aio::cache::check_protocol(client.connection_info().redis.protocol)?;
+        Self::new_inner(
+            client,
+            Self::DEFAULT_CONNECTION_RETRY_EXPONENT_BASE,
+            Self::DEFAULT_CONNECTION_RETRY_FACTOR,
+            Self::DEFAULT_NUMBER_OF_CONNECTION_RETRIESE,
+            Some(LocalCache::new(config)),
+        )
+        .await
+    }
+
+    async fn new_inner(
+        client: Client,
+        exponent_base: u64,
+        factor: u64,
+        number_of_retries: usize,
+        #[cfg(feature = "cache-aio")] cache: Option<LocalCache>,
     ) -> RedisResult<Self> {
         // Create a MultiplexedConnection and wait for it to be established
 
         let runtime = Runtime::locate();
         let retry_strategy = ExponentialBackoff::from_millis(exponent_base).factor(factor);
-        let connection =
-            Self::new_connection(client.clone(), retry_strategy.clone(), number_of_retries).await?;
+        let connection = Self::new_connection(
+            client.clone(),
+            retry_strategy.clone(),
+            number_of_retries,
+            #[cfg(feature = "cache-aio")]
+            cache.clone(),
+        )
+        .await?;
 
         // Wrap the connection in an `ArcSwap` instance for fast atomic access
         Ok(Self {
@@ -136,15 +183,26 @@ impl ConnectionManager {
             runtime,
             number_of_retries,
             retry_strategy,
+            #[cfg(feature = "cache-aio")]
+            cache,
         })
     }
 
+    #[allow(deprecated)]
     async fn new_connection(
         client: Client,
         exponential_backoff: ExponentialBackoff,
         number_of_retries: usize,
+        #[cfg(feature = "cache-aio")] cache: Option<LocalCache>,
     ) -> RedisResult<MultiplexedConnection> {
         let retry_strategy = exponential_backoff.map(jitter).take(number_of_retries);
+        #[cfg(feature = "cache-aio")]
+        if let Some(cache) = cache {
+            return Retry::spawn(retry_strategy, || {
+                client.get_cached_multiplexed_async_connection(cache.clone())
+            })
+            .await;
+        }
         Retry::spawn(retry_strategy, || client.get_multiplexed_async_connection()).await
     }
 
@@ -156,8 +214,17 @@ impl ConnectionManager {
         let client = self.client.clone();
         let retry_strategy = self.retry_strategy.clone();
         let number_of_retries = self.number_of_retries;
+        #[cfg(feature = "cache-aio")]
+        let cache = self.cache.clone();
         let new_connection: SharedRedisFuture<MultiplexedConnection> = async move {
-            Ok(Self::new_connection(client, retry_strategy, number_of_retries).await?)
+            Ok(Self::new_connection(
+                client,
+                retry_strategy,
+                number_of_retries,
+                #[cfg(feature = "cache-aio")]
+                cache,
+            )
+            .await?)
         }
         .boxed()
         .shared();
@@ -1,7 +1,7 @@
 //! Adds async IO support to redis.
 use crate::cmd::{cmd, Cmd};
 use crate::connection::RedisConnectionInfo;
-use crate::types::{ErrorKind, RedisFuture, RedisResult, Value};
+use crate::types::{ErrorKind, ProtocolVersion, RedisFuture, RedisResult, Value};
 use ::tokio::io::{AsyncRead, AsyncWrite};
 use async_trait::async_trait;
 use futures_util::Future;
@@ -84,7 +84,12 @@ async fn setup_connection<C>(connection_info: &RedisConnectionInfo, con: &mut C)
 where
     C: ConnectionLike,
 {
-    if let Some(password) = &connection_info.password {
+    if connection_info.protocol == ProtocolVersion::RESP3 {
+        crate::connection::hello_cmd(connection_info)
+            .query_async::<_, Value>(con)
+            .await
+            .map_err(crate::connection::hello_error)?;
+    } else if let Some(password) = &connection_info.password {
         let mut command = cmd("AUTH");
         if let Some(username) = &connection_info.username {
             command.arg(username);
@@ -143,6 +148,11 @@ where
     Ok(())
 }
 
+#[cfg(feature = "cache-aio")]
+pub(crate) mod cache;
+#[cfg(feature = "cache-aio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cache-aio")))]
+pub use cache::{CacheConfig, TrackingMode};
 mod connection;
 pub use connection::*;
 mod multiplexed_connection;
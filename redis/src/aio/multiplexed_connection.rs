@@ -1,10 +1,12 @@
 use super::ConnectionLike;
+#[cfg(feature = "cache-aio")]
+use crate::aio::cache::{self, LocalCache};
 use crate::aio::setup_connection;
 use crate::cmd::Cmd;
 use crate::connection::RedisConnectionInfo;
 #[cfg(any(feature = "tokio-comp", feature = "async-std-comp"))]
 use crate::parser::ValueCodec;
-use crate::types::{RedisError, RedisFuture, RedisResult, Value};
+use crate::types::{PushInfo, RedisError, RedisFuture, RedisResult, Value};
 use ::tokio::{
     io::{AsyncRead, AsyncWrite},
     sync::{mpsc, oneshot},
@@ -28,24 +30,61 @@ use tokio_util::codec::Decoder;
 // Senders which the result of a single request are sent through
 type PipelineOutput<O, E> = oneshot::Sender<Result<Vec<O>, E>>;
 
+/// A sender which receives the RESP3 push messages of a connection.
+pub type PushSender = mpsc::UnboundedSender<PushInfo>;
+
+/// Errors which can record the position of the failed reply within a
+/// request that expects several replies.
+trait ReplyError {
+    fn at_reply(self, index: usize) -> Self;
+}
+
+impl ReplyError for RedisError {
+    fn at_reply(self, index: usize) -> Self {
+        if self.is_io_error() {
+            self
+        } else {
+            self.with_pipeline_index(index)
+        }
+    }
+}
+
 struct InFlight<O, E> {
     output: PipelineOutput<O, E>,
     expected_response_count: usize,
     current_response_count: usize,
+    // The number of (un)subscribe confirmations making up each response, see
+    // `subscription_confirmations`.  Missing entries are regular replies.
+    confirmations: Vec<usize>,
+    received_confirmations: usize,
     buffer: Vec<O>,
     first_err: Option<E>,
 }
 
 impl<O, E> InFlight<O, E> {
-    fn new(output: PipelineOutput<O, E>, expected_response_count: usize) -> Self {
+    fn new(
+        output: PipelineOutput<O, E>,
+        expected_response_count: usize,
+        confirmations: Vec<usize>,
+    ) -> Self {
         Self {
             output,
             expected_response_count,
             current_response_count: 0,
+            confirmations,
+            received_confirmations: 0,
             buffer: Vec::new(),
             first_err: None,
         }
     }
+
+    // The number of confirmations expected for the current response
+    fn expected_confirmations(&self) -> usize {
+        self.confirmations
+            .get(self.current_response_count)
+            .copied()
+            .unwrap_or(0)
+    }
 }
 
 // A single message sent through the pipeline
@@ -53,6 +92,29 @@ struct PipelineMessage<S, I, E> {
     input: S,
     output: PipelineOutput<I, E>,
     response_count: usize,
+    confirmations: Vec<usize>,
+}
+
+/// Returns the number of RESP3 push messages which confirm `cmd`, or `0` if it
+/// is not a (un)subscribe command.
+///
+/// The server confirms each channel separately, so `SUBSCRIBE a b c` is
+/// answered by three pushes.  When called without channels the unsubscribe
+/// commands are confirmed once per current subscription, which is not known
+/// here, so only the first confirmation is waited for and the remaining ones
+/// are merely forwarded to the push sender.
+fn subscription_confirmations(cmd: &Cmd) -> usize {
+    match cmd
+        .arg_idx(0)
+        .map(|name| name.to_ascii_uppercase())
+        .as_deref()
+    {
+        Some(
+            b"SUBSCRIBE" | b"PSUBSCRIBE" | b"SSUBSCRIBE" | b"UNSUBSCRIBE" | b"PUNSUBSCRIBE"
+            | b"SUNSUBSCRIBE",
+        ) => cmd.args_iter().len().saturating_sub(1).max(1),
+        _ => 0,
+    }
 }
 
 /// Wrapper around a `Stream + Sink` where each item sent through the `Sink` results in one or more
@@ -79,34 +141,38 @@ where
 }
 
 pin_project! {
-    struct PipelineSink<T, I, E> {
+    struct PipelineSink<T, E> {
         #[pin]
         sink_stream: T,
-        in_flight: VecDeque<InFlight<I, E>>,
+        in_flight: VecDeque<InFlight<Value, E>>,
         error: Option<E>,
+        push_sender: Option<PushSender>,
     }
 }
 
-impl<T, I, E> PipelineSink<T, I, E>
+impl<T, E> PipelineSink<T, E>
 where
-    T: Stream<Item = Result<I, E>> + 'static,
+    T: Stream<Item = Result<Value, E>> + 'static,
+    E: ReplyError,
 {
-    fn new<SinkItem>(sink_stream: T) -> Self
+    fn new<SinkItem>(sink_stream: T, push_sender: Option<PushSender>) -> Self
     where
-        T: Sink<SinkItem, Error = E> + Stream<Item = Result<I, E>> + 'static,
+        T: Sink<SinkItem, Error = E> + Stream<Item = Result<Value, E>> + 'static,
     {
         PipelineSink {
             sink_stream,
             in_flight: VecDeque::new(),
             error: None,
+            push_sender,
         }
     }
 
     // Read messages from the stream and send them back to the caller
     fn poll_read(mut self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<Result<(), ()>> {
         loop {
-            // No need to try reading a message if there is no message in flight
-            if self.in_flight.is_empty() {
+            // No need to try reading a message if there is no message in flight, unless push
+            // messages may arrive at any time
+            if self.in_flight.is_empty() && self.push_sender.is_none() {
                 return Poll::Ready(Ok(()));
             }
             let item = match ready!(self.as_mut().project().sink_stream.poll_next(cx)) {
@@ -119,22 +185,62 @@ where
         }
     }
 
-    fn send_result(self: Pin<&mut Self>, result: Result<I, E>) {
+    fn send_result(self: Pin<&mut Self>, result: Result<Value, E>) {
         let self_ = self.project();
 
+        // Push messages are not replies to the requests in flight, except for the
+        // (un)subscribe confirmations which are both.
+        let mut is_confirmation = false;
+        let result = match result {
+            Ok(Value::Push { kind, data }) => {
+                if let Some(push_sender) = self_.push_sender {
+                    // The receiver might be gone, in which case the message is dropped
+                    let _ = push_sender.send(PushInfo {
+                        kind: kind.clone(),
+                        data: data.clone(),
+                    });
+                }
+                if !kind.has_reply() {
+                    return;
+                }
+                is_confirmation = true;
+                Ok(Value::Push { kind, data })
+            }
+            result => result,
+        };
+
         {
             let entry = match self_.in_flight.front_mut() {
                 Some(entry) => entry,
                 None => return,
             };
 
+            if is_confirmation {
+                let expected = entry.expected_confirmations();
+                // Confirmations the current request does not wait for, such as
+                // server initiated `sunsubscribe` pushes, are only forwarded
+                if expected == 0 {
+                    return;
+                }
+                entry.received_confirmations += 1;
+                if entry.received_confirmations < expected {
+                    return;
+                }
+            }
+            // The last confirmation, or an error, is the reply of the command
+            entry.received_confirmations = 0;
+
             match result {
                 Ok(item) => {
                     entry.buffer.push(item);
                 }
                 Err(err) => {
                     if entry.first_err.is_none() {
-                        entry.first_err = Some(err);
+                        entry.first_err = Some(if entry.expected_response_count > 1 {
+                            err.at_reply(entry.current_response_count)
+                        } else {
+                            err
+                        });
                     }
                 }
             }
@@ -159,9 +265,10 @@ where
     }
 }
 
-impl<SinkItem, T, I, E> Sink<PipelineMessage<SinkItem, I, E>> for PipelineSink<T, I, E>
+impl<SinkItem, T, E> Sink<PipelineMessage<SinkItem, Value, E>> for PipelineSink<T, E>
 where
-    T: Sink<SinkItem, Error = E> + Stream<Item = Result<I, E>> + 'static,
+    T: Sink<SinkItem, Error = E> + Stream<Item = Result<Value, E>> + 'static,
+    E: ReplyError,
 {
     type Error = ();
 
@@ -185,7 +292,8 @@ where
             input,
             output,
             response_count,
-        }: PipelineMessage<SinkItem, I, E>,
+            confirmations,
+        }: PipelineMessage<SinkItem, Value, E>,
     ) -> Result<(), Self::Error> {
         // If there is nothing to receive our output we do not need to send the message as it is
         // ambiguous whether the message will be sent anyway. Helps shed some load on the
@@ -205,7 +313,7 @@ where
             Ok(()) => {
                 self_
                     .in_flight
-                    .push_back(InFlight::new(output, response_count));
+                    .push_back(InFlight::new(output, response_count, confirmations));
                 Ok(())
             }
             Err(err) => {
@@ -246,15 +354,14 @@ where
     }
 }
 
-impl<SinkItem, I, E> Pipeline<SinkItem, I, E>
+impl<SinkItem, E> Pipeline<SinkItem, Value, E>
 where
     SinkItem: Send + 'static,
-    I: Send + 'static,
-    E: Send + 'static,
+    E: ReplyError + Send + 'static,
 {
-    fn new<T>(sink_stream: T) -> (Self, impl Future<Output = ()>)
+    fn new<T>(sink_stream: T, push_sender: Option<PushSender>) -> (Self, impl Future<Output = ()>)
     where
-        T: Sink<SinkItem, Error = E> + Stream<Item = Result<I, E>> + 'static,
+        T: Sink<SinkItem, Error = E> + Stream<Item = Result<Value, E>> + 'static,
         T: Send + 'static,
         T::Item: Send,
         T::Error: Send,
@@ -264,14 +371,14 @@ where
         let (sender, mut receiver) = mpsc::channel(BUFFER_SIZE);
         let f = stream::poll_fn(move |cx| receiver.poll_recv(cx))
             .map(Ok)
-            .forward(PipelineSink::new::<SinkItem>(sink_stream))
+            .forward(PipelineSink::new::<SinkItem>(sink_stream, push_sender))
             .map(|_| ());
         (Pipeline(sender), f)
     }
 
     // `None` means that the stream was out of items causing that poll loop to shut down.
-    async fn send(&mut self, item: SinkItem) -> Result<I, Option<E>> {
-        self.send_recv_multiple(item, 1)
+    async fn send(&mut self, item: SinkItem, confirmations: usize) -> Result<Value, Option<E>> {
+        self.send_recv_multiple(item, 1, vec![confirmations])
             .await
             // We can unwrap since we do a request for `1` item
             .map(|mut item| item.pop().unwrap())
@@ -281,13 +388,15 @@ where
         &mut self,
         input: SinkItem,
         count: usize,
-    ) -> Result<Vec<I>, Option<E>> {
+        confirmations: Vec<usize>,
+    ) -> Result<Vec<Value>, Option<E>> {
         let (sender, receiver) = oneshot::channel();
 
         self.0
             .send(PipelineMessage {
                 input,
                 response_count: count,
+                confirmations,
                 output: sender,
             })
             .await
@@ -309,6 +418,8 @@ where
 pub struct MultiplexedConnection {
     pipeline: Pipeline<Vec<u8>, Value, RedisError>,
     db: i64,
+    #[cfg(feature = "cache-aio")]
+    cache: Option<LocalCache>,
 }
 
 impl Debug for MultiplexedConnection {
@@ -327,6 +438,58 @@ impl MultiplexedConnection {
         connection_info: &RedisConnectionInfo,
         stream: C,
     ) -> RedisResult<(Self, impl Future<Output = ()>)>
+    where
+        C: Unpin + AsyncRead + AsyncWrite + Send + 'static,
+    {
+        Self::new_with_push_sender(connection_info, stream, None).await
+    }
+
+    /// Constructs a new `MultiplexedConnection` out of a `AsyncRead + AsyncWrite` object
+    /// and a `ConnectionInfo`.
+    ///
+    /// RESP3 push messages (pubsub messages, client tracking invalidations, ...) received by
+    /// the connection are sent through `push_sender` instead of being treated as replies.
+    /// Without a push sender they are dropped.
+    pub async fn new_with_push_sender<C>(
+        connection_info: &RedisConnectionInfo,
+        stream: C,
+        push_sender: Option<PushSender>,
+    ) -> RedisResult<(Self, impl Future<Output = ()>)>
+    where
+        C: Unpin + AsyncRead + AsyncWrite + Send + 'static,
+    {
+        Self::new_inner(
+            connection_info,
+            stream,
+            push_sender,
+            #[cfg(feature = "cache-aio")]
+            None,
+        )
+        .await
+    }
+
+    /// Constructs a new `MultiplexedConnection` whose read-only commands are
+    /// served from `cache`, with `CLIENT TRACKING` enabled.
+    #[cfg(feature = "cache-aio")]
+    pub(crate) async fn new_with_cache<C>(
+        connection_info: &RedisConnectionInfo,
+        stream: C,
+        cache: LocalCache,
+    ) -> RedisResult<(Self, impl Future<Output = ()>)>
+    where
+        C: Unpin + AsyncRead + AsyncWrite + Send + 'static,
+    {
+        cache::check_protocol(connection_info.protocol)?;
+        let push_sender = cache.connect();
+        Self::new_inner(connection_info, stream, Some(push_sender), Some(cache)).await
+    }
+
+    async fn new_inner<C>(
+        connection_info: &RedisConnectionInfo,
+        stream: C,
+        push_sender: Option<PushSender>,
+        #[cfg(feature = "cache-aio")] cache: Option<LocalCache>,
+    ) -> RedisResult<(Self, impl Future<Output = ()>)>
     where
         C: Unpin + AsyncRead + AsyncWrite + Send + 'static,
     {
@@ -339,17 +502,26 @@ impl MultiplexedConnection {
         #[cfg(all(not(feature = "tokio-comp"), not(feature = "async-std-comp")))]
         compile_error!("tokio-comp or async-std-comp features required for aio feature");
 
-        let codec = ValueCodec::default()
+        let codec = ValueCodec::with_limits(connection_info.parser_limits)
             .framed(stream)
             .and_then(|msg| async move { msg });
-        let (pipeline, driver) = Pipeline::new(codec);
+        let (pipeline, driver) = Pipeline::new(codec, push_sender);
         let driver = boxed(driver);
         let mut con = MultiplexedConnection {
             pipeline,
             db: connection_info.db,
+            #[cfg(feature = "cache-aio")]
+            cache: None,
         };
         let driver = {
-            let auth = setup_connection(connection_info, &mut con);
+            let auth = async {
+                setup_connection(connection_info, &mut con).await?;
+                #[cfg(feature = "cache-aio")]
+                if let Some(cache) = &cache {
+                    cache.tracking_cmd().query_async::<_, ()>(&mut con).await?;
+                }
+                Ok::<_, RedisError>(())
+            };
             futures_util::pin_mut!(auth);
 
             match futures_util::future::select(auth, driver).await {
@@ -362,15 +534,61 @@ impl MultiplexedConnection {
                 }
             }
         };
+        #[cfg(feature = "cache-aio")]
+        {
+            con.cache = cache;
+        }
         Ok((con, driver))
     }
 
     /// Sends an already encoded (packed) command into the TCP socket and
     /// reads the single response from it.
     pub async fn send_packed_command(&mut self, cmd: &Cmd) -> RedisResult<Value> {
+        #[cfg(feature = "cache-aio")]
+        if let Some(cache) = self.cache.clone() {
+            return self.send_cached_command(&cache, cmd).await;
+        }
+        self.send_uncached_command(cmd).await
+    }
+
+    #[cfg(feature = "cache-aio")]
+    async fn send_cached_command(&mut self, cache: &LocalCache, cmd: &Cmd) -> RedisResult<Value> {
+        let miss = match cache.lookup(cmd) {
+            Ok(value) => return Ok(value),
+            Err(Some(miss)) => miss,
+            Err(None) => {
+                let result = self.send_uncached_command(cmd).await;
+                cache.evict_args(cmd);
+                return result;
+            }
+        };
+        let value = if cache.is_opt_in() {
+            let mut packed = crate::cmd("CLIENT")
+                .arg("CACHING")
+                .arg("YES")
+                .get_packed_command();
+            cmd.write_packed_command(&mut packed);
+            self.pipeline
+                .send_recv_multiple(packed, 2, Vec::new())
+                .await
+                .map(|mut values| values.pop().unwrap())
+                .map_err(|err| match err {
+                    // The caller sent a single command, the `CLIENT CACHING`
+                    // request is an implementation detail
+                    Some(err) => err.without_pipeline_index(),
+                    None => RedisError::from(io::Error::from(io::ErrorKind::BrokenPipe)),
+                })?
+        } else {
+            self.send_uncached_command(cmd).await?
+        };
+        cache.insert(miss, &value);
+        Ok(value)
+    }
+
+    async fn send_uncached_command(&mut self, cmd: &Cmd) -> RedisResult<Value> {
         let value = self
             .pipeline
-            .send(cmd.get_packed_command())
+            .send(cmd.get_packed_command(), subscription_confirmations(cmd))
             .await
             .map_err(|err| {
                 err.unwrap_or_else(|| RedisError::from(io::Error::from(io::ErrorKind::BrokenPipe)))
@@ -387,13 +605,30 @@ impl MultiplexedConnection {
         offset: usize,
         count: usize,
     ) -> RedisResult<Vec<Value>> {
-        let mut value = self
+        // Transactions reply with a single `EXEC` array, which is never a push
+        let confirmations = if offset == 0 {
+            cmd.cmd_iter().map(subscription_confirmations).collect()
+        } else {
+            Vec::new()
+        };
+        let result = self
             .pipeline
-            .send_recv_multiple(cmd.get_packed_pipeline(), offset + count)
-            .await
-            .map_err(|err| {
-                err.unwrap_or_else(|| RedisError::from(io::Error::from(io::ErrorKind::BrokenPipe)))
-            })?;
+            .send_recv_multiple(cmd.get_packed_pipeline(), offset + count, confirmations)
+            .await;
+        // Pipelines are never served from the cache, but they may write keys
+        // whose replies are cached
+        #[cfg(feature = "cache-aio")]
+        if let Some(cache) = &self.cache {
+            for cmd in cmd.cmd_iter() {
+                cache.evict_args(cmd);
+            }
+        }
+        let mut value = result.map_err(|err| match err {
+            // A single reply is not tagged by the pipeline sink
+            Some(err) if offset + count == 1 => err.at_reply(0),
+            Some(err) => err,
+            None => RedisError::from(io::Error::from(io::ErrorKind::BrokenPipe)),
+        })?;
 
         value.drain(..offset);
         Ok(value)
@@ -132,6 +132,83 @@ impl Client {
         }
     }
 
+    /// Returns an async multiplexed connection from the client, which sends the RESP3 push
+    /// messages it receives through `push_sender`.
+    ///
+    /// This allows a single connection to serve both commands and notifications such as
+    /// pubsub messages or client tracking invalidations.  Push messages are only sent by
+    /// the server when the connection uses [`ProtocolVersion::RESP3`](crate::ProtocolVersion).
+    #[cfg(any(feature = "tokio-comp", feature = "async-std-comp"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "tokio-comp", feature = "async-std-comp")))
+    )]
+    pub async fn get_multiplexed_async_connection_with_push_sender(
+        &self,
+        push_sender: crate::aio::PushSender,
+    ) -> RedisResult<crate::aio::MultiplexedConnection> {
+        match Runtime::locate() {
+            #[cfg(feature = "tokio-comp")]
+            Runtime::Tokio => {
+                self.get_multiplexed_async_connection_inner::<crate::aio::tokio::Tokio>(Some(
+                    push_sender,
+                ))
+                .await
+            }
+            #[cfg(feature = "async-std-comp")]
+            Runtime::AsyncStd => {
+                self.get_multiplexed_async_connection_inner::<crate::aio::async_std::AsyncStd>(
+                    Some(push_sender),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Returns an async multiplexed connection from the client, which serves the replies of
+    /// read-only commands from a local cache.
+    ///
+    /// The connection enables `CLIENT TRACKING` and evicts cached replies when the server
+    /// reports that their keys changed.  The cache is shared by all clones of the connection.
+    /// The connection must use [`ProtocolVersion::RESP3`](crate::ProtocolVersion).
+    #[cfg(all(
+        feature = "cache-aio",
+        any(feature = "tokio-comp", feature = "async-std-comp")
+    ))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cache-aio")))]
+    pub async fn get_multiplexed_async_connection_with_cache(
+        &self,
+        config: crate::aio::CacheConfig,
+    ) -> RedisResult<crate::aio::MultiplexedConnection> {
+        self.get_cached_multiplexed_async_connection(crate::aio::cache::LocalCache::new(config))
+            .await
+    }
+
+    #[cfg(all(
+        feature = "cache-aio",
+        any(feature = "tokio-comp", feature = "async-std-comp")
+    ))]
+    pub(crate) async fn get_cached_multiplexed_async_connection(
+        &self,
+        cache: crate::aio::cache::LocalCache,
+    ) -> RedisResult<crate::aio::MultiplexedConnection> {
+        match Runtime::locate() {
+            #[cfg(feature = "tokio-comp")]
+            Runtime::Tokio => {
+                self.get_cached_multiplexed_async_connection_inner::<crate::aio::tokio::Tokio>(
+                    cache,
+                )
+                .await
+            }
+            #[cfg(feature = "async-std-comp")]
+            Runtime::AsyncStd => self
+                .get_cached_multiplexed_async_connection_inner::<crate::aio::async_std::AsyncStd>(
+                    cache,
+                )
+                .await,
+        }
+    }
+
     /// Returns an async multiplexed connection from the client.
     ///
     /// A multiplexed connection can be cloned, allowing requests to be be sent concurrently
@@ -141,7 +218,7 @@ impl Client {
     pub async fn get_multiplexed_tokio_connection(
         &self,
     ) -> RedisResult<crate::aio::MultiplexedConnection> {
-        self.get_multiplexed_async_connection_inner::<crate::aio::tokio::Tokio>()
+        self.get_multiplexed_async_connection_inner::<crate::aio::tokio::Tokio>(None)
             .await
     }
 
@@ -154,7 +231,7 @@ impl Client {
     pub async fn get_multiplexed_async_std_connection(
         &self,
     ) -> RedisResult<crate::aio::MultiplexedConnection> {
-        self.get_multiplexed_async_connection_inner::<crate::aio::async_std::AsyncStd>()
+        self.get_multiplexed_async_connection_inner::<crate::aio::async_std::AsyncStd>(None)
             .await
     }
 
@@ -171,7 +248,7 @@ impl Client {
         crate::aio::MultiplexedConnection,
         impl std::future::Future<Output = ()>,
     )> {
-        self.create_multiplexed_async_connection_inner::<crate::aio::tokio::Tokio>()
+        self.create_multiplexed_async_connection_inner::<crate::aio::tokio::Tokio>(None)
             .await
     }
 
@@ -188,7 +265,7 @@ impl Client {
         crate::aio::MultiplexedConnection,
         impl std::future::Future<Output = ()>,
     )> {
-        self.create_multiplexed_async_connection_inner::<crate::aio::async_std::AsyncStd>()
+        self.create_multiplexed_async_connection_inner::<crate::aio::async_std::AsyncStd>(None)
             .await
     }
 
@@ -239,6 +316,25 @@ impl Client {
         crate::aio::ConnectionManager::new(self.clone()).await
     }
 
+    /// Returns an async [`ConnectionManager`][connection-manager] from the client, which
+    /// serves the replies of read-only commands from a local cache.
+    ///
+    /// See [`Client::get_multiplexed_async_connection_with_cache`] for how the cache works.
+    /// The cache is flushed whenever the manager reconnects.
+    ///
+    /// [connection-manager]: aio/struct.ConnectionManager.html
+    #[cfg(all(feature = "connection-manager", feature = "cache-aio"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(feature = "connection-manager", feature = "cache-aio")))
+    )]
+    pub async fn get_connection_manager_with_cache(
+        &self,
+        config: crate::aio::CacheConfig,
+    ) -> RedisResult<crate::aio::ConnectionManager> {
+        crate::aio::ConnectionManager::new_with_cache(self.clone(), config).await
+    }
+
     /// Returns an async [`ConnectionManager`][connection-manager] from the client.
     ///
     /// The connection manager wraps a
@@ -310,19 +406,40 @@ impl Client {
 
     async fn get_multiplexed_async_connection_inner<T>(
         &self,
+        push_sender: Option<crate::aio::PushSender>,
     ) -> RedisResult<crate::aio::MultiplexedConnection>
     where
         T: crate::aio::RedisRuntime,
     {
         let (connection, driver) = self
-            .create_multiplexed_async_connection_inner::<T>()
+            .create_multiplexed_async_connection_inner::<T>(push_sender)
             .await?;
         T::spawn(driver);
         Ok(connection)
     }
 
+    #[cfg(feature = "cache-aio")]
+    async fn get_cached_multiplexed_async_connection_inner<T>(
+        &self,
+        cache: crate::aio::cache::LocalCache,
+    ) -> RedisResult<crate::aio::MultiplexedConnection>
+    where
+        T: crate::aio::RedisRuntime,
+    {
+        let con = self.get_simple_async_connection::<T>().await?;
+        let (connection, driver) = crate::aio::MultiplexedConnection::new_with_cache(
+            &self.connection_info.redis,
+            con,
+            cache,
+        )
+        .await?;
+        T::spawn(driver);
+        Ok(connection)
+    }
+
     async fn create_multiplexed_async_connection_inner<T>(
         &self,
+        push_sender: Option<crate::aio::PushSender>,
     ) -> RedisResult<(
         crate::aio::MultiplexedConnection,
         impl std::future::Future<Output = ()>,
@@ -331,7 +448,12 @@ impl Client {
         T: crate::aio::RedisRuntime,
     {
         let con = self.get_simple_async_connection::<T>().await?;
-        crate::aio::MultiplexedConnection::new(&self.connection_info.redis, con).await
+        crate::aio::MultiplexedConnection::new_with_push_sender(
+            &self.connection_info.redis,
+            con,
+            push_sender,
+        )
+        .await
     }
 
     async fn get_simple_async_connection<T>(
@@ -354,10 +476,10 @@ impl Client {
     ///
     /// - `conn_info` - URL using the `rediss://` scheme.
     /// - `tls_certs` - `TlsCertificates` structure containing:
-    /// -- `client_tls` - Optional `ClientTlsConfig` containing byte streams for
-    /// --- `client_cert` - client's byte stream containing client certificate in PEM format
-    /// --- `client_key` - client's byte stream containing private key in PEM format
-    /// -- `root_cert` - Optional byte stream yielding PEM formatted file for root certificates.
+    ///   - `client_tls` - Optional `ClientTlsConfig` containing byte streams for
+    ///     - `client_cert` - client's byte stream containing client certificate in PEM format
+    ///     - `client_key` - client's byte stream containing private key in PEM format
+    ///   - `root_cert` - Optional byte stream yielding PEM formatted file for root certificates.
     ///
     /// If `ClientTlsConfig` ( cert+key pair ) is not provided, then client-side authentication is not enabled.
     /// If `root_cert` is not provided, then system root certificates are used instead.
@@ -411,9 +533,9 @@ impl Client {
     ///
     ///     let mut con = client.get_async_connection().await?;
     ///
-    ///     con.set("key1", b"foo").await?;
+    ///     let _: () = con.set("key1", b"foo").await?;
     ///
-    ///     redis::cmd("SET")
+    ///     let _: () = redis::cmd("SET")
     ///         .arg(&["key2", "bar"])
     ///         .query_async(&mut con)
     ///         .await?;
@@ -53,7 +53,9 @@ use crate::connection::{
     connect, Connection, ConnectionAddr, ConnectionInfo, ConnectionLike, RedisConnectionInfo,
 };
 use crate::parser::parse_redis_value;
-use crate::types::{ErrorKind, HashMap, RedisError, RedisResult, Value};
+use crate::types::{
+    ErrorKind, HashMap, ParserLimits, ProtocolVersion, RedisError, RedisResult, Value,
+};
 use crate::IntoConnectionInfo;
 pub use crate::TlsMode; // Pub for backwards compatibility
 use crate::{
@@ -102,6 +104,11 @@ impl<'a> Input<'a> {
                 .map(Output::Multi),
         }
     }
+
+    fn command_name(&self) -> Option<String> {
+        self.command()
+            .map(|name| String::from_utf8_lossy(&name).into_owned())
+    }
 }
 
 impl<'a> Routable for Input<'a> {
@@ -214,6 +221,8 @@ pub struct ClusterConnection<C = Connection> {
     read_from_replicas: bool,
     username: Option<String>,
     password: Option<String>,
+    protocol: ProtocolVersion,
+    parser_limits: ParserLimits,
     read_timeout: RefCell<Option<Duration>>,
     write_timeout: RefCell<Option<Duration>>,
     tls: Option<TlsMode>,
@@ -237,6 +246,8 @@ where
             read_from_replicas: cluster_params.read_from_replicas,
             username: cluster_params.username,
             password: cluster_params.password,
+            protocol: cluster_params.protocol,
+            parser_limits: cluster_params.parser_limits,
             read_timeout: RefCell::new(None),
             write_timeout: RefCell::new(None),
             tls: cluster_params.tls,
@@ -432,6 +443,8 @@ where
         let params = ClusterParams {
             password: self.password.clone(),
             username: self.username.clone(),
+            protocol: self.protocol,
+            parser_limits: self.parser_limits,
             tls: self.tls,
             ..Default::default()
         };
@@ -440,7 +453,7 @@ where
         let mut conn = C::connect(info, None)?;
         if self.read_from_replicas {
             // If READONLY is sent to primary nodes, it will have no effect
-            cmd("READONLY").query(&mut conn)?;
+            cmd("READONLY").query::<()>(&mut conn)?;
         }
         conn.set_read_timeout(*self.read_timeout.borrow())?;
         conn.set_write_timeout(*self.write_timeout.borrow())?;
@@ -537,7 +550,7 @@ where
         // TODO: reconnect and shit
         let results = addresses.iter().enumerate().map(|(index, addr)| {
             if let Some(connection) = connections.get_mut(*addr) {
-                match &routing {
+                let result = match &routing {
                     MultipleNodeRoutingInfo::MultiSlot(vec) => {
                         let (_, indices) = vec.get(index).unwrap();
                         let cmd = crate::cluster_routing::command_for_multi_slot_indices(
@@ -560,7 +573,11 @@ where
                         )
                             .into()),
                     },
-                }
+                };
+                result.map_err(|err| {
+                    err.with_command_name(input.command_name())
+                        .with_node_address(addr)
+                })
             } else {
                 Err((
                     ErrorKind::IoError,
@@ -698,6 +715,9 @@ where
             match rv {
                 Ok(rv) => return Ok(rv),
                 Err(err) => {
+                    let err = err
+                        .with_command_name(input.command_name())
+                        .with_node_address(&addr);
                     if retries == self.retry_params.number_of_retries {
                         return Err(err);
                     }
@@ -717,7 +737,10 @@ where
                                 .redirect_node()
                                 .map(|(node, _slot)| Redirect::Moved(node.to_string()));
                         }
-                        ErrorKind::TryAgain | ErrorKind::ClusterDown => {
+                        ErrorKind::TryAgain
+                        | ErrorKind::ClusterDown
+                        | ErrorKind::Busy
+                        | ErrorKind::NoReplicas => {
                             // Sleep and retry.
                             let sleep_time = self.retry_params.wait_time_for_retry(retries);
                             thread::sleep(sleep_time);
@@ -750,7 +773,7 @@ where
 
         let to_retry = self
             .send_all_commands(cmds)
-            .and_then(|node_cmds| self.recv_all_commands(&mut results, &node_cmds))?;
+            .and_then(|node_cmds| self.recv_all_commands(&mut results, cmds, &node_cmds))?;
 
         if to_retry.is_empty() {
             return Ok(results);
@@ -764,7 +787,10 @@ where
         // retry logic that handles these cases.
         for retry_idx in to_retry {
             let cmd = &cmds[retry_idx];
-            results[retry_idx] = self.request(Input::Cmd(cmd))?.into();
+            results[retry_idx] = self
+                .request(Input::Cmd(cmd))
+                .map_err(|err| err.with_pipeline_index(retry_idx))?
+                .into();
         }
         Ok(results)
     }
@@ -785,6 +811,7 @@ where
     fn recv_all_commands(
         &self,
         results: &mut [Value],
+        cmds: &[Cmd],
         node_cmds: &[NodeCmd],
     ) -> RedisResult<Vec<usize>> {
         let mut to_retry = Vec::new();
@@ -799,7 +826,15 @@ where
                 {
                     Ok(item) => results[*cmd_idx] = item,
                     Err(err) if err.is_cluster_error() => to_retry.push(*cmd_idx),
-                    Err(err) => first_err = first_err.or(Some(err)),
+                    Err(err) => {
+                        first_err = first_err.or_else(|| {
+                            Some(
+                                err.with_command(&cmds[*cmd_idx])
+                                    .with_pipeline_index(*cmd_idx)
+                                    .with_node_address(&nc.addr),
+                            )
+                        })
+                    }
                 }
             }
         }
@@ -1009,6 +1044,8 @@ pub(crate) fn get_connection_info(
         redis: RedisConnectionInfo {
             password: cluster_params.password,
             username: cluster_params.username,
+            protocol: cluster_params.protocol,
+            parser_limits: cluster_params.parser_limits,
             ..Default::default()
         },
     })
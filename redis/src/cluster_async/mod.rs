@@ -400,7 +400,10 @@ where
                             .map(|(node, _slot)| Redirect::Moved(node.to_string()));
                         Next::RefreshSlots { request }.into()
                     }
-                    ErrorKind::TryAgain | ErrorKind::ClusterDown => {
+                    ErrorKind::TryAgain
+                    | ErrorKind::ClusterDown
+                    | ErrorKind::Busy
+                    | ErrorKind::NoReplicas => {
                         // Sleep and retry.
                         let sleep_duration = this.retry_params.wait_time_for_retry(request.retry);
                         this.future.set(RequestState::Sleep {
@@ -809,14 +812,22 @@ where
                 // commands that have concrete connections, and don't require redirection, are handled here.
                 CommandRouting::Connection { addr, conn } => {
                     let mut conn = conn.await;
-                    let result = conn.req_packed_command(&cmd).await.map(Response::Single);
+                    let result = conn
+                        .req_packed_command(&cmd)
+                        .await
+                        .map(Response::Single)
+                        .map_err(|err| err.with_command(&cmd).with_node_address(&addr));
                     return (addr.into(), result);
                 }
             }
         };
 
         let (addr, mut conn) = Self::get_connection(redirect, route, core, asking).await;
-        let result = conn.req_packed_command(&cmd).await.map(Response::Single);
+        let result = conn
+            .req_packed_command(&cmd)
+            .await
+            .map(Response::Single)
+            .map_err(|err| err.with_command(&cmd).with_node_address(&addr));
         (addr.into(), result)
     }
 
@@ -830,7 +841,8 @@ where
         let result = conn
             .req_packed_commands(&pipeline, offset, count)
             .await
-            .map(Response::Multiple);
+            .map(Response::Multiple)
+            .map_err(|err| err.with_node_address(&addr));
         (OperationTarget::Node { address: addr }, result)
     }
 
@@ -1272,7 +1284,9 @@ where
     check_connection(&mut conn).await?;
     if read_from_replicas {
         // If READONLY is sent to primary nodes, it will have no effect
-        crate::cmd("READONLY").query_async(&mut conn).await?;
+        crate::cmd("READONLY")
+            .query_async::<_, ()>(&mut conn)
+            .await?;
     }
     Ok(conn)
 }
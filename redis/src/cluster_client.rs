@@ -3,7 +3,7 @@ use std::time::Duration;
 use rand::Rng;
 
 use crate::connection::{ConnectionAddr, ConnectionInfo, IntoConnectionInfo};
-use crate::types::{ErrorKind, RedisError, RedisResult};
+use crate::types::{ErrorKind, ParserLimits, ProtocolVersion, RedisError, RedisResult};
 use crate::{cluster, TlsMode};
 
 #[cfg(feature = "tls-rustls")]
@@ -25,6 +25,8 @@ use crate::tls::{retrieve_tls_certificates, TlsCertificates};
 struct BuilderParams {
     password: Option<String>,
     username: Option<String>,
+    protocol: Option<ProtocolVersion>,
+    parser_limits: Option<ParserLimits>,
     read_from_replicas: bool,
     tls: Option<TlsMode>,
     #[cfg(feature = "tls-rustls")]
@@ -74,6 +76,8 @@ impl RetryParams {
 pub(crate) struct ClusterParams {
     pub(crate) password: Option<String>,
     pub(crate) username: Option<String>,
+    pub(crate) protocol: ProtocolVersion,
+    pub(crate) parser_limits: ParserLimits,
     pub(crate) read_from_replicas: bool,
     /// tls indicates tls behavior of connections.
     /// When Some(TlsMode), connections use tls and verify certification depends on TlsMode.
@@ -87,6 +91,8 @@ impl From<BuilderParams> for ClusterParams {
         Self {
             password: value.password,
             username: value.username,
+            protocol: value.protocol.unwrap_or_default(),
+            parser_limits: ParserLimits::default(),
             read_from_replicas: value.read_from_replicas,
             tls: value.tls,
             retry_params: value.retries_configuration,
@@ -155,7 +161,17 @@ impl ClusterClientBuilder {
             }
         };
 
+        let protocol = self
+            .builder_params
+            .protocol
+            .unwrap_or(first_node.redis.protocol);
+        let parser_limits = self
+            .builder_params
+            .parser_limits
+            .unwrap_or(first_node.redis.parser_limits);
         let mut cluster_params: ClusterParams = self.builder_params.into();
+        cluster_params.protocol = protocol;
+        cluster_params.parser_limits = parser_limits;
         let password = if cluster_params.password.is_none() {
             cluster_params.password = first_node.redis.password.clone();
             &cluster_params.password
@@ -226,6 +242,23 @@ impl ClusterClientBuilder {
         self
     }
 
+    /// Sets the protocol version used by the connections of the new ClusterClient.
+    ///
+    /// If not set, the protocol of the first initial node is used.
+    pub fn use_protocol(mut self, protocol: ProtocolVersion) -> ClusterClientBuilder {
+        self.builder_params.protocol = Some(protocol);
+        self
+    }
+
+    /// Sets the limits enforced while parsing the replies of the connections
+    /// of the new ClusterClient.
+    ///
+    /// If not set, the limits of the first initial node are used.
+    pub fn parser_limits(mut self, parser_limits: ParserLimits) -> ClusterClientBuilder {
+        self.builder_params.parser_limits = Some(parser_limits);
+        self
+    }
+
     /// Sets number of retries for the new ClusterClient.
     pub fn retries(mut self, retries: u32) -> ClusterClientBuilder {
         self.builder_params.retries_configuration.number_of_retries = retries;
@@ -269,10 +302,10 @@ impl ClusterClientBuilder {
     /// checked during `build()` call.
     ///
     /// - `certificates` - `TlsCertificates` structure containing:
-    /// -- `client_tls` - Optional `ClientTlsConfig` containing byte streams for
-    /// --- `client_cert` - client's byte stream containing client certificate in PEM format
-    /// --- `client_key` - client's byte stream containing private key in PEM format
-    /// -- `root_cert` - Optional byte stream yielding PEM formatted file for root certificates.
+    ///   - `client_tls` - Optional `ClientTlsConfig` containing byte streams for
+    ///     - `client_cert` - client's byte stream containing client certificate in PEM format
+    ///     - `client_key` - client's byte stream containing private key in PEM format
+    ///   - `root_cert` - Optional byte stream yielding PEM formatted file for root certificates.
     ///
     /// If `ClientTlsConfig` ( cert+key pair ) is not provided, then client-side authentication is not enabled.
     /// If `root_cert` is not provided, then system root certificates are used instead.
@@ -498,6 +531,21 @@ mod tests {
         assert_eq!(client.cluster_params.username, Some("user1".to_string()));
     }
 
+    #[test]
+    fn give_parser_limits_by_method() {
+        let limits = crate::ParserLimits {
+            max_array_length: 10,
+            ..Default::default()
+        };
+        let client = ClusterClient::new(get_connection_data()).unwrap();
+        assert_eq!(client.cluster_params.parser_limits, Default::default());
+        let client = ClusterClientBuilder::new(get_connection_data())
+            .parser_limits(limits)
+            .build()
+            .unwrap();
+        assert_eq!(client.cluster_params.parser_limits, limits);
+    }
+
     #[test]
     fn give_empty_initial_nodes() {
         let client = ClusterClient::new(Vec::<String>::new());
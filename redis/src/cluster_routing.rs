@@ -206,7 +206,7 @@ pub(crate) fn combine_array_results(values: Vec<Value>) -> RedisResult<Value> {
 /// the results in the final array.
 pub(crate) fn combine_and_sort_array_results<'a>(
     values: Vec<Value>,
-    sorting_order: impl Iterator<Item = &'a Vec<usize>> + ExactSizeIterator,
+    sorting_order: impl ExactSizeIterator<Item = &'a Vec<usize>>,
 ) -> RedisResult<Value> {
     let mut results = Vec::new();
     results.resize(
@@ -388,7 +388,10 @@ impl RoutingInfo {
             // TODO - special handling - b"SCAN"
             b"SCAN" | b"CLIENT SETNAME" | b"SHUTDOWN" | b"SLAVEOF" | b"REPLICAOF" | b"MOVE"
             | b"BITOP" => None,
-            b"EVALSHA" | b"EVAL" => {
+            b"FUNCTION DUMP" | b"FUNCTION LIST" => {
+                Some(RoutingInfo::SingleNode(SingleNodeRoutingInfo::Random))
+            }
+            b"EVALSHA" | b"EVAL" | b"FCALL" | b"FCALL_RO" => {
                 let key_count = r
                     .arg_idx(2)
                     .and_then(|x| std::str::from_utf8(x).ok())
@@ -399,6 +402,17 @@ impl RoutingInfo {
                     r.arg_idx(3).map(|key| RoutingInfo::for_key(cmd, key))
                 }
             }
+            b"MIGRATE" => match r.arg_idx(3)? {
+                b"" => {
+                    let keys_position = r.position(b"KEYS")?;
+                    r.arg_idx(keys_position + 1)
+                        .map(|key| RoutingInfo::for_key(cmd, key))
+                }
+                key => Some(RoutingInfo::for_key(cmd, key)),
+            },
+            b"ZINTER" | b"ZUNION" | b"ZDIFF" | b"ZINTERCARD" | b"SINTERCARD" => {
+                r.arg_idx(2).map(|key| RoutingInfo::for_key(cmd, key))
+            }
             b"XGROUP CREATE"
             | b"XGROUP CREATECONSUMER"
             | b"XGROUP DELCONSUMER"
@@ -432,26 +446,7 @@ pub trait Routable {
     /// Convenience function to return ascii uppercase version of the
     /// the first argument (i.e., the command).
     fn command(&self) -> Option<Vec<u8>> {
-        let primary_command = self.arg_idx(0).map(|x| x.to_ascii_uppercase())?;
-        let mut primary_command = match primary_command.as_slice() {
-            b"XGROUP" | b"OBJECT" | b"SLOWLOG" | b"FUNCTION" | b"MODULE" | b"COMMAND"
-            | b"PUBSUB" | b"CONFIG" | b"MEMORY" | b"XINFO" | b"CLIENT" | b"ACL" | b"SCRIPT"
-            | b"CLUSTER" | b"LATENCY" => primary_command,
-            _ => {
-                return Some(primary_command);
-            }
-        };
-
-        let secondary_command = self.arg_idx(1).map(|x| x.to_ascii_uppercase());
-        Some(match secondary_command {
-            Some(cmd) => {
-                primary_command.reserve(cmd.len() + 1);
-                primary_command.extend(b" ");
-                primary_command.extend(cmd);
-                primary_command
-            }
-            None => primary_command,
-        })
+        Some(crate::cmd::command_name(self.arg_idx(0)?, self.arg_idx(1)))
     }
 
     /// Returns a reference to the data for the argument at `idx`.
@@ -620,7 +615,7 @@ impl SlotMap {
         self.0.clear();
     }
 
-    pub fn values(&self) -> std::collections::btree_map::Values<u16, SlotAddrs> {
+    pub fn values(&self) -> std::collections::btree_map::Values<'_, u16, SlotAddrs> {
         self.0.values()
     }
 
@@ -674,16 +669,10 @@ impl Route {
 
 fn get_hashtag(key: &[u8]) -> Option<&[u8]> {
     let open = key.iter().position(|v| *v == b'{');
-    let open = match open {
-        Some(open) => open,
-        None => return None,
-    };
+    let open = open?;
 
     let close = key[open..].iter().position(|v| *v == b'}');
-    let close = match close {
-        Some(close) => close,
-        None => return None,
-    };
+    let close = close?;
 
     let rv = &key[open + 1..open + close];
     if rv.is_empty() {
@@ -833,7 +822,7 @@ mod tests {
             )))
         );
 
-        for cmd in vec![
+        for cmd in [
             cmd("SCAN"),
             cmd("CLIENT SETNAME"),
             cmd("SHUTDOWN"),
@@ -853,6 +842,8 @@ mod tests {
         for cmd in [
             cmd("EVAL").arg(r#"redis.call("PING");"#).arg(0),
             cmd("EVALSHA").arg(r#"redis.call("PING");"#).arg(0),
+            cmd("FCALL").arg("ping").arg(0),
+            cmd("FUNCTION").arg("LIST"),
         ] {
             assert_eq!(
                 RoutingInfo::for_routable(cmd),
@@ -870,6 +861,15 @@ mod tests {
                     SingleNodeRoutingInfo::SpecificNode(Route::new(slot(b"foo"), SlotAddr::Master)),
                 )),
             ),
+            (
+                cmd("FCALL_RO").arg("get").arg(1).arg("foo"),
+                Some(RoutingInfo::SingleNode(
+                    SingleNodeRoutingInfo::SpecificNode(Route::new(
+                        slot(b"foo"),
+                        SlotAddr::Replica,
+                    )),
+                )),
+            ),
             (
                 cmd("XGROUP")
                     .arg("CREATE")
@@ -923,6 +923,74 @@ mod tests {
                     )),
                 )),
             ),
+            (
+                cmd("BITFIELD_RO").arg("foo").arg("GET").arg("u8").arg(0),
+                Some(RoutingInfo::SingleNode(
+                    SingleNodeRoutingInfo::SpecificNode(Route::new(
+                        slot(b"foo"),
+                        SlotAddr::Replica,
+                    )),
+                )),
+            ),
+            (
+                cmd("ZINTER").arg(2).arg("{foo}1").arg("{foo}2"),
+                Some(RoutingInfo::SingleNode(
+                    SingleNodeRoutingInfo::SpecificNode(Route::new(
+                        slot(b"foo"),
+                        SlotAddr::Replica,
+                    )),
+                )),
+            ),
+            (
+                cmd("MIGRATE")
+                    .arg("host")
+                    .arg(6379)
+                    .arg("foo")
+                    .arg(0)
+                    .arg(5000),
+                Some(RoutingInfo::SingleNode(
+                    SingleNodeRoutingInfo::SpecificNode(Route::new(slot(b"foo"), SlotAddr::Master)),
+                )),
+            ),
+            (
+                cmd("MIGRATE")
+                    .arg("host")
+                    .arg(6379)
+                    .arg("")
+                    .arg(0)
+                    .arg(5000)
+                    .arg("REPLACE")
+                    .arg("KEYS")
+                    .arg("{foo}1")
+                    .arg("{foo}2"),
+                Some(RoutingInfo::SingleNode(
+                    SingleNodeRoutingInfo::SpecificNode(Route::new(slot(b"foo"), SlotAddr::Master)),
+                )),
+            ),
+            (
+                cmd("SINTERCARD").arg(2).arg("{foo}1").arg("{foo}2"),
+                Some(RoutingInfo::SingleNode(
+                    SingleNodeRoutingInfo::SpecificNode(Route::new(
+                        slot(b"foo"),
+                        SlotAddr::Replica,
+                    )),
+                )),
+            ),
+            (
+                cmd("SORT_RO").arg("foo").arg("ALPHA"),
+                Some(RoutingInfo::SingleNode(
+                    SingleNodeRoutingInfo::SpecificNode(Route::new(
+                        slot(b"foo"),
+                        SlotAddr::Replica,
+                    )),
+                )),
+            ),
+            (
+                cmd("BITFIELD").arg("foo").arg("GET").arg("u8").arg(0),
+                Some(RoutingInfo::SingleNode(
+                    SingleNodeRoutingInfo::SpecificNode(Route::new(slot(b"foo"), SlotAddr::Master)),
+                )),
+            ),
         ] {
             assert_eq!(
                 RoutingInfo::for_routable(cmd),
@@ -999,7 +1067,7 @@ mod tests {
             .arg("baz")
             .arg("{bar}vaz");
         let routing = RoutingInfo::for_routable(&original_cmd);
-        let expected = vec![vec![0], vec![1, 3], vec![2]];
+        let expected = [vec![0], vec![1, 3], vec![2]];
 
         let mut indices: Vec<_> = match routing {
             Some(RoutingInfo::MultiNode((MultipleNodeRoutingInfo::MultiSlot(vec), _))) => {
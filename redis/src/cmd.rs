@@ -10,7 +10,9 @@ use std::{fmt, io};
 
 use crate::connection::ConnectionLike;
 use crate::pipeline::Pipeline;
-use crate::types::{from_redis_value, FromRedisValue, RedisResult, RedisWrite, ToRedisArgs};
+use crate::types::{
+    from_owned_redis_value, from_redis_value, FromRedisValue, RedisResult, RedisWrite, ToRedisArgs,
+};
 
 /// An argument to a redis command
 #[derive(Clone)]
@@ -132,8 +134,8 @@ impl<'a, T: FromRedisValue + 'a + Unpin + Send> AsyncIter<'a, T> {
     /// # async fn scan_set() -> redis::RedisResult<()> {
     /// # let client = redis::Client::open("redis://127.0.0.1/")?;
     /// # let mut con = client.get_async_connection().await?;
-    /// con.sadd("my_set", 42i32).await?;
-    /// con.sadd("my_set", 43i32).await?;
+    /// let _: () = con.sadd("my_set", 42i32).await?;
+    /// let _: () = con.sadd("my_set", 43i32).await?;
     /// let mut iter: redis::AsyncIter<i32> = con.sscan("my_set").await?;
     /// while let Some(element) = iter.next_item().await {
     ///     assert!(element == 42 || element == 43);
@@ -418,7 +420,7 @@ impl Cmd {
     #[inline]
     pub fn query<T: FromRedisValue>(&self, con: &mut dyn ConnectionLike) -> RedisResult<T> {
         match con.req_command(self) {
-            Ok(val) => from_redis_value(&val),
+            Ok(val) => from_owned_redis_value(val),
             Err(e) => Err(e),
         }
     }
@@ -431,7 +433,7 @@ impl Cmd {
         C: crate::aio::ConnectionLike,
     {
         let val = con.req_packed_command(self).await?;
-        from_redis_value(&val)
+        from_owned_redis_value(val)
     }
 
     /// Similar to `query()` but returns an iterator over the items of the
@@ -527,7 +529,7 @@ impl Cmd {
     }
 
     /// Returns an iterator over the arguments in this command (including the command name itself)
-    pub fn args_iter(&self) -> impl Iterator<Item = Arg<&[u8]>> + Clone + ExactSizeIterator {
+    pub fn args_iter(&self) -> impl Clone + ExactSizeIterator<Item = Arg<&[u8]>> {
         let mut prev = 0;
         self.args.iter().map(move |arg| match *arg {
             Arg::Simple(i) => {
@@ -541,7 +543,6 @@ impl Cmd {
     }
 
     // Get a reference to the argument at `idx`
-    #[cfg(feature = "cluster")]
     pub(crate) fn arg_idx(&self, idx: usize) -> Option<&[u8]> {
         if idx >= self.args.len() {
             return None;
@@ -564,6 +565,31 @@ impl Cmd {
         }
         Some(&self.data[start..end])
     }
+
+    /// Returns the name of the command, see [`command_name`].
+    pub(crate) fn command_name(&self) -> Option<String> {
+        let name = command_name(self.arg_idx(0)?, self.arg_idx(1));
+        Some(String::from_utf8_lossy(&name).into_owned())
+    }
+}
+
+/// Returns the uppercase name of a command from its first two arguments.
+///
+/// For container commands such as `CONFIG` or `XINFO` this includes the
+/// subcommand.  No other arguments are ever included.
+pub(crate) fn command_name(first: &[u8], second: Option<&[u8]>) -> Vec<u8> {
+    let mut name = first.to_ascii_uppercase();
+    if let (
+        b"XGROUP" | b"OBJECT" | b"SLOWLOG" | b"FUNCTION" | b"MODULE" | b"COMMAND" | b"PUBSUB"
+        | b"CONFIG" | b"MEMORY" | b"XINFO" | b"CLIENT" | b"ACL" | b"SCRIPT" | b"CLUSTER"
+        | b"LATENCY",
+        Some(second),
+    ) = (name.as_slice(), second)
+    {
+        name.push(b' ');
+        name.extend(second.to_ascii_uppercase());
+    }
+    name
 }
 
 /// Shortcut function to creating a command with a single argument.
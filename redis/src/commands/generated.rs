@@ -0,0 +1,1044 @@
+// This file is generated by `cargo run -p redis-codegen` from
+// `redis-codegen/commands.json`.  Do not edit it by hand.
+#![cfg_attr(rustfmt, rustfmt_skip)]
+use crate::types::{RedisWrite, ToRedisArgs};
+
+/// Writes a token in front of every argument of the wrapped value, as
+/// needed by arguments like `PREFIX prefix [PREFIX prefix ...]`.
+pub(crate) struct Repeated<T>(pub(crate) &'static str, pub(crate) T);
+
+impl<T: ToRedisArgs> ToRedisArgs for Repeated<T> {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        for arg in self.1.to_redis_args() {
+            out.write_arg(self.0.as_bytes());
+            out.write_arg(&arg);
+        }
+    }
+}
+
+/// Passes the hand-written command entries on to `implement_commands!`,
+/// followed by the entries generated for the remaining commands of the
+/// spec.
+macro_rules! implement_commands_with_generated {
+    (
+        $lifetime: lifetime
+        $($entries:tt)*
+    ) => (
+        implement_commands! {
+            $lifetime
+            $($entries)*
+
+            // generated: cluster
+
+            /// Signals that a cluster client is following an -ASK redirect.
+            ///
+            /// `ASKING`
+            fn asking<>() {
+                &mut cmd("ASKING")
+            }
+
+            /// Assigns new hash slots to a node.
+            ///
+            /// `CLUSTER ADDSLOTS slot [slot ...]`
+            fn cluster_addslots<T: ToRedisArgs>(slot: T) {
+                cmd("CLUSTER").arg("ADDSLOTS").arg(slot)
+            }
+
+            /// Assigns new hash slot ranges to a node.
+            ///
+            /// `CLUSTER ADDSLOTSRANGE start-slot end-slot [start-slot end-slot ...]`
+            ///
+            /// These parameters are written as given and have to follow the syntax:
+            /// - `range`: `start-slot end-slot [start-slot end-slot ...]`
+            fn cluster_addslotsrange<T: ToRedisArgs>(range: T) {
+                cmd("CLUSTER").arg("ADDSLOTSRANGE").arg(range)
+            }
+
+            /// Advances the cluster config epoch.
+            ///
+            /// `CLUSTER BUMPEPOCH`
+            fn cluster_bumpepoch<>() {
+                cmd("CLUSTER").arg("BUMPEPOCH")
+            }
+
+            /// Returns the number of active failure reports active for a node.
+            ///
+            /// `CLUSTER COUNT-FAILURE-REPORTS node-id`
+            fn cluster_count_failure_reports<V: ToRedisArgs>(node_id: V) {
+                cmd("CLUSTER").arg("COUNT-FAILURE-REPORTS").arg(node_id)
+            }
+
+            /// Returns the number of keys in a hash slot.
+            ///
+            /// `CLUSTER COUNTKEYSINSLOT slot`
+            fn cluster_countkeysinslot<>(slot: i64) {
+                cmd("CLUSTER").arg("COUNTKEYSINSLOT").arg(slot)
+            }
+
+            /// Sets hash slots as unbound for a node.
+            ///
+            /// `CLUSTER DELSLOTS slot [slot ...]`
+            fn cluster_delslots<T: ToRedisArgs>(slot: T) {
+                cmd("CLUSTER").arg("DELSLOTS").arg(slot)
+            }
+
+            /// Sets hash slot ranges as unbound for a node.
+            ///
+            /// `CLUSTER DELSLOTSRANGE start-slot end-slot [start-slot end-slot ...]`
+            ///
+            /// These parameters are written as given and have to follow the syntax:
+            /// - `range`: `start-slot end-slot [start-slot end-slot ...]`
+            fn cluster_delslotsrange<T: ToRedisArgs>(range: T) {
+                cmd("CLUSTER").arg("DELSLOTSRANGE").arg(range)
+            }
+
+            /// Forces a replica to perform a manual failover of its master.
+            ///
+            /// `CLUSTER FAILOVER [<FORCE | TAKEOVER>]`
+            fn cluster_failover<>(options: Option<&$lifetime str>) {
+                cmd("CLUSTER").arg("FAILOVER").arg(options)
+            }
+
+            /// Deletes all slots information from a node.
+            ///
+            /// `CLUSTER FLUSHSLOTS`
+            fn cluster_flushslots<>() {
+                cmd("CLUSTER").arg("FLUSHSLOTS")
+            }
+
+            /// Removes a node from the nodes table.
+            ///
+            /// `CLUSTER FORGET node-id`
+            fn cluster_forget<V: ToRedisArgs>(node_id: V) {
+                cmd("CLUSTER").arg("FORGET").arg(node_id)
+            }
+
+            /// Returns the key names in a hash slot.
+            ///
+            /// `CLUSTER GETKEYSINSLOT slot count`
+            fn cluster_getkeysinslot<>(slot: i64, count: i64) {
+                cmd("CLUSTER").arg("GETKEYSINSLOT").arg(slot).arg(count)
+            }
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `CLUSTER HELP`
+            fn cluster_help<>() {
+                cmd("CLUSTER").arg("HELP")
+            }
+
+            /// Returns information about the state of a node.
+            ///
+            /// `CLUSTER INFO`
+            fn cluster_info<>() {
+                cmd("CLUSTER").arg("INFO")
+            }
+
+            /// Returns the hash slot for a key.
+            ///
+            /// `CLUSTER KEYSLOT key`
+            fn cluster_keyslot<V: ToRedisArgs>(key: V) {
+                cmd("CLUSTER").arg("KEYSLOT").arg(key)
+            }
+
+            /// Returns a list of all TCP links to and from peer nodes.
+            ///
+            /// `CLUSTER LINKS`
+            fn cluster_links<>() {
+                cmd("CLUSTER").arg("LINKS")
+            }
+
+            /// Forces a node to handshake with another node.
+            ///
+            /// `CLUSTER MEET ip port [cluster-bus-port]`
+            fn cluster_meet<V: ToRedisArgs>(ip: V, port: i64, cluster_bus_port: Option<i64>) {
+                cmd("CLUSTER").arg("MEET").arg(ip).arg(port).arg(cluster_bus_port)
+            }
+
+            /// Returns the ID of a node.
+            ///
+            /// `CLUSTER MYID`
+            fn cluster_myid<>() {
+                cmd("CLUSTER").arg("MYID")
+            }
+
+            /// Returns the shard ID of a node.
+            ///
+            /// `CLUSTER MYSHARDID`
+            fn cluster_myshardid<>() {
+                cmd("CLUSTER").arg("MYSHARDID")
+            }
+
+            /// Returns the cluster configuration for a node.
+            ///
+            /// `CLUSTER NODES`
+            fn cluster_nodes<>() {
+                cmd("CLUSTER").arg("NODES")
+            }
+
+            /// Lists the replica nodes of a master node.
+            ///
+            /// `CLUSTER REPLICAS node-id`
+            fn cluster_replicas<V: ToRedisArgs>(node_id: V) {
+                cmd("CLUSTER").arg("REPLICAS").arg(node_id)
+            }
+
+            /// Configure a node as replica of a master node.
+            ///
+            /// `CLUSTER REPLICATE node-id`
+            fn cluster_replicate<V: ToRedisArgs>(node_id: V) {
+                cmd("CLUSTER").arg("REPLICATE").arg(node_id)
+            }
+
+            /// Resets a node.
+            ///
+            /// `CLUSTER RESET [<HARD | SOFT>]`
+            fn cluster_reset<>(reset_type: Option<&$lifetime str>) {
+                cmd("CLUSTER").arg("RESET").arg(reset_type)
+            }
+
+            /// Forces a node to save the cluster configuration to disk.
+            ///
+            /// `CLUSTER SAVECONFIG`
+            fn cluster_saveconfig<>() {
+                cmd("CLUSTER").arg("SAVECONFIG")
+            }
+
+            /// Sets the configuration epoch for a new node.
+            ///
+            /// `CLUSTER SET-CONFIG-EPOCH config-epoch`
+            fn cluster_set_config_epoch<>(config_epoch: i64) {
+                cmd("CLUSTER").arg("SET-CONFIG-EPOCH").arg(config_epoch)
+            }
+
+            /// Binds a hash slot to a node.
+            ///
+            /// `CLUSTER SETSLOT slot <IMPORTING node-id | MIGRATING node-id | NODE node-id | STABLE>`
+            ///
+            /// These parameters are written as given and have to follow the syntax:
+            /// - `subcommand`: `<IMPORTING node-id | MIGRATING node-id | NODE node-id | STABLE>`
+            fn cluster_setslot<T: ToRedisArgs>(slot: i64, subcommand: T) {
+                cmd("CLUSTER").arg("SETSLOT").arg(slot).arg(subcommand)
+            }
+
+            /// Returns the mapping of cluster slots to shards.
+            ///
+            /// `CLUSTER SHARDS`
+            fn cluster_shards<>() {
+                cmd("CLUSTER").arg("SHARDS")
+            }
+
+            /// Lists the replica nodes of a master node.
+            ///
+            /// `CLUSTER SLAVES node-id`
+            ///
+            /// Deprecated by the server since 5.0.0, replaced by `CLUSTER REPLICAS`.
+            fn cluster_slaves<V: ToRedisArgs>(node_id: V) {
+                cmd("CLUSTER").arg("SLAVES").arg(node_id)
+            }
+
+            /// Returns the mapping of cluster slots to nodes.
+            ///
+            /// `CLUSTER SLOTS`
+            ///
+            /// Deprecated by the server since 7.0.0, replaced by `CLUSTER SHARDS`.
+            fn cluster_slots<>() {
+                cmd("CLUSTER").arg("SLOTS")
+            }
+
+            /// Enables read-only queries for a connection to a Redis Cluster replica node.
+            ///
+            /// `READONLY`
+            fn readonly<>() {
+                &mut cmd("READONLY")
+            }
+
+            /// Enables read-write queries for a connection to a Redis Cluster replica node.
+            ///
+            /// `READWRITE`
+            fn readwrite<>() {
+                &mut cmd("READWRITE")
+            }
+
+            // generated: connection
+
+            /// Instructs the server whether to track the keys in the next request.
+            ///
+            /// `CLIENT CACHING <YES | NO>`
+            fn client_caching<>(mode: &$lifetime str) {
+                cmd("CLIENT").arg("CACHING").arg(mode)
+            }
+
+            /// Returns the name of the connection.
+            ///
+            /// `CLIENT GETNAME`
+            fn client_getname<>() {
+                cmd("CLIENT").arg("GETNAME")
+            }
+
+            /// Returns the client ID to which the connection's tracking notifications are redirected.
+            ///
+            /// `CLIENT GETREDIR`
+            fn client_getredir<>() {
+                cmd("CLIENT").arg("GETREDIR")
+            }
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `CLIENT HELP`
+            fn client_help<>() {
+                cmd("CLIENT").arg("HELP")
+            }
+
+            /// Returns the unique client ID of the connection.
+            ///
+            /// `CLIENT ID`
+            fn client_id<>() {
+                cmd("CLIENT").arg("ID")
+            }
+
+            /// Returns information about the connection.
+            ///
+            /// `CLIENT INFO`
+            fn client_info<>() {
+                cmd("CLIENT").arg("INFO")
+            }
+
+            /// Terminates open connections.
+            ///
+            /// `CLIENT KILL <ip-port | <ID client-id | TYPE <NORMAL | MASTER | SLAVE | REPLICA | PUBSUB> | USER username | ADDR ip-port | LADDR ip-port | SKIPME <YES | NO>> [<ID client-id | TYPE <NORMAL | MASTER | SLAVE | REPLICA | PUBSUB> | USER username | ADDR ip-port | LADDR ip-port | SKIPME <YES | NO>> ...]>`
+            ///
+            /// These parameters are written as given and have to follow the syntax:
+            /// - `filter`: `<ip-port | <ID client-id | TYPE <NORMAL | MASTER | SLAVE | REPLICA | PUBSUB> | USER username | ADDR ip-port | LADDR ip-port | SKIPME <YES | NO>> [<ID client-id | TYPE <NORMAL | MASTER | SLAVE | REPLICA | PUBSUB> | USER username | ADDR ip-port | LADDR ip-port | SKIPME <YES | NO>> ...]>`
+            fn client_kill<T: ToRedisArgs>(filter: T) {
+                cmd("CLIENT").arg("KILL").arg(filter)
+            }
+
+            /// Lists open connections.
+            ///
+            /// `CLIENT LIST [TYPE <NORMAL | MASTER | REPLICA | PUBSUB>] [ID client-id [client-id ...]]`
+            fn client_list<T: ToRedisArgs>(client_type: Option<&$lifetime str>, client_id: Option<T>) {
+                cmd("CLIENT")
+                    .arg("LIST")
+                    .arg(client_type.map(|v| ("TYPE", v)))
+                    .arg(client_id.map(|v| ("ID", v)))
+            }
+
+            /// Sets the client eviction mode of the connection.
+            ///
+            /// `CLIENT NO-EVICT <ON | OFF>`
+            fn client_no_evict<>(enabled: &$lifetime str) {
+                cmd("CLIENT").arg("NO-EVICT").arg(enabled)
+            }
+
+            /// Controls whether commands sent by the client affect the LRU/LFU of accessed keys.
+            ///
+            /// `CLIENT NO-TOUCH <ON | OFF>`
+            fn client_no_touch<>(enabled: &$lifetime str) {
+                cmd("CLIENT").arg("NO-TOUCH").arg(enabled)
+            }
+
+            /// Suspends commands processing.
+            ///
+            /// `CLIENT PAUSE timeout [<WRITE | ALL>]`
+            fn client_pause<>(timeout: i64, mode: Option<&$lifetime str>) {
+                cmd("CLIENT").arg("PAUSE").arg(timeout).arg(mode)
+            }
+
+            /// Sets information specific to the client or connection.
+            ///
+            /// `CLIENT SETINFO <LIB-NAME libname | LIB-VER libver>`
+            ///
+            /// These parameters are written as given and have to follow the syntax:
+            /// - `attr`: `<LIB-NAME libname | LIB-VER libver>`
+            fn client_setinfo<T: ToRedisArgs>(attr: T) {
+                cmd("CLIENT").arg("SETINFO").arg(attr)
+            }
+
+            /// Sets the connection name.
+            ///
+            /// `CLIENT SETNAME connection-name`
+            fn client_setname<V: ToRedisArgs>(connection_name: V) {
+                cmd("CLIENT").arg("SETNAME").arg(connection_name)
+            }
+
+            /// Controls server-assisted client-side caching for the connection.
+            ///
+            /// `CLIENT TRACKING <ON | OFF> [REDIRECT client-id] [PREFIX prefix [PREFIX prefix ...]] [BCAST] [OPTIN] [OPTOUT] [NOLOOP]`
+            #[allow(clippy::too_many_arguments)]
+            fn client_tracking<V: ToRedisArgs>(status: &$lifetime str, client_id: Option<i64>, prefix: Option<V>, bcast: bool, optin: bool, optout: bool, noloop: bool) {
+                cmd("CLIENT")
+                    .arg("TRACKING")
+                    .arg(status)
+                    .arg(client_id.map(|v| ("REDIRECT", v)))
+                    .arg(prefix.map(|v| crate::commands::generated::Repeated("PREFIX", v)))
+                    .arg(bcast.then_some("BCAST"))
+                    .arg(optin.then_some("OPTIN"))
+                    .arg(optout.then_some("OPTOUT"))
+                    .arg(noloop.then_some("NOLOOP"))
+            }
+
+            /// Returns information about server-assisted client-side caching for the connection.
+            ///
+            /// `CLIENT TRACKINGINFO`
+            fn client_trackinginfo<>() {
+                cmd("CLIENT").arg("TRACKINGINFO")
+            }
+
+            /// Unblocks a client blocked by a blocking command from a different connection.
+            ///
+            /// `CLIENT UNBLOCK client-id [<TIMEOUT | ERROR>]`
+            fn client_unblock<>(client_id: i64, unblock_type: Option<&$lifetime str>) {
+                cmd("CLIENT").arg("UNBLOCK").arg(client_id).arg(unblock_type)
+            }
+
+            /// Resumes processing commands from paused clients.
+            ///
+            /// `CLIENT UNPAUSE`
+            fn client_unpause<>() {
+                cmd("CLIENT").arg("UNPAUSE")
+            }
+
+            /// Returns the given string.
+            ///
+            /// `ECHO message`
+            fn echo<V: ToRedisArgs>(message: V) {
+                cmd("ECHO").arg(message)
+            }
+
+            /// Returns the server's liveliness response.
+            ///
+            /// `PING [message]`
+            fn ping<V: ToRedisArgs>(message: Option<V>) {
+                cmd("PING").arg(message)
+            }
+
+            // generated: generic
+
+            /// Blocks until the asynchronous replication of all preceding write commands sent by the connection is completed.
+            ///
+            /// `WAIT numreplicas timeout`
+            fn wait<>(numreplicas: i64, timeout: i64) {
+                cmd("WAIT").arg(numreplicas).arg(timeout)
+            }
+
+            /// Blocks until all of the preceding write commands sent by the connection are written to the append-only file of the master and/or replicas.
+            ///
+            /// `WAITAOF numlocal numreplicas timeout`
+            fn waitaof<>(numlocal: i64, numreplicas: i64, timeout: i64) {
+                cmd("WAITAOF").arg(numlocal).arg(numreplicas).arg(timeout)
+            }
+
+            // generated: geo
+
+            /// Returns members from a geospatial index that are within a distance from a member.
+            ///
+            /// `GEORADIUSBYMEMBER_RO key member radius <M | KM | FT | MI> [WITHCOORD] [WITHDIST] [WITHHASH] [COUNT count [ANY]] [<ASC | DESC>]`
+            ///
+            /// Deprecated by the server since 6.2.0, replaced by `GEOSEARCH` with the `BYRADIUS` and `FROMMEMBER` arguments.
+            #[allow(clippy::too_many_arguments)]
+            fn georadiusbymember_ro<K: ToRedisArgs, V: ToRedisArgs>(key: K, member: V, radius: f64, unit: &$lifetime str, withcoord: bool, withdist: bool, withhash: bool, count_block: Option<(i64, bool)>, order: Option<&$lifetime str>) {
+                cmd("GEORADIUSBYMEMBER_RO")
+                    .arg(key)
+                    .arg(member)
+                    .arg(radius)
+                    .arg(unit)
+                    .arg(withcoord.then_some("WITHCOORD"))
+                    .arg(withdist.then_some("WITHDIST"))
+                    .arg(withhash.then_some("WITHHASH"))
+                    .arg(count_block.map(|(count, any)| ("COUNT", count, any.then_some("ANY"))))
+                    .arg(order)
+            }
+
+            /// Returns members from a geospatial index that are within a distance from a coordinate.
+            ///
+            /// `GEORADIUS_RO key longitude latitude radius <M | KM | FT | MI> [WITHCOORD] [WITHDIST] [WITHHASH] [COUNT count [ANY]] [<ASC | DESC>]`
+            ///
+            /// Deprecated by the server since 6.2.0, replaced by `GEOSEARCH` with the `BYRADIUS` argument.
+            #[allow(clippy::too_many_arguments)]
+            fn georadius_ro<K: ToRedisArgs>(key: K, longitude: f64, latitude: f64, radius: f64, unit: &$lifetime str, withcoord: bool, withdist: bool, withhash: bool, count_block: Option<(i64, bool)>, order: Option<&$lifetime str>) {
+                cmd("GEORADIUS_RO")
+                    .arg(key)
+                    .arg(longitude)
+                    .arg(latitude)
+                    .arg(radius)
+                    .arg(unit)
+                    .arg(withcoord.then_some("WITHCOORD"))
+                    .arg(withdist.then_some("WITHDIST"))
+                    .arg(withhash.then_some("WITHHASH"))
+                    .arg(count_block.map(|(count, any)| ("COUNT", count, any.then_some("ANY"))))
+                    .arg(order)
+            }
+
+            // generated: pubsub
+
+            /// Returns the active channels.
+            ///
+            /// `PUBSUB CHANNELS [pattern]`
+            fn pubsub_channels<P: ToRedisArgs>(pattern: Option<P>) {
+                cmd("PUBSUB").arg("CHANNELS").arg(pattern)
+            }
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `PUBSUB HELP`
+            fn pubsub_help<>() {
+                cmd("PUBSUB").arg("HELP")
+            }
+
+            /// Returns a count of unique pattern subscriptions.
+            ///
+            /// `PUBSUB NUMPAT`
+            fn pubsub_numpat<>() {
+                cmd("PUBSUB").arg("NUMPAT")
+            }
+
+            /// Returns a count of subscribers to channels.
+            ///
+            /// `PUBSUB NUMSUB [channel [channel ...]]`
+            fn pubsub_numsub<V: ToRedisArgs>(channel: Option<V>) {
+                cmd("PUBSUB").arg("NUMSUB").arg(channel)
+            }
+
+            /// Returns the active shard channels.
+            ///
+            /// `PUBSUB SHARDCHANNELS [pattern]`
+            fn pubsub_shardchannels<P: ToRedisArgs>(pattern: Option<P>) {
+                cmd("PUBSUB").arg("SHARDCHANNELS").arg(pattern)
+            }
+
+            /// Returns the count of subscribers of shard channels.
+            ///
+            /// `PUBSUB SHARDNUMSUB [shardchannel [shardchannel ...]]`
+            fn pubsub_shardnumsub<V: ToRedisArgs>(shardchannel: Option<V>) {
+                cmd("PUBSUB").arg("SHARDNUMSUB").arg(shardchannel)
+            }
+
+            /// Post a message to a shard channel.
+            ///
+            /// `SPUBLISH shardchannel message`
+            fn spublish<V: ToRedisArgs, V2: ToRedisArgs>(shardchannel: V, message: V2) {
+                cmd("SPUBLISH").arg(shardchannel).arg(message)
+            }
+
+            // generated: scripting
+
+            /// Executes a server-side Lua script.
+            ///
+            /// `EVAL script numkeys [key [key ...]] [arg [arg ...]]`
+            fn eval<V: ToRedisArgs, K: ToRedisArgs, V2: ToRedisArgs>(script: V, numkeys: i64, key: Option<K>, arg: Option<V2>) {
+                cmd("EVAL").arg(script).arg(numkeys).arg(key).arg(arg)
+            }
+
+            /// Executes a server-side Lua script by SHA1 digest.
+            ///
+            /// `EVALSHA sha1 numkeys [key [key ...]] [arg [arg ...]]`
+            fn evalsha<V: ToRedisArgs, K: ToRedisArgs, V2: ToRedisArgs>(sha1: V, numkeys: i64, key: Option<K>, arg: Option<V2>) {
+                cmd("EVALSHA").arg(sha1).arg(numkeys).arg(key).arg(arg)
+            }
+
+            /// Executes a read-only server-side Lua script by SHA1 digest.
+            ///
+            /// `EVALSHA_RO sha1 numkeys [key [key ...]] [arg [arg ...]]`
+            fn evalsha_ro<V: ToRedisArgs, K: ToRedisArgs, V2: ToRedisArgs>(sha1: V, numkeys: i64, key: Option<K>, arg: Option<V2>) {
+                cmd("EVALSHA_RO").arg(sha1).arg(numkeys).arg(key).arg(arg)
+            }
+
+            /// Executes a read-only server-side Lua script.
+            ///
+            /// `EVAL_RO script numkeys [key [key ...]] [arg [arg ...]]`
+            fn eval_ro<V: ToRedisArgs, K: ToRedisArgs, V2: ToRedisArgs>(script: V, numkeys: i64, key: Option<K>, arg: Option<V2>) {
+                cmd("EVAL_RO").arg(script).arg(numkeys).arg(key).arg(arg)
+            }
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `FUNCTION HELP`
+            fn function_help<>() {
+                cmd("FUNCTION").arg("HELP")
+            }
+
+            /// Terminates a function during execution.
+            ///
+            /// `FUNCTION KILL`
+            fn function_kill<>() {
+                cmd("FUNCTION").arg("KILL")
+            }
+
+            /// Sets the debug mode of server-side Lua scripts.
+            ///
+            /// `SCRIPT DEBUG <YES | SYNC | NO>`
+            fn script_debug<>(mode: &$lifetime str) {
+                cmd("SCRIPT").arg("DEBUG").arg(mode)
+            }
+
+            /// Determines whether server-side Lua scripts exist in the script cache.
+            ///
+            /// `SCRIPT EXISTS sha1 [sha1 ...]`
+            fn script_exists<V: ToRedisArgs>(sha1: V) {
+                cmd("SCRIPT").arg("EXISTS").arg(sha1)
+            }
+
+            /// Removes all server-side Lua scripts from the script cache.
+            ///
+            /// `SCRIPT FLUSH [<ASYNC | SYNC>]`
+            fn script_flush<>(flush_type: Option<&$lifetime str>) {
+                cmd("SCRIPT").arg("FLUSH").arg(flush_type)
+            }
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `SCRIPT HELP`
+            fn script_help<>() {
+                cmd("SCRIPT").arg("HELP")
+            }
+
+            /// Terminates a server-side Lua script during execution.
+            ///
+            /// `SCRIPT KILL`
+            fn script_kill<>() {
+                cmd("SCRIPT").arg("KILL")
+            }
+
+            /// Loads a server-side Lua script to the script cache.
+            ///
+            /// `SCRIPT LOAD script`
+            fn script_load<V: ToRedisArgs>(script: V) {
+                cmd("SCRIPT").arg("LOAD").arg(script)
+            }
+
+            // generated: server
+
+            /// Simulates the execution of a command by a user, without executing the command.
+            ///
+            /// `ACL DRYRUN username command [arg [arg ...]]`
+            fn acl_dryrun<V: ToRedisArgs, V2: ToRedisArgs, V3: ToRedisArgs>(username: V, command: V2, arg: Option<V3>) {
+                cmd("ACL").arg("DRYRUN").arg(username).arg(command).arg(arg)
+            }
+
+            /// Asynchronously rewrites the append-only file to disk.
+            ///
+            /// `BGREWRITEAOF`
+            fn bgrewriteaof<>() {
+                &mut cmd("BGREWRITEAOF")
+            }
+
+            /// Asynchronously saves the database(s) to disk.
+            ///
+            /// `BGSAVE [SCHEDULE]`
+            fn bgsave<>(schedule: bool) {
+                cmd("BGSAVE").arg(schedule.then_some("SCHEDULE"))
+            }
+
+            /// Returns detailed information about all commands.
+            ///
+            /// `COMMAND`
+            fn command<>() {
+                &mut cmd("COMMAND")
+            }
+
+            /// Returns a count of commands.
+            ///
+            /// `COMMAND COUNT`
+            fn command_count<>() {
+                cmd("COMMAND").arg("COUNT")
+            }
+
+            /// Returns documentary information about one, multiple or all commands.
+            ///
+            /// `COMMAND DOCS [command-name [command-name ...]]`
+            fn command_docs<V: ToRedisArgs>(command_name: Option<V>) {
+                cmd("COMMAND").arg("DOCS").arg(command_name)
+            }
+
+            /// Extracts the key names from an arbitrary command.
+            ///
+            /// `COMMAND GETKEYS command [arg [arg ...]]`
+            fn command_getkeys<V: ToRedisArgs, V2: ToRedisArgs>(command: V, arg: Option<V2>) {
+                cmd("COMMAND").arg("GETKEYS").arg(command).arg(arg)
+            }
+
+            /// Extracts the key names and access flags for an arbitrary command.
+            ///
+            /// `COMMAND GETKEYSANDFLAGS command [arg [arg ...]]`
+            fn command_getkeysandflags<V: ToRedisArgs, V2: ToRedisArgs>(command: V, arg: Option<V2>) {
+                cmd("COMMAND").arg("GETKEYSANDFLAGS").arg(command).arg(arg)
+            }
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `COMMAND HELP`
+            fn command_help<>() {
+                cmd("COMMAND").arg("HELP")
+            }
+
+            /// Returns information about one, multiple or all commands.
+            ///
+            /// `COMMAND INFO [command-name [command-name ...]]`
+            fn command_info<V: ToRedisArgs>(command_name: Option<V>) {
+                cmd("COMMAND").arg("INFO").arg(command_name)
+            }
+
+            /// Returns a list of command names.
+            ///
+            /// `COMMAND LIST [FILTERBY <MODULE module-name | ACLCAT category | PATTERN pattern>]`
+            ///
+            /// These parameters are written as given and have to follow the syntax:
+            /// - `filterby`: `<MODULE module-name | ACLCAT category | PATTERN pattern>`
+            fn command_list<T: ToRedisArgs>(filterby: Option<T>) {
+                cmd("COMMAND").arg("LIST").arg(filterby.map(|v| ("FILTERBY", v)))
+            }
+
+            /// Returns the effective values of configuration parameters.
+            ///
+            /// `CONFIG GET parameter [parameter ...]`
+            fn config_get<V: ToRedisArgs>(parameter: V) {
+                cmd("CONFIG").arg("GET").arg(parameter)
+            }
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `CONFIG HELP`
+            fn config_help<>() {
+                cmd("CONFIG").arg("HELP")
+            }
+
+            /// Resets the server's statistics.
+            ///
+            /// `CONFIG RESETSTAT`
+            fn config_resetstat<>() {
+                cmd("CONFIG").arg("RESETSTAT")
+            }
+
+            /// Persists the effective configuration to file.
+            ///
+            /// `CONFIG REWRITE`
+            fn config_rewrite<>() {
+                cmd("CONFIG").arg("REWRITE")
+            }
+
+            /// Sets configuration parameters in-flight.
+            ///
+            /// `CONFIG SET parameter value [parameter value ...]`
+            ///
+            /// These parameters are written as given and have to follow the syntax:
+            /// - `data`: `parameter value [parameter value ...]`
+            fn config_set<T: ToRedisArgs>(data: T) {
+                cmd("CONFIG").arg("SET").arg(data)
+            }
+
+            /// Returns the number of keys in the database.
+            ///
+            /// `DBSIZE`
+            fn dbsize<>() {
+                &mut cmd("DBSIZE")
+            }
+
+            /// Starts a coordinated failover from a server to one of its replicas.
+            ///
+            /// `FAILOVER [TO host port [FORCE]] [ABORT] [TIMEOUT milliseconds]`
+            fn failover<V: ToRedisArgs>(target: Option<(V, i64, bool)>, abort: bool, milliseconds: Option<i64>) {
+                cmd("FAILOVER")
+                    .arg(target.map(|(host, port, force)| ("TO", host, port, force.then_some("FORCE"))))
+                    .arg(abort.then_some("ABORT"))
+                    .arg(milliseconds.map(|v| ("TIMEOUT", v)))
+            }
+
+            /// Removes all keys from all databases.
+            ///
+            /// `FLUSHALL [<ASYNC | SYNC>]`
+            fn flushall<>(flush_type: Option<&$lifetime str>) {
+                cmd("FLUSHALL").arg(flush_type)
+            }
+
+            /// Remove all keys from the current database.
+            ///
+            /// `FLUSHDB [<ASYNC | SYNC>]`
+            fn flushdb<>(flush_type: Option<&$lifetime str>) {
+                cmd("FLUSHDB").arg(flush_type)
+            }
+
+            /// Returns the Unix timestamp of the last successful save to disk.
+            ///
+            /// `LASTSAVE`
+            fn lastsave<>() {
+                &mut cmd("LASTSAVE")
+            }
+
+            /// Returns a human-readable latency analysis report.
+            ///
+            /// `LATENCY DOCTOR`
+            fn latency_doctor<>() {
+                cmd("LATENCY").arg("DOCTOR")
+            }
+
+            /// Returns a latency graph for an event.
+            ///
+            /// `LATENCY GRAPH event`
+            fn latency_graph<V: ToRedisArgs>(event: V) {
+                cmd("LATENCY").arg("GRAPH").arg(event)
+            }
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `LATENCY HELP`
+            fn latency_help<>() {
+                cmd("LATENCY").arg("HELP")
+            }
+
+            /// Returns the cumulative distribution of latencies of a subset or all commands.
+            ///
+            /// `LATENCY HISTOGRAM [command [command ...]]`
+            fn latency_histogram<V: ToRedisArgs>(command: Option<V>) {
+                cmd("LATENCY").arg("HISTOGRAM").arg(command)
+            }
+
+            /// Returns timestamp-latency samples for an event.
+            ///
+            /// `LATENCY HISTORY event`
+            fn latency_history<V: ToRedisArgs>(event: V) {
+                cmd("LATENCY").arg("HISTORY").arg(event)
+            }
+
+            /// Returns the latest latency samples for all events.
+            ///
+            /// `LATENCY LATEST`
+            fn latency_latest<>() {
+                cmd("LATENCY").arg("LATEST")
+            }
+
+            /// Resets the latency data for one or more events.
+            ///
+            /// `LATENCY RESET [event [event ...]]`
+            fn latency_reset<V: ToRedisArgs>(event: Option<V>) {
+                cmd("LATENCY").arg("RESET").arg(event)
+            }
+
+            /// Displays computer art and the Redis version.
+            ///
+            /// `LOLWUT [VERSION version]`
+            fn lolwut<>(version: Option<i64>) {
+                cmd("LOLWUT").arg(version.map(|v| ("VERSION", v)))
+            }
+
+            /// Outputs a memory problems report.
+            ///
+            /// `MEMORY DOCTOR`
+            fn memory_doctor<>() {
+                cmd("MEMORY").arg("DOCTOR")
+            }
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `MEMORY HELP`
+            fn memory_help<>() {
+                cmd("MEMORY").arg("HELP")
+            }
+
+            /// Returns the allocator statistics.
+            ///
+            /// `MEMORY MALLOC-STATS`
+            fn memory_malloc_stats<>() {
+                cmd("MEMORY").arg("MALLOC-STATS")
+            }
+
+            /// Asks the allocator to release memory.
+            ///
+            /// `MEMORY PURGE`
+            fn memory_purge<>() {
+                cmd("MEMORY").arg("PURGE")
+            }
+
+            /// Returns details about memory usage.
+            ///
+            /// `MEMORY STATS`
+            fn memory_stats<>() {
+                cmd("MEMORY").arg("STATS")
+            }
+
+            /// Estimates the memory usage of a key.
+            ///
+            /// `MEMORY USAGE key [SAMPLES count]`
+            fn memory_usage<K: ToRedisArgs>(key: K, count: Option<i64>) {
+                cmd("MEMORY").arg("USAGE").arg(key).arg(count.map(|v| ("SAMPLES", v)))
+            }
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `MODULE HELP`
+            fn module_help<>() {
+                cmd("MODULE").arg("HELP")
+            }
+
+            /// Returns all loaded modules.
+            ///
+            /// `MODULE LIST`
+            fn module_list<>() {
+                cmd("MODULE").arg("LIST")
+            }
+
+            /// Loads a module.
+            ///
+            /// `MODULE LOAD path [arg [arg ...]]`
+            fn module_load<V: ToRedisArgs, V2: ToRedisArgs>(path: V, arg: Option<V2>) {
+                cmd("MODULE").arg("LOAD").arg(path).arg(arg)
+            }
+
+            /// Loads a module using extended parameters.
+            ///
+            /// `MODULE LOADEX path [CONFIG name value [CONFIG name value ...]] [ARGS args [args ...]]`
+            ///
+            /// These parameters are written as given and have to follow the syntax:
+            /// - `configs`: `CONFIG name value [CONFIG name value ...]`
+            fn module_loadex<V: ToRedisArgs, T: ToRedisArgs, V2: ToRedisArgs>(path: V, configs: Option<T>, args: Option<V2>) {
+                cmd("MODULE")
+                    .arg("LOADEX")
+                    .arg(path)
+                    .arg(configs)
+                    .arg(args.map(|v| ("ARGS", v)))
+            }
+
+            /// Unloads a module.
+            ///
+            /// `MODULE UNLOAD name`
+            fn module_unload<V: ToRedisArgs>(name: V) {
+                cmd("MODULE").arg("UNLOAD").arg(name)
+            }
+
+            /// Configures a server as replica of another, or promotes it to a master.
+            ///
+            /// `REPLICAOF <host port | NO ONE>`
+            ///
+            /// These parameters are written as given and have to follow the syntax:
+            /// - `args`: `<host port | NO ONE>`
+            fn replicaof<T: ToRedisArgs>(args: T) {
+                cmd("REPLICAOF").arg(args)
+            }
+
+            /// Returns the replication role.
+            ///
+            /// `ROLE`
+            fn role<>() {
+                &mut cmd("ROLE")
+            }
+
+            /// Synchronously saves the database(s) to disk.
+            ///
+            /// `SAVE`
+            fn save<>() {
+                &mut cmd("SAVE")
+            }
+
+            /// Synchronously saves the database(s) to disk and shuts down the Redis server.
+            ///
+            /// `SHUTDOWN [<NOSAVE | SAVE>] [NOW] [FORCE] [ABORT]`
+            fn shutdown<>(save_selector: Option<&$lifetime str>, now: bool, force: bool, abort: bool) {
+                cmd("SHUTDOWN")
+                    .arg(save_selector)
+                    .arg(now.then_some("NOW"))
+                    .arg(force.then_some("FORCE"))
+                    .arg(abort.then_some("ABORT"))
+            }
+
+            /// Sets a Redis server as a replica of another, or promotes it to being a master.
+            ///
+            /// `SLAVEOF <host port | NO ONE>`
+            ///
+            /// These parameters are written as given and have to follow the syntax:
+            /// - `args`: `<host port | NO ONE>`
+            ///
+            /// Deprecated by the server since 5.0.0, replaced by `REPLICAOF`.
+            fn slaveof<T: ToRedisArgs>(args: T) {
+                cmd("SLAVEOF").arg(args)
+            }
+
+            /// Returns the slow log's entries.
+            ///
+            /// `SLOWLOG GET [count]`
+            fn slowlog_get<>(count: Option<i64>) {
+                cmd("SLOWLOG").arg("GET").arg(count)
+            }
+
+            /// Show helpful text about the different subcommands.
+            ///
+            /// `SLOWLOG HELP`
+            fn slowlog_help<>() {
+                cmd("SLOWLOG").arg("HELP")
+            }
+
+            /// Returns the number of entries in the slow log.
+            ///
+            /// `SLOWLOG LEN`
+            fn slowlog_len<>() {
+                cmd("SLOWLOG").arg("LEN")
+            }
+
+            /// Clears all entries from the slow log.
+            ///
+            /// `SLOWLOG RESET`
+            fn slowlog_reset<>() {
+                cmd("SLOWLOG").arg("RESET")
+            }
+
+            /// Swaps two Redis databases.
+            ///
+            /// `SWAPDB index1 index2`
+            fn swapdb<>(index1: i64, index2: i64) {
+                cmd("SWAPDB").arg(index1).arg(index2)
+            }
+
+            /// Returns the server time.
+            ///
+            /// `TIME`
+            fn time<>() {
+                &mut cmd("TIME")
+            }
+
+            // generated: stream
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `XGROUP HELP`
+            fn xgroup_help<>() {
+                cmd("XGROUP").arg("HELP")
+            }
+
+            /// Returns helpful text about the different subcommands.
+            ///
+            /// `XINFO HELP`
+            fn xinfo_help<>() {
+                cmd("XINFO").arg("HELP")
+            }
+
+            // generated: string
+
+            /// Returns a substring from a string value.
+            ///
+            /// `SUBSTR key start end`
+            ///
+            /// Deprecated by the server since 2.0.0, replaced by `GETRANGE`.
+            fn substr<K: ToRedisArgs>(key: K, start: i64, end: i64) {
+                cmd("SUBSTR").arg(key).arg(start).arg(end)
+            }
+
+            // generated: transactions
+
+            /// Forgets about watched keys of a transaction.
+            ///
+            /// `UNWATCH`
+            fn unwatch<>() {
+                &mut cmd("UNWATCH")
+            }
+
+            /// Monitors changes to keys to determine the execution of a MULTI transaction block.
+            ///
+            /// `WATCH key [key ...]`
+            fn watch<K: ToRedisArgs>(key: K) {
+                cmd("WATCH").arg(key)
+            }
+        }
+    )
+}
@@ -48,7 +48,7 @@ macro_rules! implement_json_commands {
         /// # fn do_something() -> redis::RedisResult<()> {
         /// let client = redis::Client::open("redis://127.0.0.1/")?;
         /// let mut con = client.get_connection()?;
-        /// con.json_set("my_key", "$", &json!({"item": 42i32}).to_string())?;
+        /// let _: () = con.json_set("my_key", "$", &json!({"item": 42i32}).to_string())?;
         /// assert_eq!(con.json_get("my_key", "$"), Ok(String::from(r#"[{"item":42}]"#)));
         /// assert_eq!(con.json_get("my_key", "$.item"), Ok(String::from(r#"[42]"#)));
         /// # Ok(()) }
@@ -92,7 +92,7 @@ macro_rules! implement_json_commands {
         /// # async fn do_something() -> redis::RedisResult<()> {
         /// let client = redis::Client::open("redis://127.0.0.1/")?;
         /// let mut con = client.get_async_connection().await?;
-        /// redis::cmd("JSON.SET").arg("my_key").arg("$").arg(&json!({"item": 42i32}).to_string()).query_async(&mut con).await?;
+        /// let _: () = redis::cmd("JSON.SET").arg("my_key").arg("$").arg(&json!({"item": 42i32}).to_string()).query_async(&mut con).await?;
         /// assert_eq!(redis::cmd("GET").arg("my_key").arg("$").query_async(&mut con).await, Ok(String::from(r#"[{"item":42}]"#)));
         /// # Ok(()) }
         /// ```
@@ -106,7 +106,7 @@ macro_rules! implement_json_commands {
         /// use redis::Commands;
         /// let client = redis::Client::open("redis://127.0.0.1/")?;
         /// let mut con = client.get_async_connection().await?;
-        /// con.json_set("my_key", "$", &json!({"item": 42i32}).to_string()).await?;
+        /// let _: () = con.json_set("my_key", "$", &json!({"item": 42i32}).to_string()).await?;
         /// assert_eq!(con.json_get("my_key", "$").await, Ok(String::from(r#"[{"item":42}]"#)));
         /// assert_eq!(con.json_get("my_key", "$.item").await, Ok(String::from(r#"[42]"#)));
         /// # Ok(()) }
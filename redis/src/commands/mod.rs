@@ -3,10 +3,12 @@
 use crate::cmd::{cmd, Cmd, Iter};
 use crate::connection::{Connection, ConnectionLike, Msg};
 use crate::pipeline::Pipeline;
-use crate::types::{FromRedisValue, NumericBehavior, RedisResult, ToRedisArgs, RedisWrite, Expiry, SetExpiry, ExistenceCheck};
+use crate::types::{FromRedisValue, NumericBehavior, RedisResult, ToRedisArgs, RedisWrite, Expiry, SetExpiry, ExistenceCheck, ExpireOption};
 
 #[macro_use]
 mod macros;
+#[macro_use]
+mod generated;
 
 #[cfg(feature = "json")]
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
@@ -24,6 +26,9 @@ use crate::cluster_pipeline::ClusterPipeline;
 #[cfg(feature = "geospatial")]
 use crate::geo;
 
+use crate::function::FunctionRestorePolicy;
+use crate::info;
+
 #[cfg(feature = "streams")]
 use crate::streams;
 
@@ -34,11 +39,11 @@ use crate::acl;
 pub(crate) fn is_readonly_cmd(cmd: &[u8]) -> bool {
     matches!(
         cmd,
-        b"BITCOUNT" | b"BITFIELD_RO" | b"BITPOS" | b"DBSIZE" | b"DUMP" | b"EVALSHA_RO" | b"EVAL_RO" | b"EXISTS" | b"EXPIRETIME" | b"FCALL_RO" | b"GEODIST" | b"GEOHASH" | b"GEOPOS" | b"GEORADIUSBYMEMBER_RO" | b"GEORADIUS_RO" | b"GEOSEARCH" | b"GET" | b"GETBIT" | b"GETRANGE" | b"HEXISTS" | b"HGET" | b"HGETALL" | b"HKEYS" | b"HLEN" | b"HMGET" | b"HRANDFIELD" | b"HSCAN" | b"HSTRLEN" | b"HVALS" | b"KEYS" | b"LCS" | b"LINDEX" | b"LLEN" | b"LOLWUT" | b"LPOS" | b"LRANGE" | b"MEMORY USAGE" | b"MGET" | b"OBJECT ENCODING" | b"OBJECT FREQ" | b"OBJECT IDLETIME" | b"OBJECT REFCOUNT" | b"PEXPIRETIME" | b"PFCOUNT" | b"PTTL" | b"RANDOMKEY" | b"SCAN" | b"SCARD" | b"SDIFF" | b"SINTER" | b"SINTERCARD" | b"SISMEMBER" | b"SMEMBERS" | b"SMISMEMBER" | b"SORT_RO" | b"SRANDMEMBER" | b"SSCAN" | b"STRLEN" | b"SUBSTR" | b"SUNION" | b"TOUCH" | b"TTL" | b"TYPE" | b"XINFO CONSUMERS" | b"XINFO GROUPS" | b"XINFO STREAM" | b"XLEN" | b"XPENDING" | b"XRANGE" | b"XREAD" | b"XREVRANGE" | b"ZCARD" | b"ZCOUNT" | b"ZDIFF" | b"ZINTER" | b"ZINTERCARD" | b"ZLEXCOUNT" | b"ZMSCORE" | b"ZRANDMEMBER" | b"ZRANGE" | b"ZRANGEBYLEX" | b"ZRANGEBYSCORE" | b"ZRANK" | b"ZREVRANGE" | b"ZREVRANGEBYLEX" | b"ZREVRANGEBYSCORE" | b"ZREVRANK" | b"ZSCAN" | b"ZSCORE" | b"ZUNION"
+        b"BITCOUNT" | b"BITFIELD_RO" | b"BITPOS" | b"DBSIZE" | b"DUMP" | b"EVALSHA_RO" | b"EVAL_RO" | b"EXISTS" | b"EXPIRETIME" | b"FCALL_RO" | b"GEODIST" | b"GEOHASH" | b"GEOPOS" | b"GEORADIUSBYMEMBER_RO" | b"GEORADIUS_RO" | b"GEOSEARCH" | b"GET" | b"GETBIT" | b"GETRANGE" | b"HEXISTS" | b"HEXPIRETIME" | b"HGET" | b"HGETALL" | b"HKEYS" | b"HLEN" | b"HMGET" | b"HPEXPIRETIME" | b"HPTTL" | b"HRANDFIELD" | b"HSCAN" | b"HSTRLEN" | b"HTTL" | b"HVALS" | b"KEYS" | b"LCS" | b"LINDEX" | b"LLEN" | b"LOLWUT" | b"LPOS" | b"LRANGE" | b"MEMORY USAGE" | b"MGET" | b"OBJECT ENCODING" | b"OBJECT FREQ" | b"OBJECT IDLETIME" | b"OBJECT REFCOUNT" | b"PEXPIRETIME" | b"PFCOUNT" | b"PTTL" | b"RANDOMKEY" | b"SCAN" | b"SCARD" | b"SDIFF" | b"SINTER" | b"SINTERCARD" | b"SISMEMBER" | b"SMEMBERS" | b"SMISMEMBER" | b"SORT_RO" | b"SRANDMEMBER" | b"SSCAN" | b"STRLEN" | b"SUBSTR" | b"SUNION" | b"TOUCH" | b"TTL" | b"TYPE" | b"XINFO CONSUMERS" | b"XINFO GROUPS" | b"XINFO STREAM" | b"XLEN" | b"XPENDING" | b"XRANGE" | b"XREAD" | b"XREVRANGE" | b"ZCARD" | b"ZCOUNT" | b"ZDIFF" | b"ZINTER" | b"ZINTERCARD" | b"ZLEXCOUNT" | b"ZMSCORE" | b"ZRANDMEMBER" | b"ZRANGE" | b"ZRANGEBYLEX" | b"ZRANGEBYSCORE" | b"ZRANK" | b"ZREVRANGE" | b"ZREVRANGEBYLEX" | b"ZREVRANGEBYSCORE" | b"ZREVRANK" | b"ZSCAN" | b"ZSCORE" | b"ZUNION"
     )
 }
 
-implement_commands! {
+implement_commands_with_generated! {
     'a
     // most common operations
 
@@ -114,6 +119,17 @@ implement_commands! {
         cmd("SETRANGE").arg(key).arg(offset).arg(value)
     }
 
+    /// Get the longest common subsequence of the values of two keys.
+    fn lcs<K1: ToRedisArgs, K2: ToRedisArgs>(key1: K1, key2: K2) {
+        cmd("LCS").arg(key1).arg(key2)
+    }
+
+    /// Get the length of the longest common subsequence of the values of two
+    /// keys.
+    fn lcs_len<K1: ToRedisArgs, K2: ToRedisArgs>(key1: K1, key2: K2) {
+        cmd("LCS").arg(key1).arg(key2).arg("LEN")
+    }
+
     /// Delete one or more keys.
     fn del<K: ToRedisArgs>(key: K) {
         cmd("DEL").arg(key)
@@ -130,25 +146,47 @@ implement_commands! {
     }
 
     /// Set a key's time to live in seconds.
-    fn expire<K: ToRedisArgs>(key: K, seconds: usize) {
+    fn expire<K: ToRedisArgs>(key: K, seconds: i64) {
         cmd("EXPIRE").arg(key).arg(seconds)
     }
 
     /// Set the expiration for a key as a UNIX timestamp.
-    fn expire_at<K: ToRedisArgs>(key: K, ts: usize) {
+    fn expire_at<K: ToRedisArgs>(key: K, ts: i64) {
         cmd("EXPIREAT").arg(key).arg(ts)
     }
 
     /// Set a key's time to live in milliseconds.
-    fn pexpire<K: ToRedisArgs>(key: K, ms: usize) {
+    fn pexpire<K: ToRedisArgs>(key: K, ms: i64) {
         cmd("PEXPIRE").arg(key).arg(ms)
     }
 
     /// Set the expiration for a key as a UNIX timestamp in milliseconds.
-    fn pexpire_at<K: ToRedisArgs>(key: K, ts: usize) {
+    fn pexpire_at<K: ToRedisArgs>(key: K, ts: i64) {
         cmd("PEXPIREAT").arg(key).arg(ts)
     }
 
+    /// Set a key's time to live in seconds, if the condition of `opt` is met.
+    fn expire_options<K: ToRedisArgs>(key: K, seconds: i64, opt: ExpireOption) {
+        cmd("EXPIRE").arg(key).arg(seconds).arg(opt)
+    }
+
+    /// Set the expiration for a key as a UNIX timestamp, if the condition of
+    /// `opt` is met.
+    fn expire_at_options<K: ToRedisArgs>(key: K, ts: i64, opt: ExpireOption) {
+        cmd("EXPIREAT").arg(key).arg(ts).arg(opt)
+    }
+
+    /// Set a key's time to live in milliseconds, if the condition of `opt` is met.
+    fn pexpire_options<K: ToRedisArgs>(key: K, ms: i64, opt: ExpireOption) {
+        cmd("PEXPIRE").arg(key).arg(ms).arg(opt)
+    }
+
+    /// Set the expiration for a key as a UNIX timestamp in milliseconds, if the
+    /// condition of `opt` is met.
+    fn pexpire_at_options<K: ToRedisArgs>(key: K, ts: i64, opt: ExpireOption) {
+        cmd("PEXPIREAT").arg(key).arg(ts).arg(opt)
+    }
+
     /// Remove the expiration from a key.
     fn persist<K: ToRedisArgs>(key: K) {
         cmd("PERSIST").arg(key)
@@ -164,6 +202,18 @@ implement_commands! {
         cmd("PTTL").arg(key)
     }
 
+    /// Get the absolute expiration of a key as a UNIX timestamp in seconds.
+    /// Parses into an [`ExpiryTime`](crate::ExpiryTime).
+    fn expire_time<K: ToRedisArgs>(key: K) {
+        cmd("EXPIRETIME").arg(key)
+    }
+
+    /// Get the absolute expiration of a key as a UNIX timestamp in
+    /// milliseconds.  Parses into an [`ExpiryTime`](crate::ExpiryTime).
+    fn pexpire_time<K: ToRedisArgs>(key: K) {
+        cmd("PEXPIRETIME").arg(key)
+    }
+
     /// Get the value of a key and set expiration
     fn get_ex<K: ToRedisArgs>(key: K, expire_at: Expiry) {
         let (option, time_arg) = match expire_at {
@@ -197,6 +247,59 @@ implement_commands! {
         cmd("UNLINK").arg(key)
     }
 
+    /// Copy the value of a key to another key.
+    fn copy<S: ToRedisArgs, D: ToRedisArgs>(source: S, destination: D, options: &'a CopyOptions) {
+        cmd("COPY").arg(source).arg(destination).arg(options)
+    }
+
+    /// Serialize the value of a key in the format used by `RESTORE`.
+    fn dump<K: ToRedisArgs>(key: K) {
+        cmd("DUMP").arg(key)
+    }
+
+    /// Create a key from a value serialized with `DUMP`.  A `ttl` of 0
+    /// creates the key without an expiration.
+    fn restore<K: ToRedisArgs, V: ToRedisArgs>(key: K, ttl: usize, serialized_value: V, options: &'a RestoreOptions) {
+        cmd("RESTORE").arg(key).arg(ttl).arg(serialized_value).arg(options)
+    }
+
+    /// Atomically transfer one or more keys to another Redis instance.
+    /// Multiple keys are sent with the `KEYS` option.
+    fn migrate<H: ToRedisArgs, K: ToRedisArgs>(host: H, port: u16, key: K, db: i64, timeout: usize, options: &'a MigrateOptions) {
+        let keys = key.to_redis_args();
+        let multiple = keys.len() != 1;
+        cmd("MIGRATE")
+            .arg(host)
+            .arg(port)
+            .arg(if multiple { &b""[..] } else { &keys[0][..] })
+            .arg(db)
+            .arg(timeout)
+            .arg(options)
+            .arg(multiple.then_some(("KEYS", &keys)))
+    }
+
+    /// Alter the last access time of one or more keys and return how many
+    /// of them exist.
+    fn touch<K: ToRedisArgs>(key: K) {
+        cmd("TOUCH").arg(key)
+    }
+
+    /// Return a random key from the database.
+    fn random_key<>() {
+        &mut cmd("RANDOMKEY")
+    }
+
+    /// Move a key to another database of the server.
+    fn move_key<K: ToRedisArgs>(key: K, db: i64) {
+        cmd("MOVE").arg(key).arg(db)
+    }
+
+    /// Sort the elements of a list, set or sorted set.  Without a `STORE`
+    /// destination this sends `SORT_RO`, which can run on replicas.
+    fn sort<K: ToRedisArgs>(key: K, options: &'a SortOptions) {
+        cmd(if options.store.is_some() { "SORT" } else { "SORT_RO" }).arg(key).arg(options)
+    }
+
     // common string operations
 
     /// Append a value to a key.
@@ -263,6 +366,43 @@ implement_commands! {
         cmd("BITOP").arg("NOT").arg(dstkey).arg(srckey)
     }
 
+    /// Runs the `GET`, `SET` and `INCRBY` operations of a [`BitField`]
+    /// over the string value stored at key.  The reply holds one value per
+    /// `GET`, `SET` and `INCRBY` operation, `None` when an `INCRBY` hit the
+    /// `FAIL` overflow behavior.
+    ///
+    /// ```rust,no_run
+    /// # use redis::{BitField, BitFieldOffset, BitFieldType, Commands, RedisResult};
+    /// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    /// # let mut con = client.get_connection().unwrap();
+    /// let field = BitField::default()
+    ///     .incr_by(BitFieldType::Unsigned(8), BitFieldOffset::Index(1), 1)
+    ///     .get(BitFieldType::Signed(4), BitFieldOffset::Bits(0));
+    /// let values: Vec<Option<i64>> = con.bitfield("counters", &field).unwrap();
+    /// ```
+    fn bitfield<K: ToRedisArgs>(key: K, field: &'a BitField) {
+        cmd("BITFIELD").arg(key).arg(field)
+    }
+
+    /// Runs the `GET` operations of a [`BitField`] over the string value
+    /// stored at key.  Unlike `bitfield` it can be sent to read only
+    /// replicas, the server rejects any other operation.
+    fn bitfield_ro<K: ToRedisArgs>(key: K, field: &'a BitField) {
+        cmd("BITFIELD_RO").arg(key).arg(field)
+    }
+
+    /// Returns the position of the first bit set to `bit` in the string
+    /// value stored at key.
+    fn bitpos<K: ToRedisArgs>(key: K, bit: bool) {
+        cmd("BITPOS").arg(key).arg(i32::from(bit))
+    }
+
+    /// Returns the position of the first bit set to `bit` in a byte range
+    /// of the string value stored at key.
+    fn bitpos_range<K: ToRedisArgs>(key: K, bit: bool, start: isize, end: isize) {
+        cmd("BITPOS").arg(key).arg(i32::from(bit)).arg(start).arg(end)
+    }
+
     /// Get the length of the value stored in a key.
     fn strlen<K: ToRedisArgs>(key: K) {
         cmd("STRLEN").arg(key)
@@ -309,6 +449,11 @@ implement_commands! {
         cmd("HEXISTS").arg(key).arg(field)
     }
 
+    /// Gets the length of the value of a field in a hash.
+    fn hstrlen<K: ToRedisArgs, F: ToRedisArgs>(key: K, field: F) {
+        cmd("HSTRLEN").arg(key).arg(field)
+    }
+
     /// Gets all the keys in a hash.
     fn hkeys<K: ToRedisArgs>(key: K) {
         cmd("HKEYS").arg(key)
@@ -329,6 +474,106 @@ implement_commands! {
         cmd("HLEN").arg(key)
     }
 
+    /// Gets a random field of a hash.
+    fn hrandfield<K: ToRedisArgs>(key: K) {
+        cmd("HRANDFIELD").arg(key)
+    }
+
+    /// Gets `count` random fields of a hash.  A negative count allows the
+    /// same field to be returned multiple times.
+    fn hrandfield_count<K: ToRedisArgs>(key: K, count: isize) {
+        cmd("HRANDFIELD").arg(key).arg(count)
+    }
+
+    /// Gets `count` random fields of a hash along with their values.
+    fn hrandfield_count_withvalues<K: ToRedisArgs>(key: K, count: isize) {
+        cmd("HRANDFIELD").arg(key).arg(count).arg("WITHVALUES")
+    }
+
+    /// Set the expiration of one or more fields of a hash, in seconds.
+    /// Returns a [`HashFieldExpireStatus`](crate::HashFieldExpireStatus) per field.
+    fn hexpire<K: ToRedisArgs, F: ToRedisArgs>(key: K, seconds: i64, opt: ExpireOption, fields: F) {
+        let fields = fields.to_redis_args();
+        cmd("HEXPIRE").arg(key).arg(seconds).arg(opt).arg("FIELDS").arg(fields.len()).arg(fields)
+    }
+
+    /// Set the expiration of one or more fields of a hash, in milliseconds.
+    /// Returns a [`HashFieldExpireStatus`](crate::HashFieldExpireStatus) per field.
+    fn hpexpire<K: ToRedisArgs, F: ToRedisArgs>(key: K, milliseconds: i64, opt: ExpireOption, fields: F) {
+        let fields = fields.to_redis_args();
+        cmd("HPEXPIRE").arg(key).arg(milliseconds).arg(opt).arg("FIELDS").arg(fields.len()).arg(fields)
+    }
+
+    /// Set the expiration of one or more fields of a hash as a UNIX timestamp
+    /// in seconds.  Returns a [`HashFieldExpireStatus`](crate::HashFieldExpireStatus)
+    /// per field.
+    fn hexpire_at<K: ToRedisArgs, F: ToRedisArgs>(key: K, ts: i64, opt: ExpireOption, fields: F) {
+        let fields = fields.to_redis_args();
+        cmd("HEXPIREAT").arg(key).arg(ts).arg(opt).arg("FIELDS").arg(fields.len()).arg(fields)
+    }
+
+    /// Set the expiration of one or more fields of a hash as a UNIX timestamp
+    /// in milliseconds.  Returns a [`HashFieldExpireStatus`](crate::HashFieldExpireStatus)
+    /// per field.
+    fn hpexpire_at<K: ToRedisArgs, F: ToRedisArgs>(key: K, ts: i64, opt: ExpireOption, fields: F) {
+        let fields = fields.to_redis_args();
+        cmd("HPEXPIREAT").arg(key).arg(ts).arg(opt).arg("FIELDS").arg(fields.len()).arg(fields)
+    }
+
+    /// Get the remaining time to live of one or more fields of a hash, in
+    /// seconds.  Returns an [`ExpiryTime`](crate::ExpiryTime) per field.
+    fn httl<K: ToRedisArgs, F: ToRedisArgs>(key: K, fields: F) {
+        let fields = fields.to_redis_args();
+        cmd("HTTL").arg(key).arg("FIELDS").arg(fields.len()).arg(fields)
+    }
+
+    /// Get the remaining time to live of one or more fields of a hash, in
+    /// milliseconds.  Returns an [`ExpiryTime`](crate::ExpiryTime) per field.
+    fn hpttl<K: ToRedisArgs, F: ToRedisArgs>(key: K, fields: F) {
+        let fields = fields.to_redis_args();
+        cmd("HPTTL").arg(key).arg("FIELDS").arg(fields.len()).arg(fields)
+    }
+
+    /// Get the expiration of one or more fields of a hash as a UNIX timestamp
+    /// in seconds.  Returns an [`ExpiryTime`](crate::ExpiryTime) per field.
+    fn hexpire_time<K: ToRedisArgs, F: ToRedisArgs>(key: K, fields: F) {
+        let fields = fields.to_redis_args();
+        cmd("HEXPIRETIME").arg(key).arg("FIELDS").arg(fields.len()).arg(fields)
+    }
+
+    /// Get the expiration of one or more fields of a hash as a UNIX timestamp
+    /// in milliseconds.  Returns an [`ExpiryTime`](crate::ExpiryTime) per field.
+    fn hpexpire_time<K: ToRedisArgs, F: ToRedisArgs>(key: K, fields: F) {
+        let fields = fields.to_redis_args();
+        cmd("HPEXPIRETIME").arg(key).arg("FIELDS").arg(fields.len()).arg(fields)
+    }
+
+    /// Remove the expiration of one or more fields of a hash.  Returns a
+    /// [`HashFieldPersistStatus`](crate::HashFieldPersistStatus) per field.
+    fn hpersist<K: ToRedisArgs, F: ToRedisArgs>(key: K, fields: F) {
+        let fields = fields.to_redis_args();
+        cmd("HPERSIST").arg(key).arg("FIELDS").arg(fields.len()).arg(fields)
+    }
+
+    /// Get the values of one or more fields of a hash and set or remove their
+    /// expiration.
+    fn hget_ex<K: ToRedisArgs, F: ToRedisArgs>(key: K, fields: F, expire_at: Expiry) {
+        let (option, time_arg) = match expire_at {
+            Expiry::EX(sec) => ("EX", Some(sec)),
+            Expiry::PX(ms) => ("PX", Some(ms)),
+            Expiry::EXAT(timestamp_sec) => ("EXAT", Some(timestamp_sec)),
+            Expiry::PXAT(timestamp_ms) => ("PXAT", Some(timestamp_ms)),
+            Expiry::PERSIST => ("PERSIST", None),
+        };
+        let fields = fields.to_redis_args();
+        cmd("HGETEX").arg(key).arg(option).arg(time_arg).arg("FIELDS").arg(fields.len()).arg(fields)
+    }
+
+    /// Set one or more fields of a hash and their expiration.
+    fn hset_ex<K: ToRedisArgs, F: ToRedisArgs, V: ToRedisArgs>(key: K, options: &'a HSetExOptions, items: &'a [(F, V)]) {
+        cmd("HSETEX").arg(key).arg(options).arg("FIELDS").arg(items.len()).arg(items)
+    }
+
     // list operations
 
     /// Pop an element from a list, push it to another list
@@ -492,11 +737,27 @@ implement_commands! {
         cmd("SINTERSTORE").arg(dstkey).arg(keys)
     }
 
+    /// Return the number of members in the intersection of multiple sets.
+    fn sintercard<K: ToRedisArgs>(keys: &'a [K]) {
+        cmd("SINTERCARD").arg(keys.len()).arg(keys)
+    }
+
+    /// Return the number of members in the intersection of multiple sets,
+    /// stopping to count at `limit`.
+    fn sintercard_limit<K: ToRedisArgs>(keys: &'a [K], limit: usize) {
+        cmd("SINTERCARD").arg(keys.len()).arg(keys).arg("LIMIT").arg(limit)
+    }
+
     /// Determine if a given value is a member of a set.
     fn sismember<K: ToRedisArgs, M: ToRedisArgs>(key: K, member: M) {
         cmd("SISMEMBER").arg(key).arg(member)
     }
 
+    /// Determine for each of the given values if it is a member of a set.
+    fn smismember<K: ToRedisArgs, M: ToRedisArgs>(key: K, members: M) {
+        cmd("SMISMEMBER").arg(key).arg(members)
+    }
+
     /// Get all the members in a set.
     fn smembers<K: ToRedisArgs>(key: K) {
         cmd("SMEMBERS").arg(key)
@@ -607,6 +868,52 @@ implement_commands! {
         cmd("ZINTERSTORE").arg(dstkey).arg(keys.len()).arg(keys).arg("AGGREGATE").arg("MAX").arg("WEIGHTS").arg(weights)
     }
 
+    /// Intersect multiple sorted sets and return the resulting members.
+    fn zinter<K: ToRedisArgs>(keys: &'a [K]) {
+        cmd("ZINTER").arg(keys.len()).arg(keys)
+    }
+
+    /// Intersect multiple sorted sets and return the resulting members with
+    /// their scores.
+    fn zinter_withscores<K: ToRedisArgs>(keys: &'a [K]) {
+        cmd("ZINTER").arg(keys.len()).arg(keys).arg("WITHSCORES")
+    }
+
+    /// Intersect multiple sorted sets with weights and an aggregation
+    /// function, see [`ZAggregateOptions`].
+    fn zinter_options<K: ToRedisArgs>(keys: &'a [K], options: &'a ZAggregateOptions) {
+        cmd("ZINTER").arg(keys.len()).arg(keys).arg(options)
+    }
+
+    /// Return the number of members in the intersection of multiple sorted sets.
+    fn zintercard<K: ToRedisArgs>(keys: &'a [K]) {
+        cmd("ZINTERCARD").arg(keys.len()).arg(keys)
+    }
+
+    /// Return the number of members in the intersection of multiple sorted
+    /// sets, stopping to count at `limit`.
+    fn zintercard_limit<K: ToRedisArgs>(keys: &'a [K], limit: usize) {
+        cmd("ZINTERCARD").arg(keys.len()).arg(keys).arg("LIMIT").arg(limit)
+    }
+
+    /// Return the members of the first sorted set that are in none of the
+    /// other sorted sets.
+    fn zdiff<K: ToRedisArgs>(keys: &'a [K]) {
+        cmd("ZDIFF").arg(keys.len()).arg(keys)
+    }
+
+    /// Return the members of the first sorted set that are in none of the
+    /// other sorted sets, with their scores.
+    fn zdiff_withscores<K: ToRedisArgs>(keys: &'a [K]) {
+        cmd("ZDIFF").arg(keys.len()).arg(keys).arg("WITHSCORES")
+    }
+
+    /// Store the members of the first sorted set that are in none of the
+    /// other sorted sets in a new key.
+    fn zdiffstore<D: ToRedisArgs, K: ToRedisArgs>(dstkey: D, keys: &'a [K]) {
+        cmd("ZDIFFSTORE").arg(dstkey).arg(keys.len()).arg(keys)
+    }
+
     /// Count the number of members in a sorted set between a given lexicographical range.
     fn zlexcount<K: ToRedisArgs, M: ToRedisArgs, MM: ToRedisArgs>(key: K, min: M, max: MM) {
         cmd("ZLEXCOUNT").arg(key).arg(min).arg(max)
@@ -680,6 +987,34 @@ implement_commands! {
         cmd("ZRANGE").arg(key).arg(start).arg(stop).arg("WITHSCORES")
     }
 
+    /// Return a range of members in a sorted set, by index, score or
+    /// lexicographical range depending on the [`ZRangeOptions`].
+    ///
+    /// ```rust,no_run
+    /// # use redis::{Commands, RedisResult, ZRangeOptions};
+    /// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    /// # let mut con = client.get_connection().unwrap();
+    /// // the two highest scores of at most 100, with their members
+    /// let opts = ZRangeOptions::default().by_score().rev().limit(0, 2).with_scores();
+    /// let top: Vec<(String, f64)> = con.zrange_options("scores", 100, "-inf", &opts).unwrap();
+    /// ```
+    fn zrange_options<K: ToRedisArgs, S: ToRedisArgs, E: ToRedisArgs>(
+            key: K, start: S, stop: E, options: &'a ZRangeOptions) {
+        cmd("ZRANGE").arg(key).arg(start).arg(stop).arg(options)
+    }
+
+    /// Store a range of members of a sorted set, by index, in a new key.
+    fn zrangestore<D: ToRedisArgs, K: ToRedisArgs>(dstkey: D, srckey: K, start: isize, stop: isize) {
+        cmd("ZRANGESTORE").arg(dstkey).arg(srckey).arg(start).arg(stop)
+    }
+
+    /// Store a range of members of a sorted set in a new key, depending on
+    /// the [`ZRangeOptions`].  The server rejects `WITHSCORES` here.
+    fn zrangestore_options<D: ToRedisArgs, K: ToRedisArgs, S: ToRedisArgs, E: ToRedisArgs>(
+            dstkey: D, srckey: K, start: S, stop: E, options: &'a ZRangeOptions) {
+        cmd("ZRANGESTORE").arg(dstkey).arg(srckey).arg(start).arg(stop).arg(options)
+    }
+
     /// Return a range of members in a sorted set, by lexicographical range.
     fn zrangebylex<K: ToRedisArgs, M: ToRedisArgs, MM: ToRedisArgs>(key: K, min: M, max: MM) {
         cmd("ZRANGEBYLEX").arg(key).arg(min).arg(max)
@@ -797,7 +1132,8 @@ implement_commands! {
         cmd("ZSCORE").arg(key).arg(member)
     }
 
-    /// Get the scores associated with multiple members in a sorted set.
+    /// Get the scores associated with multiple members in a sorted set
+    /// (`ZMSCORE`).
     fn zscore_multiple<K: ToRedisArgs, M: ToRedisArgs>(key: K, members: &'a [M]) {
         cmd("ZMSCORE").arg(key).arg(members)
     }
@@ -844,6 +1180,23 @@ implement_commands! {
         cmd("ZUNIONSTORE").arg(dstkey).arg(keys.len()).arg(keys).arg("AGGREGATE").arg("MAX").arg("WEIGHTS").arg(weights)
     }
 
+    /// Unions multiple sorted sets and return the resulting members.
+    fn zunion<K: ToRedisArgs>(keys: &'a [K]) {
+        cmd("ZUNION").arg(keys.len()).arg(keys)
+    }
+
+    /// Unions multiple sorted sets and return the resulting members with
+    /// their scores.
+    fn zunion_withscores<K: ToRedisArgs>(keys: &'a [K]) {
+        cmd("ZUNION").arg(keys.len()).arg(keys).arg("WITHSCORES")
+    }
+
+    /// Unions multiple sorted sets with weights and an aggregation function,
+    /// see [`ZAggregateOptions`].
+    fn zunion_options<K: ToRedisArgs>(keys: &'a [K], options: &'a ZAggregateOptions) {
+        cmd("ZUNION").arg(keys.len()).arg(keys).arg(options)
+    }
+
     // hyperloglog commands
 
     /// Adds the specified elements to the specified HyperLogLog.
@@ -889,6 +1242,111 @@ implement_commands! {
         cmd("OBJECT").arg("REFCOUNT").arg(key)
     }
 
+    /// Returns the help text of the `OBJECT` subcommands.
+    fn object_help<>() {
+        cmd("OBJECT").arg("HELP")
+    }
+
+    // Server commands
+
+    /// Returns information and statistics about the server.  The reply can
+    /// be read as an [`InfoDict`](crate::InfoDict) or, typed, as an
+    /// [`info::ServerInfo`](crate::info::ServerInfo).
+    fn info<>() {
+        &mut cmd("INFO")
+    }
+
+    /// Returns a single section of the information about the server.
+    ///
+    /// ```rust,no_run
+    /// # use redis::Commands;
+    /// use redis::info::{InfoSection, ServerInfo};
+    /// # fn do_something() -> redis::RedisResult<()> {
+    /// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    /// # let mut con = client.get_connection().unwrap();
+    /// let info: ServerInfo = con.info_section(InfoSection::Replication)?;
+    /// let role = info.replication.map(|replication| replication.role);
+    /// # Ok(()) }
+    /// ```
+    fn info_section<>(section: info::InfoSection) {
+        cmd("INFO").arg(section)
+    }
+
+    // Function commands
+
+    /// Loads a function library.  Fails if a library of the same name is
+    /// loaded already.
+    fn function_load<C: ToRedisArgs>(library_code: C) {
+        cmd("FUNCTION").arg("LOAD").arg(library_code)
+    }
+
+    /// Loads a function library, replacing a library of the same name.
+    fn function_load_replace<C: ToRedisArgs>(library_code: C) {
+        cmd("FUNCTION").arg("LOAD").arg("REPLACE").arg(library_code)
+    }
+
+    /// Deletes a function library and all of its functions.
+    fn function_delete<L: ToRedisArgs>(library_name: L) {
+        cmd("FUNCTION").arg("DELETE").arg(library_name)
+    }
+
+    /// Returns information about the loaded libraries, which can be read as
+    /// a `Vec<`[`LibraryInfo`](crate::LibraryInfo)`>`.
+    fn function_list<>() {
+        cmd("FUNCTION").arg("LIST")
+    }
+
+    /// Returns information about the loaded libraries whose names match
+    /// `pattern`.
+    fn function_list_library<P: ToRedisArgs>(pattern: P) {
+        cmd("FUNCTION").arg("LIST").arg("LIBRARYNAME").arg(pattern)
+    }
+
+    /// Returns information about the loaded libraries, including their
+    /// source code.
+    fn function_list_with_code<>() {
+        cmd("FUNCTION").arg("LIST").arg("WITHCODE")
+    }
+
+    /// Returns a serialized payload of all loaded libraries.
+    fn function_dump<>() {
+        cmd("FUNCTION").arg("DUMP")
+    }
+
+    /// Restores libraries from a payload of `FUNCTION DUMP`.  Fails if any
+    /// of the libraries is loaded already.
+    fn function_restore<P: ToRedisArgs>(payload: P) {
+        cmd("FUNCTION").arg("RESTORE").arg(payload)
+    }
+
+    /// Restores libraries from a payload of `FUNCTION DUMP`, handling the
+    /// loaded libraries according to `policy`.
+    fn function_restore_policy<P: ToRedisArgs>(payload: P, policy: FunctionRestorePolicy) {
+        cmd("FUNCTION").arg("RESTORE").arg(payload).arg(policy)
+    }
+
+    /// Deletes all libraries.
+    fn function_flush<>() {
+        cmd("FUNCTION").arg("FLUSH")
+    }
+
+    /// Returns information about the running function and the available
+    /// engines.
+    fn function_stats<>() {
+        cmd("FUNCTION").arg("STATS")
+    }
+
+    /// Calls a function.  See [`Function`](crate::Function) for a handle
+    /// that loads its library on demand.
+    fn fcall<F: ToRedisArgs, K: ToRedisArgs, A: ToRedisArgs>(function: F, keys: K, args: A) {
+        cmd("FCALL").arg(function).arg(keys.to_redis_args().len()).arg(keys).arg(args)
+    }
+
+    /// Calls a read only function.
+    fn fcall_ro<F: ToRedisArgs, K: ToRedisArgs, A: ToRedisArgs>(function: F, keys: K, args: A) {
+        cmd("FCALL_RO").arg(function).arg(keys.to_redis_args().len()).arg(keys).arg(args)
+    }
+
     // ACL commands
 
     /// When Redis is configured to use an ACL file (with the aclfile
@@ -1211,6 +1669,45 @@ implement_commands! {
             .arg(options)
     }
 
+    /// Retrieve members within a circle or a box around a member or a
+    /// coordinate.  This supersedes [`geo_radius`](Self::geo_radius) and
+    /// [`geo_radius_by_member`](Self::geo_radius_by_member).
+    ///
+    /// The result can be read as a `Vec<`[`RadiusSearchResult`]`>`; the
+    /// `with_*` methods of [`GeoSearchOptions`] fill in its optional fields.
+    ///
+    /// [`RadiusSearchResult`]: ./geo/struct.RadiusSearchResult.html
+    /// [`GeoSearchOptions`]: ./geo/struct.GeoSearchOptions.html
+    ///
+    /// ```rust,no_run
+    /// use redis::{Commands, RedisResult};
+    /// use redis::geo::{GeoSearchOptions, GeoSearchShape, RadiusSearchResult, Unit};
+    ///
+    /// fn nearby(con: &mut redis::Connection) -> RedisResult<Vec<RadiusSearchResult>> {
+    ///     let shape = GeoSearchShape::Radius(200.0, Unit::Kilometers);
+    ///     let opts = GeoSearchOptions::from_member("Palermo", shape).with_dist();
+    ///     con.geo_search("Sicily", opts)
+    /// }
+    /// ```
+    #[cfg(feature = "geospatial")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "geospatial")))]
+    fn geo_search<K: ToRedisArgs>(key: K, options: geo::GeoSearchOptions) {
+        cmd("GEOSEARCH").arg(key).arg(options)
+    }
+
+    /// Store the members selected like [`geo_search`](Self::geo_search) in
+    /// the sorted set at `destination`, and return their number.  The `with_*`
+    /// options must not be used.
+    #[cfg(feature = "geospatial")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "geospatial")))]
+    fn geo_search_store<D: ToRedisArgs, S: ToRedisArgs>(
+        destination: D,
+        source: S,
+        options: geo::GeoSearchOptions
+    ) {
+        cmd("GEOSEARCHSTORE").arg(destination).arg(source).arg(options)
+    }
+
     //
     // streams commands
     //
@@ -1313,6 +1810,112 @@ implement_commands! {
 
 
 
+    /// Add a stream message with optional `NOMKSTREAM` and `MAXLEN` or
+    /// `MINID` trimming arguments. `items` can be a slice of field/value
+    /// pairs or a map.
+    ///
+    /// ```no_run
+    /// use redis::{Commands, RedisResult};
+    /// use redis::streams::{StreamAddOptions, StreamTrimOptions, StreamTrimmingMode};
+    /// let client = redis::Client::open("redis://127.0.0.1/0").unwrap();
+    /// let mut con = client.get_connection().unwrap();
+    ///
+    /// let opts = StreamAddOptions::default()
+    ///     .nomkstream()
+    ///     .trim(StreamTrimOptions::minid(StreamTrimmingMode::Approx, "1000-0"));
+    /// let id: RedisResult<Option<String>> =
+    ///     con.xadd_options("k1", "*", &[("hello", "world")], opts);
+    /// ```
+    ///
+    /// ```text
+    /// XADD key [NOMKSTREAM] [MAXLEN|MINID [~|=] <threshold> [LIMIT <count>]]
+    ///     <ID or *> [field value] [field value] ...
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    fn xadd_options<K: ToRedisArgs, ID: ToRedisArgs, I: ToRedisArgs>(
+        key: K,
+        id: ID,
+        items: I,
+        options: streams::StreamAddOptions
+    ) {
+        cmd("XADD")
+            .arg(key)
+            .arg(options)
+            .arg(id)
+            .arg(items)
+    }
+
+    /// Transfers ownership of pending messages that have been idle for at
+    /// least `min_idle_time` milliseconds, scanning the pending entries list
+    /// from `start`.
+    ///
+    /// Take note of the StreamAutoClaimReply return type.
+    ///
+    /// ```text
+    /// XAUTOCLAIM <key> <group> <consumer> <min-idle-time> <start>
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    fn xautoclaim<K: ToRedisArgs, G: ToRedisArgs, C: ToRedisArgs, MIT: ToRedisArgs, S: ToRedisArgs>(
+        key: K,
+        group: G,
+        consumer: C,
+        min_idle_time: MIT,
+        start: S
+    ) {
+        cmd("XAUTOCLAIM")
+            .arg(key)
+            .arg(group)
+            .arg(consumer)
+            .arg(min_idle_time)
+            .arg(start)
+    }
+
+    /// This is the optional arguments version of `xautoclaim`.
+    ///
+    /// ```no_run
+    /// use redis::{Commands, RedisResult};
+    /// use redis::streams::{StreamAutoClaimOptions, StreamAutoClaimReply};
+    /// let client = redis::Client::open("redis://127.0.0.1/0").unwrap();
+    /// let mut con = client.get_connection().unwrap();
+    ///
+    /// // Claim up to 10 messages of group "g1" idle for at least 60s,
+    /// // and continue with `next_stream_id` until it is "0-0".
+    /// let opts = StreamAutoClaimOptions::default().count(10);
+    /// let reply: RedisResult<StreamAutoClaimReply> =
+    ///     con.xautoclaim_options("k1", "g1", "c1", 60000, "0-0", opts);
+    /// ```
+    ///
+    /// ```text
+    /// XAUTOCLAIM <key> <group> <consumer> <min-idle-time> <start>
+    ///     [COUNT <count>] [JUSTID]
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    fn xautoclaim_options<
+        K: ToRedisArgs,
+        G: ToRedisArgs,
+        C: ToRedisArgs,
+        MIT: ToRedisArgs,
+        S: ToRedisArgs
+    >(
+        key: K,
+        group: G,
+        consumer: C,
+        min_idle_time: MIT,
+        start: S,
+        options: streams::StreamAutoClaimOptions
+    ) {
+        cmd("XAUTOCLAIM")
+            .arg(key)
+            .arg(group)
+            .arg(consumer)
+            .arg(min_idle_time)
+            .arg(start)
+            .arg(options)
+    }
+
     /// Claim pending, unacked messages, after some period of time,
     /// currently checked out by another consumer.
     ///
@@ -1462,28 +2065,49 @@ implement_commands! {
     }
 
 
-    /// Alter which `id` you want consumers to begin reading from an existing
-    /// consumer `group`.
+    /// Creates a `consumer` in an existing consumer `group`, returning
+    /// whether it was created.
     ///
     /// ```text
-    /// XGROUP SETID <key> <groupname> <id or $>
+    /// XGROUP CREATECONSUMER <key> <groupname> <consumername>
     /// ```
     #[cfg(feature = "streams")]
     #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
-    fn xgroup_setid<K: ToRedisArgs, G: ToRedisArgs, ID: ToRedisArgs>(
+    fn xgroup_createconsumer<K: ToRedisArgs, G: ToRedisArgs, C: ToRedisArgs>(
         key: K,
         group: G,
-        id: ID
+        consumer: C
     ) {
         cmd("XGROUP")
-            .arg("SETID")
+            .arg("CREATECONSUMER")
             .arg(key)
             .arg(group)
-            .arg(id)
+            .arg(consumer)
     }
 
 
-    /// Destroy an existing consumer `group` for a given stream `key`
+    /// Alter which `id` you want consumers to begin reading from an existing
+    /// consumer `group`.
+    ///
+    /// ```text
+    /// XGROUP SETID <key> <groupname> <id or $>
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    fn xgroup_setid<K: ToRedisArgs, G: ToRedisArgs, ID: ToRedisArgs>(
+        key: K,
+        group: G,
+        id: ID
+    ) {
+        cmd("XGROUP")
+            .arg("SETID")
+            .arg(key)
+            .arg(group)
+            .arg(id)
+    }
+
+
+    /// Destroy an existing consumer `group` for a given stream `key`
     ///
     /// ```text
     /// XGROUP SETID <key> <groupname> <id or $>
@@ -1573,6 +2197,40 @@ implement_commands! {
         cmd("XINFO").arg("STREAM").arg(key)
     }
 
+    /// Returns the full details of a stream, including its entries and
+    /// the pending entries lists of every consumer group and consumer.
+    /// Take note of the StreamInfoStreamFullReply return type.
+    ///
+    /// The server limits the entries and pending entries to 10 each,
+    /// see `xinfo_stream_full_count` to change that.
+    ///
+    /// ```text
+    /// XINFO STREAM <key> FULL
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    fn xinfo_stream_full<K: ToRedisArgs>(key: K) {
+        cmd("XINFO").arg("STREAM").arg(key).arg("FULL")
+    }
+
+    /// An alternate version of `xinfo_stream_full` which limits the number
+    /// of returned entries and pending entries. A `count` of 0 returns
+    /// everything.
+    ///
+    /// ```text
+    /// XINFO STREAM <key> FULL COUNT <count>
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    fn xinfo_stream_full_count<K: ToRedisArgs, C: ToRedisArgs>(key: K, count: C) {
+        cmd("XINFO")
+            .arg("STREAM")
+            .arg(key)
+            .arg("FULL")
+            .arg("COUNT")
+            .arg(count)
+    }
+
     /// Returns the number of messages for a given stream `key`.
     ///
     /// ```text
@@ -1680,6 +2338,81 @@ implement_commands! {
             .arg(consumer)
     }
 
+    /// An alternate version of `xpending_count` which only returns messages
+    /// that have been idle for at least `min_idle_time` milliseconds.
+    ///
+    /// Take note of the StreamPendingCountReply return type.
+    ///
+    /// ```text
+    /// XPENDING <key> <group> IDLE <min-idle-time> <start> <stop> <count>
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    fn xpending_idle_count<
+        K: ToRedisArgs,
+        G: ToRedisArgs,
+        MIT: ToRedisArgs,
+        S: ToRedisArgs,
+        E: ToRedisArgs,
+        C: ToRedisArgs
+    >(
+        key: K,
+        group: G,
+        min_idle_time: MIT,
+        start: S,
+        end: E,
+        count: C
+    ) {
+        cmd("XPENDING")
+            .arg(key)
+            .arg(group)
+            .arg("IDLE")
+            .arg(min_idle_time)
+            .arg(start)
+            .arg(end)
+            .arg(count)
+    }
+
+
+    /// An alternate version of `xpending_idle_count` which filters by
+    /// `consumer` name.
+    ///
+    /// Take note of the StreamPendingCountReply return type.
+    ///
+    /// ```text
+    /// XPENDING <key> <group> IDLE <min-idle-time> <start> <stop> <count> <consumer>
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    #[allow(clippy::too_many_arguments)]
+    fn xpending_idle_consumer_count<
+        K: ToRedisArgs,
+        G: ToRedisArgs,
+        MIT: ToRedisArgs,
+        S: ToRedisArgs,
+        E: ToRedisArgs,
+        C: ToRedisArgs,
+        CN: ToRedisArgs
+    >(
+        key: K,
+        group: G,
+        min_idle_time: MIT,
+        start: S,
+        end: E,
+        count: C,
+        consumer: CN
+    ) {
+        cmd("XPENDING")
+            .arg(key)
+            .arg(group)
+            .arg("IDLE")
+            .arg(min_idle_time)
+            .arg(start)
+            .arg(end)
+            .arg(count)
+            .arg(consumer)
+    }
+
     /// Returns a range of messages in a given stream `key`.
     ///
     /// Set `start` to `-` to begin at the first message.
@@ -1857,6 +2590,18 @@ implement_commands! {
     }
 
 
+    /// Sets the last delivered ID of a stream `key`.
+    ///
+    /// ```text
+    /// XSETID <key> <last-id>
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    fn xsetid<K: ToRedisArgs, ID: ToRedisArgs>(key: K, id: ID) {
+        cmd("XSETID").arg(key).arg(id)
+    }
+
+
     /// Trim a stream `key` to a MAXLEN count.
     ///
     /// ```text
@@ -1870,6 +2615,21 @@ implement_commands! {
     ) {
         cmd("XTRIM").arg(key).arg(maxlen)
     }
+
+    /// Trim a stream `key` by `MAXLEN` or `MINID`, optionally limiting the
+    /// number of evicted entries.
+    ///
+    /// ```text
+    /// XTRIM <key> MAXLEN|MINID [~|=] <threshold> [LIMIT <count>]
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    fn xtrim_options<K: ToRedisArgs>(
+        key: K,
+        options: streams::StreamTrimOptions
+    ) {
+        cmd("XTRIM").arg(key).arg(options)
+    }
 }
 
 /// Allows pubsub callbacks to stop receiving messages.
@@ -2055,6 +2815,630 @@ impl ToRedisArgs for LposOptions {
     }
 }
 
+/// The integer encoding of a bitfield, e.g. `i5` or `u8`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum BitFieldType {
+    /// A signed integer of the given width, up to 64 bits.
+    Signed(u8),
+    /// An unsigned integer of the given width, up to 63 bits.
+    Unsigned(u8),
+}
+
+impl ToRedisArgs for BitFieldType {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        match self {
+            BitFieldType::Signed(bits) => out.write_arg_fmt(format_args!("i{bits}")),
+            BitFieldType::Unsigned(bits) => out.write_arg_fmt(format_args!("u{bits}")),
+        }
+    }
+}
+
+/// The offset of a bitfield in the string.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum BitFieldOffset {
+    /// The offset in bits.
+    Bits(usize),
+    /// The offset in multiples of the width of the bitfield type, so that
+    /// `Index(2)` of `u8` is the third byte (`#2`).
+    Index(usize),
+}
+
+impl ToRedisArgs for BitFieldOffset {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        match self {
+            BitFieldOffset::Bits(bits) => out.write_arg_fmt(bits),
+            BitFieldOffset::Index(index) => out.write_arg_fmt(format_args!("#{index}")),
+        }
+    }
+}
+
+/// The `OVERFLOW` behavior of the `SET` and `INCRBY` operations following it.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum BitFieldOverflow {
+    /// Wraps around on overflow, the default.
+    Wrap,
+    /// Saturates at the minimum or maximum value.
+    Sat,
+    /// Skips the operation and replies nil.
+    Fail,
+}
+
+#[derive(Clone, Debug)]
+enum BitFieldOp {
+    Get(BitFieldType, BitFieldOffset),
+    Set(BitFieldType, BitFieldOffset, i64),
+    IncrBy(BitFieldType, BitFieldOffset, i64),
+    Overflow(BitFieldOverflow),
+}
+
+/// The operations of a [BITFIELD](https://redis.io/commands/bitfield) command,
+/// in the order they are executed.
+///
+/// # Example
+/// ```rust
+/// use redis::{BitField, BitFieldOffset, BitFieldOverflow, BitFieldType};
+/// let field = BitField::default()
+///     .overflow(BitFieldOverflow::Sat)
+///     .incr_by(BitFieldType::Unsigned(4), BitFieldOffset::Index(3), 2)
+///     .get(BitFieldType::Signed(8), BitFieldOffset::Bits(100));
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct BitField {
+    ops: Vec<BitFieldOp>,
+}
+
+impl BitField {
+    /// Adds a `GET` operation, returning the value of the bitfield.
+    pub fn get(mut self, ty: BitFieldType, offset: BitFieldOffset) -> Self {
+        self.ops.push(BitFieldOp::Get(ty, offset));
+        self
+    }
+
+    /// Adds a `SET` operation, returning the old value of the bitfield.
+    pub fn set(mut self, ty: BitFieldType, offset: BitFieldOffset, value: i64) -> Self {
+        self.ops.push(BitFieldOp::Set(ty, offset, value));
+        self
+    }
+
+    /// Adds an `INCRBY` operation, returning the new value of the bitfield.
+    pub fn incr_by(mut self, ty: BitFieldType, offset: BitFieldOffset, increment: i64) -> Self {
+        self.ops.push(BitFieldOp::IncrBy(ty, offset, increment));
+        self
+    }
+
+    /// Sets the overflow behavior of the `SET` and `INCRBY` operations
+    /// added afterwards.
+    pub fn overflow(mut self, overflow: BitFieldOverflow) -> Self {
+        self.ops.push(BitFieldOp::Overflow(overflow));
+        self
+    }
+}
+
+impl ToRedisArgs for BitField {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        for op in &self.ops {
+            match op {
+                BitFieldOp::Get(ty, offset) => {
+                    out.write_arg(b"GET");
+                    ty.write_redis_args(out);
+                    offset.write_redis_args(out);
+                }
+                BitFieldOp::Set(ty, offset, value) => {
+                    out.write_arg(b"SET");
+                    ty.write_redis_args(out);
+                    offset.write_redis_args(out);
+                    out.write_arg_fmt(value);
+                }
+                BitFieldOp::IncrBy(ty, offset, increment) => {
+                    out.write_arg(b"INCRBY");
+                    ty.write_redis_args(out);
+                    offset.write_redis_args(out);
+                    out.write_arg_fmt(increment);
+                }
+                BitFieldOp::Overflow(overflow) => {
+                    out.write_arg(b"OVERFLOW");
+                    let overflow: &[u8] = match overflow {
+                        BitFieldOverflow::Wrap => b"WRAP",
+                        BitFieldOverflow::Sat => b"SAT",
+                        BitFieldOverflow::Fail => b"FAIL",
+                    };
+                    out.write_arg(overflow);
+                }
+            }
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// Options for the [ZRANGE](https://redis.io/commands/zrange) and
+/// [ZRANGESTORE](https://redis.io/commands/zrangestore) commands
+///
+/// # Example
+/// ```rust,no_run
+/// use redis::{Commands, RedisResult, ZRangeOptions};
+/// fn page_by_name(con: &mut redis::Connection, page: isize) -> RedisResult<Vec<String>> {
+///     let opts = ZRangeOptions::default().by_lex().limit(page * 10, 10);
+///     con.zrange_options("names", "-", "+", &opts)
+/// }
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct ZRangeOptions {
+    by: Option<&'static str>,
+    rev: bool,
+    limit: Option<(isize, isize)>,
+    withscores: bool,
+}
+
+impl ZRangeOptions {
+    /// Interprets start and stop as scores (`BYSCORE`), e.g. `1`, `(1` or
+    /// `+inf`, instead of indexes.
+    pub fn by_score(mut self) -> Self {
+        self.by = Some("BYSCORE");
+        self
+    }
+
+    /// Interprets start and stop as lexicographical ranges (`BYLEX`), e.g.
+    /// `[a`, `(b` or `-`, instead of indexes.
+    pub fn by_lex(mut self) -> Self {
+        self.by = Some("BYLEX");
+        self
+    }
+
+    /// Orders the members from the highest to the lowest score (`REV`).
+    /// Start and stop then have to be given from high to low as well.
+    pub fn rev(mut self) -> Self {
+        self.rev = true;
+        self
+    }
+
+    /// Skips `offset` members and returns at most `count` members, a
+    /// negative count returning all of them.  Only valid with `by_score` or
+    /// `by_lex`.
+    pub fn limit(mut self, offset: isize, count: isize) -> Self {
+        self.limit = Some((offset, count));
+        self
+    }
+
+    /// Returns the scores along with the members.
+    pub fn with_scores(mut self) -> Self {
+        self.withscores = true;
+        self
+    }
+}
+
+impl ToRedisArgs for ZRangeOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if let Some(by) = self.by {
+            out.write_arg(by.as_bytes());
+        }
+        if self.rev {
+            out.write_arg(b"REV");
+        }
+        if let Some((offset, count)) = self.limit {
+            out.write_arg(b"LIMIT");
+            out.write_arg_fmt(offset);
+            out.write_arg_fmt(count);
+        }
+        if self.withscores {
+            out.write_arg(b"WITHSCORES");
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// Options for the [COPY](https://redis.io/commands/copy) command
+///
+/// # Example
+/// ```rust,no_run
+/// use redis::{Commands, CopyOptions, RedisResult};
+/// fn backup(con: &mut redis::Connection, key: &str) -> RedisResult<bool> {
+///     con.copy(key, key, &CopyOptions::default().db(1).replace())
+/// }
+/// ```
+#[derive(Default, Clone, Copy, Debug)]
+pub struct CopyOptions {
+    db: Option<i64>,
+    replace: bool,
+}
+
+impl CopyOptions {
+    /// Copies the key into another database.
+    pub fn db(mut self, db: i64) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Overwrites the destination key if it already exists.
+    pub fn replace(mut self) -> Self {
+        self.replace = true;
+        self
+    }
+}
+
+impl ToRedisArgs for CopyOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if let Some(db) = self.db {
+            out.write_arg(b"DB");
+            out.write_arg_fmt(db);
+        }
+        if self.replace {
+            out.write_arg(b"REPLACE");
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// Options for the [RESTORE](https://redis.io/commands/restore) command
+///
+/// # Example
+/// ```rust,no_run
+/// use redis::{Commands, RedisResult, RestoreOptions};
+/// fn clone_key(con: &mut redis::Connection, from: &str, to: &str) -> RedisResult<()> {
+///     let value: Vec<u8> = con.dump(from)?;
+///     con.restore(to, 0, value, &RestoreOptions::default().replace())
+/// }
+/// ```
+#[derive(Default, Clone, Copy, Debug)]
+pub struct RestoreOptions {
+    replace: bool,
+    absttl: bool,
+    idletime: Option<usize>,
+    freq: Option<usize>,
+}
+
+impl RestoreOptions {
+    /// Overwrites the key if it already exists.
+    pub fn replace(mut self) -> Self {
+        self.replace = true;
+        self
+    }
+
+    /// Interprets the ttl as a UNIX timestamp in milliseconds instead of a
+    /// duration.
+    pub fn abs_ttl(mut self) -> Self {
+        self.absttl = true;
+        self
+    }
+
+    /// Sets the idle time of the key, in seconds, for the `LRU` eviction
+    /// policies.
+    pub fn idle_time(mut self, seconds: usize) -> Self {
+        self.idletime = Some(seconds);
+        self
+    }
+
+    /// Sets the access frequency counter of the key for the `LFU` eviction
+    /// policies.
+    pub fn freq(mut self, frequency: usize) -> Self {
+        self.freq = Some(frequency);
+        self
+    }
+}
+
+impl ToRedisArgs for RestoreOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if self.replace {
+            out.write_arg(b"REPLACE");
+        }
+        if self.absttl {
+            out.write_arg(b"ABSTTL");
+        }
+        if let Some(idletime) = self.idletime {
+            out.write_arg(b"IDLETIME");
+            out.write_arg_fmt(idletime);
+        }
+        if let Some(freq) = self.freq {
+            out.write_arg(b"FREQ");
+            out.write_arg_fmt(freq);
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// Options for the [MIGRATE](https://redis.io/commands/migrate) command
+///
+/// # Example
+/// ```rust,no_run
+/// use redis::{Commands, MigrateOptions, RedisResult};
+/// fn move_keys(con: &mut redis::Connection) -> RedisResult<String> {
+///     let opts = MigrateOptions::default().replace().auth2("migrator", "secret");
+///     con.migrate("10.0.0.2", 6379, &["a", "b"], 0, 5000, &opts)
+/// }
+/// ```
+#[derive(Default, Clone)]
+pub struct MigrateOptions {
+    copy: bool,
+    replace: bool,
+    auth: Option<(Option<String>, String)>,
+}
+
+impl std::fmt::Debug for MigrateOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // never print the password of the target instance
+        let auth = self
+            .auth
+            .as_ref()
+            .map(|(username, _)| (username, "<redacted>"));
+        f.debug_struct("MigrateOptions")
+            .field("copy", &self.copy)
+            .field("replace", &self.replace)
+            .field("auth", &auth)
+            .finish()
+    }
+}
+
+impl MigrateOptions {
+    /// Keeps the keys on the source instance.
+    pub fn copy(mut self) -> Self {
+        self.copy = true;
+        self
+    }
+
+    /// Overwrites existing keys on the target instance.
+    pub fn replace(mut self) -> Self {
+        self.replace = true;
+        self
+    }
+
+    /// Authenticates with the target instance using `AUTH password`.
+    pub fn auth(mut self, password: &str) -> Self {
+        self.auth = Some((None, password.to_string()));
+        self
+    }
+
+    /// Authenticates with the target instance using `AUTH2 username
+    /// password`.
+    pub fn auth2(mut self, username: &str, password: &str) -> Self {
+        self.auth = Some((Some(username.to_string()), password.to_string()));
+        self
+    }
+}
+
+impl ToRedisArgs for MigrateOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if self.copy {
+            out.write_arg(b"COPY");
+        }
+        if self.replace {
+            out.write_arg(b"REPLACE");
+        }
+        match self.auth {
+            Some((None, ref password)) => {
+                out.write_arg(b"AUTH");
+                out.write_arg(password.as_bytes());
+            }
+            Some((Some(ref username), ref password)) => {
+                out.write_arg(b"AUTH2");
+                out.write_arg(username.as_bytes());
+                out.write_arg(password.as_bytes());
+            }
+            None => {}
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// Options for the [SORT](https://redis.io/commands/sort) command
+///
+/// # Example
+/// ```rust,no_run
+/// use redis::{Commands, RedisResult, SortOptions};
+/// fn top_names(con: &mut redis::Connection) -> RedisResult<Vec<Option<String>>> {
+///     let opts = SortOptions::default()
+///         .by("user:*->score")
+///         .get("user:*->name")
+///         .desc()
+///         .limit(0, 10);
+///     con.sort("user_ids", &opts)
+/// }
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct SortOptions {
+    by: Option<Vec<Vec<u8>>>,
+    limit: Option<(isize, isize)>,
+    get: Vec<Vec<Vec<u8>>>,
+    desc: bool,
+    alpha: bool,
+    store: Option<Vec<Vec<u8>>>,
+}
+
+impl SortOptions {
+    /// Sorts by the values of the keys the pattern resolves to, with `*`
+    /// replaced by each element.  `nosort` skips sorting altogether.
+    pub fn by<P: ToRedisArgs>(mut self, pattern: P) -> Self {
+        self.by = Some(pattern.to_redis_args());
+        self
+    }
+
+    /// Skips `offset` elements and returns at most `count` elements.
+    pub fn limit(mut self, offset: isize, count: isize) -> Self {
+        self.limit = Some((offset, count));
+        self
+    }
+
+    /// Returns the values of the keys the pattern resolves to instead of
+    /// the elements.  Can be given multiple times, `#` returning the
+    /// element itself.
+    pub fn get<P: ToRedisArgs>(mut self, pattern: P) -> Self {
+        self.get.push(pattern.to_redis_args());
+        self
+    }
+
+    /// Sorts from the largest to the smallest element instead of ascending.
+    pub fn desc(mut self) -> Self {
+        self.desc = true;
+        self
+    }
+
+    /// Sorts the elements lexicographically instead of as numbers.
+    pub fn alpha(mut self) -> Self {
+        self.alpha = true;
+        self
+    }
+
+    /// Stores the result in the list at `destination`, the command then
+    /// returning its length.
+    pub fn store<K: ToRedisArgs>(mut self, destination: K) -> Self {
+        self.store = Some(destination.to_redis_args());
+        self
+    }
+}
+
+impl ToRedisArgs for SortOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if let Some(ref by) = self.by {
+            out.write_arg(b"BY");
+            by.write_redis_args(out);
+        }
+        if let Some((offset, count)) = self.limit {
+            out.write_arg(b"LIMIT");
+            out.write_arg_fmt(offset);
+            out.write_arg_fmt(count);
+        }
+        for pattern in &self.get {
+            out.write_arg(b"GET");
+            pattern.write_redis_args(out);
+        }
+        if self.desc {
+            out.write_arg(b"DESC");
+        }
+        if self.alpha {
+            out.write_arg(b"ALPHA");
+        }
+        if let Some(ref store) = self.store {
+            out.write_arg(b"STORE");
+            store.write_redis_args(out);
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// The function used to aggregate the scores of a member that is part of
+/// multiple sorted sets.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ZAggregate {
+    /// Sum the scores, the default.
+    Sum,
+    /// Use the lowest score.
+    Min,
+    /// Use the highest score.
+    Max,
+}
+
+/// Options for the [ZINTER](https://redis.io/commands/zinter) and
+/// [ZUNION](https://redis.io/commands/zunion) commands
+///
+/// # Example
+/// ```rust,no_run
+/// use redis::{Commands, RedisResult, ZAggregate, ZAggregateOptions};
+/// fn best_of(con: &mut redis::Connection) -> RedisResult<Vec<(String, f64)>> {
+///     let opts = ZAggregateOptions::default()
+///         .weights(&[1.0, 2.0])
+///         .aggregate(ZAggregate::Max)
+///         .with_scores();
+///     con.zunion_options(&["scores:2022", "scores:2023"], &opts)
+/// }
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct ZAggregateOptions {
+    weights: Option<Vec<f64>>,
+    aggregate: Option<ZAggregate>,
+    withscores: bool,
+}
+
+impl ZAggregateOptions {
+    /// Multiplies the scores of each sorted set by the weight at the same
+    /// position.
+    pub fn weights(mut self, weights: &[f64]) -> Self {
+        self.weights = Some(weights.to_vec());
+        self
+    }
+
+    /// Sets how the scores of a member are aggregated.
+    pub fn aggregate(mut self, aggregate: ZAggregate) -> Self {
+        self.aggregate = Some(aggregate);
+        self
+    }
+
+    /// Returns the scores along with the members.
+    pub fn with_scores(mut self) -> Self {
+        self.withscores = true;
+        self
+    }
+}
+
+impl ToRedisArgs for ZAggregateOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if let Some(ref weights) = self.weights {
+            out.write_arg(b"WEIGHTS");
+            for weight in weights {
+                weight.write_redis_args(out);
+            }
+        }
+        if let Some(aggregate) = self.aggregate {
+            out.write_arg(b"AGGREGATE");
+            let aggregate: &[u8] = match aggregate {
+                ZAggregate::Sum => b"SUM",
+                ZAggregate::Min => b"MIN",
+                ZAggregate::Max => b"MAX",
+            };
+            out.write_arg(aggregate);
+        }
+        if self.withscores {
+            out.write_arg(b"WITHSCORES");
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
 /// Enum for the LEFT | RIGHT args used by some commands
 pub enum Direction {
     /// Targets the first element (head) of the list
@@ -2076,6 +3460,75 @@ impl ToRedisArgs for Direction {
     }
 }
 
+/// Options for the [HSETEX](https://redis.io/commands/hsetex) command
+///
+/// # Example
+/// ```rust,no_run
+/// use redis::{Commands, RedisResult, HSetExOptions, SetExpiry, ExistenceCheck};
+/// fn set_fields(con: &mut redis::Connection, key: &str) -> RedisResult<bool> {
+///     let opts = HSetExOptions::default()
+///         .conditional_set(ExistenceCheck::NX)
+///         .with_expiration(SetExpiry::EX(60));
+///     con.hset_ex(key, &opts, &[("field1", "a"), ("field2", "b")])
+/// }
+/// ```
+#[derive(Default)]
+pub struct HSetExOptions {
+    conditional_set: Option<ExistenceCheck>,
+    expiration: Option<SetExpiry>,
+}
+
+impl HSetExOptions {
+    /// Only set the fields if none (`FNX`) or all (`FXX`) of them exist
+    pub fn conditional_set(mut self, existence_check: ExistenceCheck) -> Self {
+        self.conditional_set = Some(existence_check);
+        self
+    }
+
+    /// Set the expiration of the fields
+    pub fn with_expiration(mut self, expiration: SetExpiry) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+}
+
+impl ToRedisArgs for HSetExOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        match self.conditional_set {
+            Some(ExistenceCheck::NX) => out.write_arg(b"FNX"),
+            Some(ExistenceCheck::XX) => out.write_arg(b"FXX"),
+            None => {}
+        }
+        match self.expiration {
+            Some(SetExpiry::EX(secs)) => {
+                out.write_arg(b"EX");
+                out.write_arg_fmt(secs);
+            }
+            Some(SetExpiry::PX(millis)) => {
+                out.write_arg(b"PX");
+                out.write_arg_fmt(millis);
+            }
+            Some(SetExpiry::EXAT(unix_time)) => {
+                out.write_arg(b"EXAT");
+                out.write_arg_fmt(unix_time);
+            }
+            Some(SetExpiry::PXAT(unix_time)) => {
+                out.write_arg(b"PXAT");
+                out.write_arg_fmt(unix_time);
+            }
+            Some(SetExpiry::KEEPTTL) => out.write_arg(b"KEEPTTL"),
+            None => {}
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
 /// Options for the [SET](https://redis.io/commands/set) command
 ///
 /// # Example
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::fmt;
 use std::io::{self, Write};
@@ -11,11 +12,10 @@ use crate::cmd::{cmd, pipe, Cmd};
 use crate::parser::Parser;
 use crate::pipeline::Pipeline;
 use crate::types::{
-    from_redis_value, ErrorKind, FromRedisValue, RedisError, RedisResult, ToRedisArgs, Value,
+    from_redis_value, ErrorKind, FromRedisValue, HashMap, ParserLimits, ProtocolVersion,
+    RedisError, RedisResult, ToRedisArgs, Value,
 };
 
-#[cfg(unix)]
-use crate::types::HashMap;
 #[cfg(unix)]
 use std::os::unix::net::UnixStream;
 
@@ -222,6 +222,10 @@ pub struct RedisConnectionInfo {
     pub username: Option<String>,
     /// Optionally a password that should be used for connection.
     pub password: Option<String>,
+    /// The protocol version the connection should negotiate with the server.
+    pub protocol: ProtocolVersion,
+    /// Limits on the size and nesting of replies accepted from the server.
+    pub parser_limits: ParserLimits,
 }
 
 impl FromStr for ConnectionInfo {
@@ -254,7 +258,8 @@ impl IntoConnectionInfo for ConnectionInfo {
 /// - Specifying DB: `redis://127.0.0.1:6379/0`
 /// - Enabling TLS: `rediss://127.0.0.1:6379`
 /// - Enabling Insecure TLS: `rediss://127.0.0.1:6379/#insecure`
-impl<'a> IntoConnectionInfo for &'a str {
+/// - Enabling RESP3: `redis://127.0.0.1:6379/?protocol=resp3`
+impl IntoConnectionInfo for &str {
     fn into_connection_info(self) -> RedisResult<ConnectionInfo> {
         match parse_redis_url(self) {
             Some(u) => u.into_connection_info(),
@@ -283,6 +288,7 @@ where
 /// - Specifying DB: `redis://127.0.0.1:6379/0`
 /// - Enabling TLS: `rediss://127.0.0.1:6379`
 /// - Enabling Insecure TLS: `rediss://127.0.0.1:6379/#insecure`
+/// - Enabling RESP3: `redis://127.0.0.1:6379/?protocol=resp3`
 impl IntoConnectionInfo for String {
     fn into_connection_info(self) -> RedisResult<ConnectionInfo> {
         match parse_redis_url(&self) {
@@ -292,6 +298,17 @@ impl IntoConnectionInfo for String {
     }
 }
 
+fn parse_protocol(query: &HashMap<Cow<str>, Cow<str>>) -> RedisResult<ProtocolVersion> {
+    Ok(match query.get("protocol") {
+        Some(protocol) => match protocol.as_ref() {
+            "2" | "resp2" => ProtocolVersion::RESP2,
+            "3" | "resp3" => ProtocolVersion::RESP3,
+            _ => fail!((ErrorKind::InvalidClientConfig, "Invalid protocol version")),
+        },
+        None => ProtocolVersion::RESP2,
+    })
+}
+
 fn url_to_tcp_connection_info(url: url::Url) -> RedisResult<ConnectionInfo> {
     let host = match url.host() {
         Some(host) => {
@@ -346,6 +363,7 @@ fn url_to_tcp_connection_info(url: url::Url) -> RedisResult<ConnectionInfo> {
     } else {
         ConnectionAddr::Tcp(host, port)
     };
+    let query: HashMap<_, _> = url.query_pairs().collect();
     Ok(ConnectionInfo {
         addr,
         redis: RedisConnectionInfo {
@@ -377,6 +395,8 @@ fn url_to_tcp_connection_info(url: url::Url) -> RedisResult<ConnectionInfo> {
                 },
                 None => None,
             },
+            protocol: parse_protocol(&query)?,
+            parser_limits: ParserLimits::default(),
         },
     })
 }
@@ -399,6 +419,8 @@ fn url_to_unix_connection_info(url: url::Url) -> RedisResult<ConnectionInfo> {
             },
             username: query.get("user").map(|username| username.to_string()),
             password: query.get("pass").map(|password| password.to_string()),
+            protocol: parse_protocol(&query)?,
+            parser_limits: ParserLimits::default(),
         },
     })
 }
@@ -898,6 +920,55 @@ fn connect_auth(con: &mut Connection, connection_info: &RedisConnectionInfo) ->
     }
 }
 
+/// Parses the `(kind, channel, remaining subscriptions)` reply of an
+/// unsubscribe, which RESP3 delivers as a push.
+pub(crate) fn parse_unsubscribe_reply(value: Value) -> RedisResult<(Vec<u8>, (), isize)> {
+    match value {
+        Value::Push { kind, data } => from_redis_value(&Value::Bulk(
+            std::iter::once(Value::Data(kind.to_string().into_bytes()))
+                .chain(data)
+                .collect(),
+        )),
+        value => from_redis_value(&value),
+    }
+}
+
+/// Builds the `HELLO 3` command, authenticating in the same roundtrip when
+/// a password is configured.
+pub(crate) fn hello_cmd(connection_info: &RedisConnectionInfo) -> Cmd {
+    let mut command = cmd("HELLO");
+    command.arg(3);
+    if let Some(password) = &connection_info.password {
+        let username = connection_info.username.as_deref().unwrap_or("default");
+        command.arg("AUTH").arg(username).arg(password);
+    }
+    command
+}
+
+/// Maps a failed `HELLO 3` reply onto the error reported to the user.
+pub(crate) fn hello_error(err: RedisError) -> RedisError {
+    match err.code() {
+        Some("NOPROTO") => RedisError::from((
+            ErrorKind::RESP3NotSupported,
+            "Redis server does not support RESP3",
+        )),
+        Some("WRONGPASS") => RedisError::from((
+            ErrorKind::AuthenticationFailed,
+            "Password authentication failed",
+        )),
+        _ if err
+            .detail()
+            .map_or(false, |detail| detail.contains("unknown command")) =>
+        {
+            RedisError::from((
+                ErrorKind::RESP3NotSupported,
+                "Redis server does not support RESP3",
+            ))
+        }
+        _ => err,
+    }
+}
+
 pub fn connect(
     connection_info: &ConnectionInfo,
     timeout: Option<Duration>,
@@ -929,12 +1000,16 @@ fn setup_connection(
 ) -> RedisResult<Connection> {
     let mut rv = Connection {
         con,
-        parser: Parser::new(),
+        parser: Parser::with_limits(connection_info.parser_limits),
         db: connection_info.db,
         pubsub: false,
     };
 
-    if connection_info.password.is_some() {
+    if connection_info.protocol == ProtocolVersion::RESP3 {
+        hello_cmd(connection_info)
+            .query::<Value>(&mut rv)
+            .map_err(hello_error)?;
+    } else if connection_info.password.is_some() {
         connect_auth(&mut rv, connection_info)?;
     }
 
@@ -1107,7 +1182,7 @@ impl Connection {
         let mut received_unsub = false;
         let mut received_punsub = false;
         loop {
-            let res: (Vec<u8>, (), isize) = from_redis_value(&self.recv_response()?)?;
+            let res = parse_unsubscribe_reply(self.recv_response()?)?;
 
             match res.0.first() {
                 Some(&b'u') => received_unsub = true,
@@ -1178,6 +1253,18 @@ impl Connection {
         }
         result
     }
+
+    /// Fetches the reply to a request.  RESP3 push frames, such as
+    /// invalidation messages, can arrive at any time and are not replies to
+    /// anything, so they are skipped.
+    fn read_reply(&mut self) -> RedisResult<Value> {
+        loop {
+            match self.read_response()? {
+                Value::Push { .. } => continue,
+                value => return Ok(value),
+            }
+        }
+    }
 }
 
 impl ConnectionLike for Connection {
@@ -1187,7 +1274,7 @@ impl ConnectionLike for Connection {
         }
 
         self.con.send_bytes(cmd)?;
-        self.read_response()
+        self.read_reply()
     }
 
     fn req_packed_commands(
@@ -1207,7 +1294,7 @@ impl ConnectionLike for Connection {
             // We need to keep processing the rest of the responses in that case,
             // so bailing early with `?` would not be correct.
             // See: https://github.com/redis-rs/redis-rs/issues/436
-            let response = self.read_response();
+            let response = self.read_reply();
             match response {
                 Ok(item) => {
                     if idx >= offset {
@@ -1216,7 +1303,7 @@ impl ConnectionLike for Connection {
                 }
                 Err(err) => {
                     if first_err.is_none() {
-                        first_err = Some(err);
+                        first_err = Some(err.with_pipeline_index(idx));
                     }
                 }
             }
@@ -1307,14 +1394,19 @@ impl<'a> PubSub<'a> {
     }
 
     fn cache_messages_until_received_response(&mut self, cmd: &Cmd) -> RedisResult<()> {
-        let mut response = self.con.req_packed_command(&cmd.get_packed_command())?;
+        // With RESP3 the confirmation is itself a push frame, which the
+        // regular request path skips, so read the responses directly.
+        if self.con.pubsub {
+            self.con.exit_pubsub()?;
+        }
+        self.con.send_packed_command(&cmd.get_packed_command())?;
         loop {
+            let response = self.con.recv_response()?;
             if let Some(msg) = Msg::from_value(&response) {
                 self.waiting_messages.push_back(msg);
             } else {
                 return Ok(());
             }
-            response = self.con.recv_response()?;
         }
     }
 
@@ -1378,9 +1470,16 @@ impl<'a> Drop for PubSub<'a> {
 impl Msg {
     /// Tries to convert provided [`Value`] into [`Msg`].
     pub fn from_value(value: &Value) -> Option<Self> {
-        let raw_msg: Vec<Value> = from_redis_value(value).ok()?;
+        let (msg_type, raw_msg): (String, Vec<Value>) = match value {
+            Value::Push { kind, data } => (kind.to_string(), data.clone()),
+            _ => {
+                let raw_msg: Vec<Value> = from_redis_value(value).ok()?;
+                let mut iter = raw_msg.into_iter();
+                let msg_type: String = from_redis_value(&iter.next()?).ok()?;
+                (msg_type, iter.collect())
+            }
+        };
         let mut iter = raw_msg.into_iter();
-        let msg_type: String = from_redis_value(&iter.next()?).ok()?;
         let mut pattern = None;
         let payload;
         let channel;
@@ -1563,6 +1662,18 @@ mod tests {
                         db: 2,
                         username: Some("%johndoe%".to_string()),
                         password: Some("#@<>$".to_string()),
+                        protocol: ProtocolVersion::RESP2,
+                        parser_limits: ParserLimits::default(),
+                    },
+                },
+            ),
+            (
+                url::Url::parse("redis://127.0.0.1/?protocol=resp3").unwrap(),
+                ConnectionInfo {
+                    addr: ConnectionAddr::Tcp("127.0.0.1".to_string(), 6379),
+                    redis: RedisConnectionInfo {
+                        protocol: ProtocolVersion::RESP3,
+                        ..Default::default()
                     },
                 },
             ),
@@ -1582,6 +1693,10 @@ mod tests {
                 res.redis.password, expected.redis.password,
                 "password of {url} is not expected",
             );
+            assert_eq!(
+                res.redis.protocol, expected.redis.protocol,
+                "protocol of {url} is not expected",
+            );
         }
     }
 
@@ -1601,6 +1716,10 @@ mod tests {
                 url::Url::parse("redis://:C3%B0@127.0.0.1").unwrap(),
                 "Password is not valid UTF-8 string",
             ),
+            (
+                url::Url::parse("redis://127.0.0.1/?protocol=4").unwrap(),
+                "Invalid protocol version",
+            ),
         ];
         for (url, expected) in cases.into_iter() {
             let res = url_to_tcp_connection_info(url).unwrap_err();
@@ -1629,6 +1748,8 @@ mod tests {
                         db: 0,
                         username: None,
                         password: None,
+                        protocol: ProtocolVersion::RESP2,
+                        parser_limits: ParserLimits::default(),
                     },
                 },
             ),
@@ -1640,6 +1761,8 @@ mod tests {
                         db: 1,
                         username: None,
                         password: None,
+                        protocol: ProtocolVersion::RESP2,
+                        parser_limits: ParserLimits::default(),
                     },
                 },
             ),
@@ -1654,6 +1777,8 @@ mod tests {
                         db: 2,
                         username: Some("%johndoe%".to_string()),
                         password: Some("#@<>$".to_string()),
+                        protocol: ProtocolVersion::RESP2,
+                        parser_limits: ParserLimits::default(),
                     },
                 },
             ),
@@ -1668,6 +1793,18 @@ mod tests {
                         db: 2,
                         username: Some("%johndoe%".to_string()),
                         password: Some("&?= *+".to_string()),
+                        protocol: ProtocolVersion::RESP2,
+                        parser_limits: ParserLimits::default(),
+                    },
+                },
+            ),
+            (
+                url::Url::parse("unix:///example.sock?protocol=3").unwrap(),
+                ConnectionInfo {
+                    addr: ConnectionAddr::Unix("/example.sock".into()),
+                    redis: RedisConnectionInfo {
+                        protocol: ProtocolVersion::RESP3,
+                        ..Default::default()
                     },
                 },
             ),
@@ -1692,6 +1829,42 @@ mod tests {
                 res.redis.password, expected.redis.password,
                 "password of {url} is not expected",
             );
+            assert_eq!(
+                res.redis.protocol, expected.redis.protocol,
+                "protocol of {url} is not expected",
+            );
         }
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_push_frames_are_not_taken_as_replies() {
+        let (sock, mut server) = UnixStream::pair().unwrap();
+        let mut con = Connection {
+            con: ActualConnection::Unix(UnixConnection { sock, open: true }),
+            parser: Parser::new(),
+            db: 0,
+            pubsub: false,
+        };
+        let invalidate = b">2\r\n$10\r\ninvalidate\r\n*1\r\n$3\r\nfoo\r\n";
+
+        server.write_all(invalidate).unwrap();
+        server.write_all(b"$3\r\nbar\r\n").unwrap();
+        let value = con.req_packed_command(&cmd("GET").arg("baz").get_packed_command());
+        assert_eq!(value.unwrap(), Value::Data(b"bar".to_vec()));
+
+        server.write_all(b"+OK\r\n").unwrap();
+        server.write_all(invalidate).unwrap();
+        server.write_all(b":1\r\n").unwrap();
+        let values = con.req_packed_commands(
+            &pipe().set("foo", 1).incr("foo", 1).get_packed_pipeline(),
+            0,
+            2,
+        );
+        assert_eq!(values.unwrap(), vec![Value::Okay, Value::Int(1)]);
+
+        // Responses read directly, as pubsub does, still see the push.
+        server.write_all(invalidate).unwrap();
+        assert!(matches!(con.recv_response().unwrap(), Value::Push { .. }));
+    }
 }
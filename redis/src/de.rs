@@ -0,0 +1,351 @@
+use std::fmt;
+use std::str::from_utf8;
+
+use serde::de::{
+    self, Deserialize, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+
+use crate::types::{ErrorKind, FromRedisValue, MapIter, RedisError, RedisResult, Value};
+
+/// Deserializes any [`Deserialize`] type from a redis value.
+///
+/// This is an alternative to implementing [`FromRedisValue`] for types that
+/// already derive `serde::Deserialize`.  Maps are read from key/value bulks
+/// or RESP3 maps, sequences from bulks and sets, options are `None` for
+/// `Nil`, and numbers and booleans are parsed from data the same way
+/// [`FromRedisValue`] parses them.
+///
+/// ```rust,ignore
+/// #[derive(serde::Deserialize)]
+/// struct Point {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// let v: redis::Value = redis::cmd("HGETALL").arg("point").query(&mut con)?;
+/// let point: Point = redis::from_value(&v)?;
+/// ```
+pub fn from_value<'de, T: Deserialize<'de>>(v: &'de Value) -> RedisResult<T> {
+    T::deserialize(v)
+}
+
+impl de::Error for RedisError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        RedisError::from((
+            ErrorKind::TypeError,
+            "Response was of incompatible type",
+            msg.to_string(),
+        ))
+    }
+}
+
+fn invalid_type(v: &Value, expected: &str) -> RedisError {
+    RedisError::from((
+        ErrorKind::TypeError,
+        "Response was of incompatible type",
+        format!("Response type not {expected} compatible (response was {v:?})"),
+    ))
+}
+
+macro_rules! deserialize_from_redis_value {
+    ($($method:ident => $visit:ident($t:ty),)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+                visitor.$visit(<$t>::from_redis_value(self)?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &'de Value {
+    type Error = RedisError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+        match self {
+            Value::Nil => visitor.visit_none(),
+            Value::Int(val) => visitor.visit_i64(*val),
+            Value::Data(bytes) => match from_utf8(bytes) {
+                Ok(s) => visitor.visit_borrowed_str(s),
+                Err(_) => visitor.visit_borrowed_bytes(bytes),
+            },
+            Value::Bulk(items) | Value::Set(items) => {
+                visitor.visit_seq(SeqDeserializer(items.iter()))
+            }
+            Value::Status(s) => visitor.visit_borrowed_str(s),
+            Value::Okay => visitor.visit_borrowed_str("OK"),
+            Value::Map(_) => self.deserialize_map(visitor),
+            Value::Attribute { data, .. } => data.deserialize_any(visitor),
+            Value::Double(val) => visitor.visit_f64(*val),
+            Value::Boolean(val) => visitor.visit_bool(*val),
+            Value::VerbatimString { text, .. } => visitor.visit_borrowed_str(text),
+            Value::BigNumber(n) => visitor.visit_string(n.to_string()),
+            Value::Push { data, .. } => visitor.visit_seq(SeqDeserializer(data.iter())),
+        }
+    }
+
+    deserialize_from_redis_value! {
+        deserialize_bool => visit_bool(bool),
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+        deserialize_string => visit_string(String),
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+        let s = String::from_redis_value(self)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(invalid_type(self, "char")),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+        match self {
+            Value::Data(bytes) => match from_utf8(bytes) {
+                Ok(s) => visitor.visit_borrowed_str(s),
+                Err(_) => Err(invalid_type(self, "string")),
+            },
+            Value::Status(s) | Value::VerbatimString { text: s, .. } => {
+                visitor.visit_borrowed_str(s)
+            }
+            Value::Okay => visitor.visit_borrowed_str("OK"),
+            _ => visitor.visit_string(String::from_redis_value(self)?),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+        match self {
+            Value::Data(bytes) => visitor.visit_borrowed_bytes(bytes),
+            _ => self.deserialize_str(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+        match self {
+            Value::Nil => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> RedisResult<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> RedisResult<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+        match self {
+            Value::Data(bytes) => {
+                visitor.visit_seq(de::value::SeqDeserializer::new(bytes.iter().copied()))
+            }
+            Value::Push { data, .. } => visitor.visit_seq(SeqDeserializer(data.iter())),
+            Value::Attribute { data, .. } => data.deserialize_seq(visitor),
+            _ => match self.as_sequence() {
+                Some(items) => visitor.visit_seq(SeqDeserializer(items.iter())),
+                None => Err(invalid_type(self, "sequence")),
+            },
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> RedisResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> RedisResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+        match self {
+            Value::Nil => visitor.visit_map(MapDeserializer {
+                iter: None,
+                value: None,
+            }),
+            Value::Attribute { data, .. } => data.deserialize_map(visitor),
+            _ => match self.as_map_iter() {
+                Some(iter) => visitor.visit_map(MapDeserializer {
+                    iter: Some(iter),
+                    value: None,
+                }),
+                None => Err(invalid_type(self, "map")),
+            },
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> RedisResult<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> RedisResult<V::Value> {
+        match self {
+            Value::Data(_) | Value::Status(_) | Value::Okay | Value::VerbatimString { .. } => {
+                visitor.visit_enum(EnumDeserializer {
+                    variant: self,
+                    value: None,
+                })
+            }
+            _ => {
+                // Other variants are represented by a single key/value pair
+                // of variant name and content.
+                let mut iter = self
+                    .as_map_iter()
+                    .ok_or_else(|| invalid_type(self, "enum"))?;
+                match (iter.next(), iter.next()) {
+                    (Some((variant, value)), None) => visitor.visit_enum(EnumDeserializer {
+                        variant,
+                        value: Some(value),
+                    }),
+                    _ => Err(invalid_type(self, "enum")),
+                }
+            }
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> RedisResult<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+struct SeqDeserializer<'de>(std::slice::Iter<'de, Value>);
+
+impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
+    type Error = RedisError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> RedisResult<Option<T::Value>> {
+        self.0.next().map(|item| seed.deserialize(item)).transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct MapDeserializer<'de> {
+    iter: Option<MapIter<'de>>,
+    value: Option<&'de Value>,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer<'de> {
+    type Error = RedisError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> RedisResult<Option<K::Value>> {
+        match self.iter.as_mut().and_then(Iterator::next) {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> RedisResult<V::Value> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+}
+
+struct EnumDeserializer<'de> {
+    variant: &'de Value,
+    value: Option<&'de Value>,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
+    type Error = RedisError;
+    type Variant = VariantDeserializer<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> RedisResult<(V::Value, Self::Variant)> {
+        let variant = seed.deserialize(self.variant)?;
+        Ok((variant, VariantDeserializer(self.value)))
+    }
+}
+
+struct VariantDeserializer<'de>(Option<&'de Value>);
+
+impl<'de> VariantDeserializer<'de> {
+    fn value(self) -> RedisResult<&'de Value> {
+        self.0
+            .ok_or_else(|| de::Error::invalid_type(de::Unexpected::UnitVariant, &"variant content"))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
+    type Error = RedisError;
+
+    fn unit_variant(self) -> RedisResult<()> {
+        match self.0 {
+            None | Some(Value::Nil) => Ok(()),
+            Some(value) => Err(invalid_type(value, "unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> RedisResult<T::Value> {
+        seed.deserialize(self.value()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> RedisResult<V::Value> {
+        de::Deserializer::deserialize_seq(self.value()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> RedisResult<V::Value> {
+        de::Deserializer::deserialize_map(self.value()?, visitor)
+    }
+}
@@ -0,0 +1,214 @@
+use std::io::Write;
+
+use crate::types::{ProtocolVersion, RedisError, Value};
+
+/// Encodes a value into the wire format of the given protocol version.
+///
+/// This is the inverse of [`parse_redis_value`](crate::parse_redis_value)
+/// and is mostly useful to implement servers, proxies or test fakes.  When
+/// encoding for RESP2 the RESP3 only types are downgraded the same way
+/// redis does it: maps and sets become arrays, doubles, big numbers and
+/// verbatim strings become bulk strings, booleans become integers and
+/// attributes are dropped.
+pub fn encode_value(value: &Value, protocol: ProtocolVersion) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_value(value, protocol, &mut out);
+    out
+}
+
+/// Like [`encode_value`] but appends the encoded value to `out`.
+pub fn write_value(value: &Value, protocol: ProtocolVersion, out: &mut Vec<u8>) {
+    let resp3 = protocol == ProtocolVersion::RESP3;
+    match value {
+        Value::Nil if resp3 => out.extend_from_slice(b"_\r\n"),
+        Value::Nil => out.extend_from_slice(b"$-1\r\n"),
+        Value::Int(val) => write_line(out, b':', itoa::Buffer::new().format(*val).as_bytes()),
+        Value::Data(bytes) => write_bulk(out, b'$', bytes),
+        Value::Bulk(items) => write_aggregate(out, b'*', items, protocol),
+        Value::Status(status) => write_line(out, b'+', status.as_bytes()),
+        Value::Okay => out.extend_from_slice(b"+OK\r\n"),
+        Value::Map(pairs) if resp3 => write_pairs(out, b'%', pairs, protocol),
+        Value::Map(pairs) => {
+            write_header(out, b'*', pairs.len() * 2);
+            for (key, value) in pairs {
+                write_value(key, protocol, out);
+                write_value(value, protocol, out);
+            }
+        }
+        Value::Attribute { data, attributes } => {
+            if resp3 {
+                write_pairs(out, b'|', attributes, protocol);
+            }
+            write_value(data, protocol, out);
+        }
+        Value::Set(items) => write_aggregate(out, if resp3 { b'~' } else { b'*' }, items, protocol),
+        Value::Double(val) => {
+            let text = if val.is_nan() {
+                "nan".to_string()
+            } else if val.is_infinite() {
+                if val.is_sign_negative() {
+                    "-inf"
+                } else {
+                    "inf"
+                }
+                .to_string()
+            } else {
+                ryu::Buffer::new().format(*val).to_string()
+            };
+            if resp3 {
+                write_line(out, b',', text.as_bytes());
+            } else {
+                write_bulk(out, b'$', text.as_bytes());
+            }
+        }
+        Value::Boolean(val) if resp3 => write_line(out, b'#', if *val { b"t" } else { b"f" }),
+        Value::Boolean(val) => write_line(out, b':', if *val { b"1" } else { b"0" }),
+        Value::VerbatimString { format, text } if resp3 => {
+            write_bulk(out, b'=', format!("{format}:{text}").as_bytes())
+        }
+        Value::VerbatimString { text, .. } => write_bulk(out, b'$', text.as_bytes()),
+        Value::BigNumber(n) if resp3 => write_line(out, b'(', n.as_bytes()),
+        Value::BigNumber(n) => write_bulk(out, b'$', n.as_bytes()),
+        Value::Push { kind, data } => {
+            write_header(out, if resp3 { b'>' } else { b'*' }, data.len() + 1);
+            write_bulk(out, b'$', kind.to_string().as_bytes());
+            for item in data {
+                write_value(item, protocol, out);
+            }
+        }
+    }
+}
+
+/// Appends an error reply for `err` to `out`.
+///
+/// The reply starts with the error code, for instance `ERR` or `MOVED`,
+/// followed by the detail of the error.
+pub fn write_error(err: &RedisError, out: &mut Vec<u8>) {
+    #[allow(deprecated)]
+    let message = err
+        .detail()
+        .unwrap_or_else(|| std::error::Error::description(err));
+    let line = format!("{} {}", err.code().unwrap_or("ERR"), message);
+    // Error lines can't contain line breaks.
+    let line = line.replace(['\r', '\n'], " ");
+    write_line(out, b'-', line.as_bytes());
+}
+
+fn write_line(out: &mut Vec<u8>, prefix: u8, line: &[u8]) {
+    out.push(prefix);
+    out.extend_from_slice(line);
+    out.extend_from_slice(b"\r\n");
+}
+
+fn write_header(out: &mut Vec<u8>, prefix: u8, len: usize) {
+    write!(out, "{}{}\r\n", prefix as char, len).unwrap();
+}
+
+fn write_bulk(out: &mut Vec<u8>, prefix: u8, bytes: &[u8]) {
+    write_header(out, prefix, bytes.len());
+    out.extend_from_slice(bytes);
+    out.extend_from_slice(b"\r\n");
+}
+
+fn write_aggregate(out: &mut Vec<u8>, prefix: u8, items: &[Value], protocol: ProtocolVersion) {
+    write_header(out, prefix, items.len());
+    for item in items {
+        write_value(item, protocol, out);
+    }
+}
+
+fn write_pairs(out: &mut Vec<u8>, prefix: u8, pairs: &[(Value, Value)], protocol: ProtocolVersion) {
+    write_header(out, prefix, pairs.len());
+    for (key, value) in pairs {
+        write_value(key, protocol, out);
+        write_value(value, protocol, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_redis_value;
+    use crate::types::{ErrorKind, PushKind, VerbatimFormat};
+
+    #[test]
+    fn encode_resp3_roundtrip() {
+        let value = Value::Bulk(vec![
+            Value::Nil,
+            Value::Int(-3),
+            Value::Data(b"data".to_vec()),
+            Value::Okay,
+            Value::Status("QUEUED".to_string()),
+            Value::Map(vec![(Value::Data(b"key".to_vec()), Value::Double(1.5))]),
+            Value::Set(vec![Value::Boolean(true), Value::Boolean(false)]),
+            Value::Double(f64::NEG_INFINITY),
+            Value::VerbatimString {
+                format: VerbatimFormat::Markdown,
+                text: "# title".to_string(),
+            },
+            Value::BigNumber("123456789012345678901234567890".to_string()),
+        ]);
+        let encoded = encode_value(&value, ProtocolVersion::RESP3);
+        assert_eq!(parse_redis_value(&encoded).unwrap(), value);
+
+        let push = Value::Push {
+            kind: PushKind::Message,
+            data: vec![Value::Data(b"ch".to_vec()), Value::Data(b"hi".to_vec())],
+        };
+        let encoded = encode_value(&push, ProtocolVersion::RESP3);
+        assert_eq!(encoded, b">3\r\n$7\r\nmessage\r\n$2\r\nch\r\n$2\r\nhi\r\n");
+        assert_eq!(parse_redis_value(&encoded).unwrap(), push);
+    }
+
+    #[test]
+    fn encode_resp2_downgrades_resp3_types() {
+        let encode = |value: Value| encode_value(&value, ProtocolVersion::RESP2);
+
+        assert_eq!(encode(Value::Nil), b"$-1\r\n");
+        assert_eq!(encode(Value::Boolean(true)), b":1\r\n");
+        assert_eq!(encode(Value::Double(1.5)), b"$3\r\n1.5\r\n");
+        assert_eq!(
+            encode(Value::Map(vec![(Value::Int(1), Value::Int(2))])),
+            b"*2\r\n:1\r\n:2\r\n"
+        );
+        assert_eq!(encode(Value::Set(vec![Value::Int(1)])), b"*1\r\n:1\r\n");
+        assert_eq!(
+            encode(Value::Attribute {
+                data: Box::new(Value::Int(7)),
+                attributes: vec![(Value::Status("ttl".to_string()), Value::Int(1))],
+            }),
+            b":7\r\n"
+        );
+        assert_eq!(
+            encode(Value::VerbatimString {
+                format: VerbatimFormat::Text,
+                text: "hi".to_string(),
+            }),
+            b"$2\r\nhi\r\n"
+        );
+    }
+
+    #[test]
+    fn encode_error() {
+        let mut out = Vec::new();
+        write_error(
+            &RedisError::from((
+                ErrorKind::ResponseError,
+                "desc",
+                "unknown\r\ncommand".to_string(),
+            )),
+            &mut out,
+        );
+        assert_eq!(out, b"-ERR unknown  command\r\n");
+
+        let err = parse_redis_value(&out).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ResponseError);
+
+        let mut out = Vec::new();
+        write_error(
+            &RedisError::from((ErrorKind::Moved, "desc", "1 127.0.0.1:6379".to_string())),
+            &mut out,
+        );
+        assert_eq!(out, b"-MOVED 1 127.0.0.1:6379\r\n");
+    }
+}
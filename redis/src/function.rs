@@ -0,0 +1,318 @@
+use crate::cmd::cmd;
+use crate::connection::ConnectionLike;
+use crate::types::{
+    from_redis_value, ErrorKind, FromRedisValue, HashMap, RedisError, RedisResult, RedisWrite,
+    ToRedisArgs, Value,
+};
+use crate::Cmd;
+
+/// Represents a function of a Redis function library.
+#[derive(Debug, Clone)]
+pub struct Function {
+    name: String,
+    library_code: String,
+    read_only: bool,
+}
+
+/// The function object represents a function of a library that can be
+/// called on the redis server.  The object takes care of loading the
+/// library when the server reports the function missing, which makes it
+/// work the same way as [`Script`](crate::Script) does for lua scripts.
+///
+/// Example:
+///
+/// ```rust,no_run
+/// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+/// # let mut con = client.get_connection().unwrap();
+/// let function = redis::Function::new(r"#!lua name=mylib
+///     redis.register_function('add', function(keys, args)
+///         return tonumber(args[1]) + tonumber(args[2])
+///     end)
+/// ", "add");
+/// let result = function.arg(1).arg(2).invoke(&mut con);
+/// assert_eq!(result, Ok(3));
+/// ```
+impl Function {
+    /// Creates a new function object for the function `name` of the library
+    /// with the given source code.
+    pub fn new(library_code: &str, name: &str) -> Function {
+        Function {
+            name: name.to_string(),
+            library_code: library_code.to_string(),
+            read_only: false,
+        }
+    }
+
+    /// Calls the function with `FCALL_RO` instead of `FCALL`.  This requires
+    /// the function to be registered with the `no-writes` flag, and allows
+    /// it to run on read only replicas.
+    pub fn read_only(mut self) -> Function {
+        self.read_only = true;
+        self
+    }
+
+    /// Returns the name of the function.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Creates a function invocation object with a key filled in.
+    #[inline]
+    pub fn key<T: ToRedisArgs>(&self, key: T) -> FunctionInvocation<'_> {
+        FunctionInvocation {
+            function: self,
+            args: vec![],
+            keys: key.to_redis_args(),
+        }
+    }
+
+    /// Creates a function invocation object with an argument filled in.
+    #[inline]
+    pub fn arg<T: ToRedisArgs>(&self, arg: T) -> FunctionInvocation<'_> {
+        FunctionInvocation {
+            function: self,
+            args: arg.to_redis_args(),
+            keys: vec![],
+        }
+    }
+
+    /// Returns an empty function invocation object.  This is primarily
+    /// useful for programmatically adding arguments and keys because the
+    /// type will not change.  Normally you can use `arg` and `key` directly.
+    #[inline]
+    pub fn prepare_invoke(&self) -> FunctionInvocation<'_> {
+        FunctionInvocation {
+            function: self,
+            args: vec![],
+            keys: vec![],
+        }
+    }
+
+    /// Invokes the function directly without arguments.
+    #[inline]
+    pub fn invoke<T: FromRedisValue>(&self, con: &mut dyn ConnectionLike) -> RedisResult<T> {
+        self.prepare_invoke().invoke(con)
+    }
+
+    /// Asynchronously invokes the function without arguments.
+    #[inline]
+    #[cfg(feature = "aio")]
+    pub async fn invoke_async<C, T>(&self, con: &mut C) -> RedisResult<T>
+    where
+        C: crate::aio::ConnectionLike,
+        T: FromRedisValue,
+    {
+        self.prepare_invoke().invoke_async(con).await
+    }
+
+    /// Loads the library of the function, replacing an already loaded
+    /// library of the same name, and returns the name of the library.
+    #[inline]
+    pub fn load(&self, con: &mut dyn ConnectionLike) -> RedisResult<String> {
+        self.load_cmd().query(con)
+    }
+
+    /// Asynchronously loads the library of the function, replacing an
+    /// already loaded library of the same name, and returns the name of the
+    /// library.
+    #[inline]
+    #[cfg(feature = "aio")]
+    pub async fn load_async<C>(&self, con: &mut C) -> RedisResult<String>
+    where
+        C: crate::aio::ConnectionLike,
+    {
+        self.load_cmd().query_async(con).await
+    }
+
+    fn load_cmd(&self) -> Cmd {
+        let mut cmd = cmd("FUNCTION");
+        cmd.arg("LOAD")
+            .arg("REPLACE")
+            .arg(self.library_code.as_bytes());
+        cmd
+    }
+}
+
+/// Represents a prepared function call.
+pub struct FunctionInvocation<'a> {
+    function: &'a Function,
+    args: Vec<Vec<u8>>,
+    keys: Vec<Vec<u8>>,
+}
+
+/// This type collects keys and other arguments for the function so that it
+/// can be then invoked.  While the `Function` type itself holds the library,
+/// the `FunctionInvocation` holds the arguments that should be invoked until
+/// it's sent to the server.
+impl<'a> FunctionInvocation<'a> {
+    /// Adds a regular argument to the invocation.  This ends up in the
+    /// `args` table of the function.
+    #[inline]
+    pub fn arg<'b, T: ToRedisArgs>(&'b mut self, arg: T) -> &'b mut FunctionInvocation<'a>
+    where
+        'a: 'b,
+    {
+        arg.write_redis_args(&mut self.args);
+        self
+    }
+
+    /// Adds a key argument to the invocation.  This ends up in the `keys`
+    /// table of the function.
+    #[inline]
+    pub fn key<'b, T: ToRedisArgs>(&'b mut self, key: T) -> &'b mut FunctionInvocation<'a>
+    where
+        'a: 'b,
+    {
+        key.write_redis_args(&mut self.keys);
+        self
+    }
+
+    /// Invokes the function and returns the result.
+    #[inline]
+    pub fn invoke<T: FromRedisValue>(&self, con: &mut dyn ConnectionLike) -> RedisResult<T> {
+        let fcall_cmd = self.fcall_cmd();
+        match fcall_cmd.query(con) {
+            Ok(val) => Ok(val),
+            Err(err) => {
+                if is_function_missing(&err) {
+                    self.function.load_cmd().query::<()>(con)?;
+                    fcall_cmd.query(con)
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    /// Asynchronously invokes the function and returns the result.
+    #[inline]
+    #[cfg(feature = "aio")]
+    pub async fn invoke_async<C, T>(&self, con: &mut C) -> RedisResult<T>
+    where
+        C: crate::aio::ConnectionLike,
+        T: FromRedisValue,
+    {
+        let fcall_cmd = self.fcall_cmd();
+        match fcall_cmd.query_async(con).await {
+            Ok(val) => Ok(val),
+            Err(err) => {
+                // Load the library if the server doesn't know the function yet
+                if is_function_missing(&err) {
+                    self.function.load_cmd().query_async::<_, ()>(con).await?;
+                    fcall_cmd.query_async(con).await
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    fn fcall_cmd(&self) -> Cmd {
+        let mut cmd = cmd(if self.function.read_only {
+            "FCALL_RO"
+        } else {
+            "FCALL"
+        });
+        cmd.arg(self.function.name.as_bytes())
+            .arg(self.keys.len())
+            .arg(&*self.keys)
+            .arg(&*self.args);
+        cmd
+    }
+}
+
+// The server has no dedicated error code for unknown functions.
+fn is_function_missing(err: &RedisError) -> bool {
+    err.kind() == ErrorKind::ResponseError
+        && err
+            .detail()
+            .map_or(false, |detail| detail.starts_with("Function not found"))
+}
+
+/// Policies for how `FUNCTION RESTORE` handles libraries that are already
+/// loaded.
+#[derive(PartialEq, Eq, Clone, Debug, Copy)]
+pub enum FunctionRestorePolicy {
+    /// Deletes all existing libraries before restoring the payload.
+    Flush,
+    /// Appends the restored libraries, failing on name collisions.
+    Append,
+    /// Appends the restored libraries, replacing existing ones of the same
+    /// name.
+    Replace,
+}
+
+impl ToRedisArgs for FunctionRestorePolicy {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        let policy: &[u8] = match self {
+            FunctionRestorePolicy::Flush => b"FLUSH",
+            FunctionRestorePolicy::Append => b"APPEND",
+            FunctionRestorePolicy::Replace => b"REPLACE",
+        };
+        out.write_arg(policy);
+    }
+}
+
+/// A library, as returned by the `FUNCTION LIST` command.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct LibraryInfo {
+    /// The name of the library.
+    pub name: String,
+    /// The engine of the library, e.g. `LUA`.
+    pub engine: String,
+    /// The functions of the library.
+    pub functions: Vec<FunctionInfo>,
+    /// The source code of the library, only returned with `WITHCODE`.
+    pub code: Option<String>,
+}
+
+/// A function of a [`LibraryInfo`].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct FunctionInfo {
+    /// The name of the function.
+    pub name: String,
+    /// The description of the function, if any.
+    pub description: Option<String>,
+    /// The flags the function was registered with.
+    pub flags: Vec<String>,
+}
+
+impl FromRedisValue for LibraryInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let map: HashMap<String, Value> = from_redis_value(v)?;
+        let mut library = LibraryInfo::default();
+        if let Some(v) = map.get("library_name") {
+            library.name = from_redis_value(v)?;
+        }
+        if let Some(v) = map.get("engine") {
+            library.engine = from_redis_value(v)?;
+        }
+        if let Some(v) = map.get("functions") {
+            library.functions = from_redis_value(v)?;
+        }
+        if let Some(v) = map.get("library_code") {
+            library.code = from_redis_value(v)?;
+        }
+        Ok(library)
+    }
+}
+
+impl FromRedisValue for FunctionInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let map: HashMap<String, Value> = from_redis_value(v)?;
+        let mut function = FunctionInfo::default();
+        if let Some(v) = map.get("name") {
+            function.name = from_redis_value(v)?;
+        }
+        if let Some(v) = map.get("description") {
+            function.description = from_redis_value(v)?;
+        }
+        if let Some(v) = map.get("flags") {
+            function.flags = from_redis_value(v)?;
+        }
+        Ok(function)
+    }
+}
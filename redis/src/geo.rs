@@ -13,10 +13,11 @@ macro_rules! invalid_type_error {
     }};
 }
 
-/// Units used by [`geo_dist`][1] and [`geo_radius`][2].
+/// Units used by [`geo_dist`][1], [`geo_radius`][2] and [`geo_search`][3].
 ///
 /// [1]: ../trait.Commands.html#method.geo_dist
 /// [2]: ../trait.Commands.html#method.geo_radius
+/// [3]: ../trait.Commands.html#method.geo_search
 pub enum Unit {
     /// Represents meters.
     Meters,
@@ -100,10 +101,12 @@ impl<T: ToRedisArgs> ToRedisArgs for Coord<T> {
     }
 }
 
-/// Options to sort results from [GEORADIUS][1] and [GEORADIUSBYMEMBER][2] commands
+/// Options to sort results from [GEORADIUS][1], [GEORADIUSBYMEMBER][2] and
+/// [GEOSEARCH][3] commands
 ///
 /// [1]: https://redis.io/commands/georadius
 /// [2]: https://redis.io/commands/georadiusbymember
+/// [3]: https://redis.io/commands/geosearch
 #[derive(Default)]
 pub enum RadiusOrder {
     /// Don't sort the results
@@ -238,10 +241,227 @@ impl ToRedisArgs for RadiusOptions {
     }
 }
 
-/// Contain an item returned by [`geo_radius`][1] and [`geo_radius_by_member`][2].
+enum SearchOrigin {
+    Member(Vec<Vec<u8>>),
+    LonLat(f64, f64),
+}
+
+/// The area selected by [`GeoSearchOptions`].
+pub enum GeoSearchShape {
+    /// A circle of the given radius around the origin (`BYRADIUS`).
+    Radius(f64, Unit),
+    /// An axis-aligned box of the given width and height, centered on the
+    /// origin (`BYBOX`).
+    Box(f64, f64, Unit),
+}
+
+/// Options for the [GEOSEARCH][1] and [GEOSEARCHSTORE][2] commands
+///
+/// A search starts at an origin, which is either a member of the sorted set
+/// or a coordinate, and selects the members within a circle or a box around
+/// it, given as a [`GeoSearchShape`].
+///
+/// [1]: https://redis.io/commands/geosearch
+/// [2]: https://redis.io/commands/geosearchstore
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use redis::{Commands, RedisResult};
+/// use redis::geo::{GeoSearchOptions, GeoSearchShape, RadiusOrder, RadiusSearchResult, Unit};
+/// fn members_in_tile(
+///     con: &mut redis::Connection,
+///     key: &str,
+///     longitude: f64,
+///     latitude: f64,
+///     kilometers: f64,
+/// ) -> RedisResult<Vec<RadiusSearchResult>> {
+///     let shape = GeoSearchShape::Box(kilometers, kilometers, Unit::Kilometers);
+///     let opts = GeoSearchOptions::from_lonlat(longitude, latitude, shape)
+///         .order(RadiusOrder::Asc)
+///         .with_coord();
+///     con.geo_search(key, opts)
+/// }
+/// ```
+pub struct GeoSearchOptions {
+    origin: SearchOrigin,
+    shape: GeoSearchShape,
+    order: RadiusOrder,
+    count: Option<usize>,
+    any: bool,
+    with_coord: bool,
+    with_dist: bool,
+    with_hash: bool,
+    store_dist: bool,
+}
+
+impl GeoSearchOptions {
+    fn new(origin: SearchOrigin, shape: GeoSearchShape) -> Self {
+        GeoSearchOptions {
+            origin,
+            shape,
+            order: RadiusOrder::Unsorted,
+            count: None,
+            any: false,
+            with_coord: false,
+            with_dist: false,
+            with_hash: false,
+            store_dist: false,
+        }
+    }
+
+    /// Search within `shape` around the position of an existing `member`
+    /// (`FROMMEMBER`).
+    pub fn from_member<M: ToRedisArgs>(member: M, shape: GeoSearchShape) -> Self {
+        Self::new(SearchOrigin::Member(member.to_redis_args()), shape)
+    }
+
+    /// Search within `shape` around the given coordinate (`FROMLONLAT`).
+    pub fn from_lonlat(longitude: f64, latitude: f64, shape: GeoSearchShape) -> Self {
+        Self::new(SearchOrigin::LonLat(longitude, latitude), shape)
+    }
+
+    /// Sort the returned items relative to the origin.
+    pub fn order(mut self, o: RadiusOrder) -> Self {
+        self.order = o;
+        self
+    }
+
+    /// Limit the results to the first N matching items.
+    pub fn limit(mut self, n: usize) -> Self {
+        self.count = Some(n);
+        self.any = false;
+        self
+    }
+
+    /// Limit the results to N matching items, returning as soon as enough
+    /// matches are found (`COUNT n ANY`).  The results may not be the ones
+    /// closest to the origin.
+    pub fn limit_any(mut self, n: usize) -> Self {
+        self.count = Some(n);
+        self.any = true;
+        self
+    }
+
+    /// Return the `longitude, latitude` coordinates of the matching items.
+    ///
+    /// Can't be used with [`geo_search_store`][1].
+    ///
+    /// [1]: ../trait.Commands.html#method.geo_search_store
+    pub fn with_coord(mut self) -> Self {
+        self.with_coord = true;
+        self
+    }
+
+    /// Return the distance of the returned items from the origin, in the
+    /// unit of the shape.
+    ///
+    /// Can't be used with [`geo_search_store`][1].
+    ///
+    /// [1]: ../trait.Commands.html#method.geo_search_store
+    pub fn with_dist(mut self) -> Self {
+        self.with_dist = true;
+        self
+    }
+
+    /// Return the raw geohash of the returned items.
+    ///
+    /// Can't be used with [`geo_search_store`][1].
+    ///
+    /// [1]: ../trait.Commands.html#method.geo_search_store
+    pub fn with_hash(mut self) -> Self {
+        self.with_hash = true;
+        self
+    }
+
+    /// Store the distance from the origin as the score of the stored
+    /// members, instead of their geohash.
+    ///
+    /// Only used by [`geo_search_store`][1].
+    ///
+    /// [1]: ../trait.Commands.html#method.geo_search_store
+    pub fn store_dist(mut self) -> Self {
+        self.store_dist = true;
+        self
+    }
+}
+
+impl ToRedisArgs for GeoSearchOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        match self.origin {
+            SearchOrigin::Member(ref member) => {
+                out.write_arg(b"FROMMEMBER");
+                for i in member {
+                    out.write_arg(i);
+                }
+            }
+            SearchOrigin::LonLat(longitude, latitude) => {
+                out.write_arg(b"FROMLONLAT");
+                longitude.write_redis_args(out);
+                latitude.write_redis_args(out);
+            }
+        }
+
+        match self.shape {
+            GeoSearchShape::Radius(radius, ref unit) => {
+                out.write_arg(b"BYRADIUS");
+                radius.write_redis_args(out);
+                unit.write_redis_args(out);
+            }
+            GeoSearchShape::Box(width, height, ref unit) => {
+                out.write_arg(b"BYBOX");
+                width.write_redis_args(out);
+                height.write_redis_args(out);
+                unit.write_redis_args(out);
+            }
+        }
+
+        match self.order {
+            RadiusOrder::Asc => out.write_arg(b"ASC"),
+            RadiusOrder::Desc => out.write_arg(b"DESC"),
+            _ => (),
+        };
+
+        if let Some(n) = self.count {
+            out.write_arg(b"COUNT");
+            out.write_arg_fmt(n);
+            if self.any {
+                out.write_arg(b"ANY");
+            }
+        }
+
+        if self.with_coord {
+            out.write_arg(b"WITHCOORD");
+        }
+
+        if self.with_dist {
+            out.write_arg(b"WITHDIST");
+        }
+
+        if self.with_hash {
+            out.write_arg(b"WITHHASH");
+        }
+
+        if self.store_dist {
+            out.write_arg(b"STOREDIST");
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// Contain an item returned by [`geo_radius`][1], [`geo_radius_by_member`][2]
+/// and [`geo_search`][3].
 ///
 /// [1]: ../trait.Commands.html#method.geo_radius
 /// [2]: ../trait.Commands.html#method.geo_radius_by_member
+/// [3]: ../trait.Commands.html#method.geo_search
+#[non_exhaustive]
 pub struct RadiusSearchResult {
     /// The name that was found.
     pub name: String,
@@ -249,6 +469,8 @@ pub struct RadiusSearchResult {
     pub coord: Option<Coord<f64>>,
     /// The distance if available.
     pub dist: Option<f64>,
+    /// The raw geohash if available.
+    pub hash: Option<u64>,
 }
 
 impl FromRedisValue for RadiusSearchResult {
@@ -259,6 +481,7 @@ impl FromRedisValue for RadiusSearchResult {
                 name,
                 coord: None,
                 dist: None,
+                hash: None,
             });
         }
 
@@ -283,32 +506,34 @@ impl RadiusSearchResult {
             _ => return None,
         };
 
-        let mut next = iter.next();
-
-        // Next element, if present, will be the distance.
-        let dist = match next.map(FromRedisValue::from_redis_value) {
-            Some(Ok(c)) => {
-                next = iter.next();
-                Some(c)
-            }
-            _ => None,
-        };
-
-        // Finally, if present, the last item will be the coordinates
-
-        let coord = match next.map(FromRedisValue::from_redis_value) {
-            Some(Ok(c)) => Some(c),
-            _ => None,
+        // The distance, hash and coordinates follow in this order, each only
+        // if requested.  They are told apart by their types: the distance is
+        // a string, the hash an integer and the coordinates a pair.
+        let mut result = RadiusSearchResult {
+            name,
+            coord: None,
+            dist: None,
+            hash: None,
         };
+        for item in iter {
+            match *item {
+                Value::Int(hash) => result.hash = Some(hash as u64),
+                Value::Bulk(_) => result.coord = FromRedisValue::from_redis_value(item).ok(),
+                _ => result.dist = FromRedisValue::from_redis_value(item).ok(),
+            }
+        }
 
-        Some(RadiusSearchResult { name, coord, dist })
+        Some(result)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Coord, RadiusOptions, RadiusOrder};
-    use crate::types::ToRedisArgs;
+    use super::{
+        Coord, GeoSearchOptions, GeoSearchShape, RadiusOptions, RadiusOrder, RadiusSearchResult,
+        Unit,
+    };
+    use crate::types::{FromRedisValue, ToRedisArgs, Value};
     use std::str;
 
     macro_rules! assert_args {
@@ -358,4 +583,88 @@ mod tests {
             "ASC"
         );
     }
+
+    #[test]
+    fn test_geo_search_options() {
+        assert_args!(
+            GeoSearchOptions::from_member(
+                "Palermo",
+                GeoSearchShape::Radius(200.0, Unit::Kilometers)
+            ),
+            "FROMMEMBER",
+            "Palermo",
+            "BYRADIUS",
+            "200.0",
+            "km"
+        );
+
+        assert_args!(
+            GeoSearchOptions::from_lonlat(
+                15.0,
+                37.5,
+                GeoSearchShape::Box(400.0, 300.5, Unit::Meters)
+            )
+            .order(RadiusOrder::Desc)
+            .limit_any(5)
+            .with_coord()
+            .with_dist()
+            .with_hash(),
+            "FROMLONLAT",
+            "15.0",
+            "37.5",
+            "BYBOX",
+            "400.0",
+            "300.5",
+            "m",
+            "DESC",
+            "COUNT",
+            "5",
+            "ANY",
+            "WITHCOORD",
+            "WITHDIST",
+            "WITHHASH"
+        );
+
+        assert_args!(
+            GeoSearchOptions::from_member("Catania", GeoSearchShape::Radius(1.0, Unit::Miles))
+                .limit(3)
+                .store_dist(),
+            "FROMMEMBER",
+            "Catania",
+            "BYRADIUS",
+            "1.0",
+            "mi",
+            "COUNT",
+            "3",
+            "STOREDIST"
+        );
+    }
+
+    #[test]
+    fn test_radius_search_result() {
+        let data = |s: &str| Value::Data(s.as_bytes().to_vec());
+        let coord = Value::Bulk(vec![data("15.08"), data("37.50")]);
+
+        let result = RadiusSearchResult::from_redis_value(&Value::Bulk(vec![
+            data("Catania"),
+            data("56.44"),
+            Value::Int(3479447370796909),
+            coord.clone(),
+        ]))
+        .unwrap();
+        assert_eq!(result.name, "Catania");
+        assert_eq!(result.dist, Some(56.44));
+        assert_eq!(result.hash, Some(3479447370796909));
+        assert_eq!(result.coord, Some(Coord::lon_lat(15.08, 37.5)));
+
+        // Without WITHDIST the hash must not be taken for the distance.
+        let result = RadiusSearchResult::from_redis_value(&Value::Bulk(vec![
+            data("Catania"),
+            Value::Int(3479447370796909),
+            coord,
+        ]))
+        .unwrap();
+        assert_eq!(result.dist, None);
+        assert_eq!(result.hash, Some(3479447370796909));
+    }
 }
@@ -0,0 +1,474 @@
+//! Defines types to use with the INFO command.
+//!
+//! [`ServerInfo`] splits the reply of `INFO` into its `# Section` blocks and
+//! provides typed access to the commonly used fields, including the compound
+//! ones like `db0:keys=1,expires=0,avg_ttl=0` or `slave0:ip=...,port=...`.
+//!
+//! ```rust,no_run
+//! # fn do_something() -> redis::RedisResult<()> {
+//! # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+//! # let mut con = client.get_connection().unwrap();
+//! use redis::Commands;
+//! use redis::info::{InfoSection, ServerInfo};
+//!
+//! let info: ServerInfo = con.info_section(InfoSection::Memory)?;
+//! let used_memory = info.memory.map(|memory| memory.used_memory);
+//! # Ok(()) }
+//! ```
+
+use crate::types::{
+    from_redis_value, ConversionPathSegment, FromRedisValue, HashMap, InfoDict, RedisResult,
+    RedisWrite, ToRedisArgs, Value,
+};
+
+/// Sections of the reply that can be requested with `INFO <section>`.
+#[derive(PartialEq, Eq, Clone, Debug, Copy)]
+pub enum InfoSection {
+    /// General information about the server.
+    Server,
+    /// Client connections.
+    Clients,
+    /// Memory consumption.
+    Memory,
+    /// RDB and AOF persistence.
+    Persistence,
+    /// General statistics.
+    Stats,
+    /// Master/replica replication.
+    Replication,
+    /// CPU consumption.
+    Cpu,
+    /// Loaded modules.
+    Modules,
+    /// Error statistics.
+    Errorstats,
+    /// Redis Cluster.
+    Cluster,
+    /// Database related statistics.
+    Keyspace,
+    /// Command statistics.
+    Commandstats,
+    /// Command latency percentiles.
+    Latencystats,
+    /// The default set of sections.
+    Default,
+    /// All sections, excluding module generated ones.
+    All,
+    /// All sections, including module generated ones.
+    Everything,
+}
+
+impl InfoSection {
+    fn as_str(self) -> &'static str {
+        match self {
+            InfoSection::Server => "server",
+            InfoSection::Clients => "clients",
+            InfoSection::Memory => "memory",
+            InfoSection::Persistence => "persistence",
+            InfoSection::Stats => "stats",
+            InfoSection::Replication => "replication",
+            InfoSection::Cpu => "cpu",
+            InfoSection::Modules => "modules",
+            InfoSection::Errorstats => "errorstats",
+            InfoSection::Cluster => "cluster",
+            InfoSection::Keyspace => "keyspace",
+            InfoSection::Commandstats => "commandstats",
+            InfoSection::Latencystats => "latencystats",
+            InfoSection::Default => "default",
+            InfoSection::All => "all",
+            InfoSection::Everything => "everything",
+        }
+    }
+}
+
+impl ToRedisArgs for InfoSection {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.as_str().as_bytes());
+    }
+}
+
+fn field<T: FromRedisValue + Default>(dict: &InfoDict, key: &str) -> RedisResult<T> {
+    match dict.find(&key) {
+        Some(v) => from_redis_value(v)
+            .map_err(|err| err.with_conversion_segment(ConversionPathSegment::Key(key.into()))),
+        None => Ok(T::default()),
+    }
+}
+
+// Splits compound values like `keys=1,expires=0,avg_ttl=0`.
+fn compound(v: &Value) -> RedisResult<InfoDict> {
+    let s: String = from_redis_value(v)?;
+    Ok(InfoDict::from_pairs(
+        s.split(',').filter_map(|pair| pair.split_once('=')),
+    ))
+}
+
+macro_rules! info_struct {
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident {
+            $(
+                $(#[$field_attr:meta])*
+                pub $field:ident: $t:ty,
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        ///
+        /// Fields missing from the reply keep their default value.
+        #[derive(Debug, Clone, Default, PartialEq)]
+        #[non_exhaustive]
+        pub struct $name {
+            $(
+                $(#[$field_attr])*
+                pub $field: $t,
+            )*
+        }
+
+        impl $name {
+            fn from_dict(dict: &InfoDict) -> RedisResult<Self> {
+                Ok($name {
+                    $($field: field(dict, stringify!($field))?,)*
+                })
+            }
+        }
+    };
+}
+
+info_struct! {
+    /// The `# Server` section.
+    pub struct ServerSection {
+        /// Version of the server.
+        pub redis_version: String,
+        /// `standalone`, `sentinel` or `cluster`.
+        pub redis_mode: String,
+        /// Operating system hosting the server.
+        pub os: String,
+        /// Architecture (32 or 64 bits).
+        pub arch_bits: u32,
+        /// PID of the server process.
+        pub process_id: u64,
+        /// Random value identifying the server, used by Sentinel and Cluster.
+        pub run_id: String,
+        /// TCP/IP listen port.
+        pub tcp_port: u16,
+        /// Number of seconds since the server was started.
+        pub uptime_in_seconds: u64,
+        /// Frequency setting of the server's background tasks.
+        pub hz: u32,
+        /// Path to the server's executable.
+        pub executable: String,
+        /// Path to the config file.
+        pub config_file: String,
+    }
+}
+
+info_struct! {
+    /// The `# Clients` section.
+    pub struct ClientsSection {
+        /// Number of client connections, excluding replicas.
+        pub connected_clients: u64,
+        /// Number of clients pending on a blocking call.
+        pub blocked_clients: u64,
+        /// Number of clients being tracked.
+        pub tracking_clients: u64,
+        /// Value of the `maxclients` configuration.
+        pub maxclients: u64,
+    }
+}
+
+info_struct! {
+    /// The `# Memory` section.
+    pub struct MemorySection {
+        /// Bytes allocated by the server's allocator.
+        pub used_memory: u64,
+        /// Bytes allocated as seen by the operating system.
+        pub used_memory_rss: u64,
+        /// Peak memory consumed in bytes.
+        pub used_memory_peak: u64,
+        /// Bytes used by the Lua engine.
+        pub used_memory_lua: u64,
+        /// Value of the `maxmemory` configuration.
+        pub maxmemory: u64,
+        /// Value of the `maxmemory-policy` configuration.
+        pub maxmemory_policy: String,
+        /// Ratio between `used_memory_rss` and `used_memory`.
+        pub mem_fragmentation_ratio: f64,
+    }
+}
+
+info_struct! {
+    /// The `# Persistence` section.
+    pub struct PersistenceSection {
+        /// Whether a dump file is being loaded.
+        pub loading: bool,
+        /// Number of changes since the last dump.
+        pub rdb_changes_since_last_save: u64,
+        /// Whether a RDB save is in progress.
+        pub rdb_bgsave_in_progress: bool,
+        /// Unix timestamp of the last successful RDB save.
+        pub rdb_last_save_time: i64,
+        /// Status of the last RDB save operation.
+        pub rdb_last_bgsave_status: String,
+        /// Whether AOF logging is activated.
+        pub aof_enabled: bool,
+        /// Whether an AOF rewrite is in progress.
+        pub aof_rewrite_in_progress: bool,
+        /// Status of the last AOF rewrite operation.
+        pub aof_last_bgrewrite_status: String,
+    }
+}
+
+info_struct! {
+    /// The `# Stats` section.
+    pub struct StatsSection {
+        /// Total number of connections accepted by the server.
+        pub total_connections_received: u64,
+        /// Total number of commands processed by the server.
+        pub total_commands_processed: u64,
+        /// Number of commands processed per second.
+        pub instantaneous_ops_per_sec: u64,
+        /// Total number of bytes read from the network.
+        pub total_net_input_bytes: u64,
+        /// Total number of bytes written to the network.
+        pub total_net_output_bytes: u64,
+        /// Number of connections rejected because of `maxclients`.
+        pub rejected_connections: u64,
+        /// Total number of key expiration events.
+        pub expired_keys: u64,
+        /// Number of keys evicted due to `maxmemory`.
+        pub evicted_keys: u64,
+        /// Number of successful lookups of keys.
+        pub keyspace_hits: u64,
+        /// Number of failed lookups of keys.
+        pub keyspace_misses: u64,
+        /// Number of pub/sub channels with client subscriptions.
+        pub pubsub_channels: u64,
+        /// Number of pub/sub patterns with client subscriptions.
+        pub pubsub_patterns: u64,
+    }
+}
+
+info_struct! {
+    /// A connected replica, from the `slaveN` fields of the `# Replication`
+    /// section.
+    pub struct ReplicaInfo {
+        /// Address of the replica.
+        pub ip: String,
+        /// Port of the replica.
+        pub port: u16,
+        /// Replication state, e.g. `online`.
+        pub state: String,
+        /// Replication offset acknowledged by the replica.
+        pub offset: i64,
+        /// Seconds since the last interaction with the replica.
+        pub lag: i64,
+    }
+}
+
+/// The `# Replication` section.
+///
+/// Fields missing from the reply keep their default value.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ReplicationSection {
+    /// `master` or `slave`.
+    pub role: String,
+    /// Number of connected replicas.
+    pub connected_slaves: u64,
+    /// Host of the master, on replicas.
+    pub master_host: Option<String>,
+    /// Port of the master, on replicas.
+    pub master_port: Option<u16>,
+    /// Status of the link to the master (`up` or `down`), on replicas.
+    pub master_link_status: Option<String>,
+    /// Replication ID of the server.
+    pub master_replid: String,
+    /// Replication offset of the server.
+    pub master_repl_offset: i64,
+    /// Connected replicas, ordered by their position in the reply.
+    pub replicas: Vec<ReplicaInfo>,
+}
+
+impl ReplicationSection {
+    fn from_dict(dict: &InfoDict) -> RedisResult<Self> {
+        let mut replicas: Vec<(usize, ReplicaInfo)> = compound_fields(
+            dict,
+            |key| key.strip_prefix("slave")?.parse().ok(),
+            ReplicaInfo::from_dict,
+        )?
+        .into_iter()
+        .collect();
+        replicas.sort_by_key(|(index, _)| *index);
+
+        Ok(ReplicationSection {
+            role: field(dict, "role")?,
+            connected_slaves: field(dict, "connected_slaves")?,
+            master_host: field(dict, "master_host")?,
+            master_port: field(dict, "master_port")?,
+            master_link_status: field(dict, "master_link_status")?,
+            master_replid: field(dict, "master_replid")?,
+            master_repl_offset: field(dict, "master_repl_offset")?,
+            replicas: replicas.into_iter().map(|(_, replica)| replica).collect(),
+        })
+    }
+}
+
+info_struct! {
+    /// Statistics of a database, from the `dbN` fields of the `# Keyspace`
+    /// section.
+    pub struct KeyspaceInfo {
+        /// Number of keys.
+        pub keys: u64,
+        /// Number of keys with an expiration.
+        pub expires: u64,
+        /// Average time to live of the keys with an expiration, in
+        /// milliseconds.
+        pub avg_ttl: u64,
+    }
+}
+
+info_struct! {
+    /// Statistics of a command, from the `cmdstat_*` fields of the
+    /// `# Commandstats` section.
+    pub struct CommandStats {
+        /// Number of calls that reached command execution.
+        pub calls: u64,
+        /// Total CPU time consumed by the command, in microseconds.
+        pub usec: u64,
+        /// Average CPU time per call, in microseconds.
+        pub usec_per_call: f64,
+        /// Number of calls rejected before execution.
+        pub rejected_calls: u64,
+        /// Number of calls that failed during execution.
+        pub failed_calls: u64,
+    }
+}
+
+/// The parsed reply of the `INFO` command.
+///
+/// Each typed section is `None` unless the reply contained it, which depends
+/// on the sections that were requested.  All sections, including the ones
+/// without a typed model, are also available as [`InfoDict`]s through
+/// [`ServerInfo::section`].
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ServerInfo {
+    /// The `# Server` section.
+    pub server: Option<ServerSection>,
+    /// The `# Clients` section.
+    pub clients: Option<ClientsSection>,
+    /// The `# Memory` section.
+    pub memory: Option<MemorySection>,
+    /// The `# Persistence` section.
+    pub persistence: Option<PersistenceSection>,
+    /// The `# Stats` section.
+    pub stats: Option<StatsSection>,
+    /// The `# Replication` section.
+    pub replication: Option<ReplicationSection>,
+    /// The `# Keyspace` section, keyed by database index.
+    pub keyspace: Option<HashMap<i64, KeyspaceInfo>>,
+    /// The `# Commandstats` section, keyed by lowercase command name.
+    /// Subcommands are named like `config|get`.
+    pub commandstats: Option<HashMap<String, CommandStats>>,
+    sections: HashMap<String, InfoDict>,
+}
+
+impl ServerInfo {
+    /// Parses the text of an `INFO` reply.
+    pub fn parse(text: &str) -> RedisResult<ServerInfo> {
+        let mut sections = HashMap::new();
+        let mut name = String::new();
+        let mut pairs = Vec::new();
+        for line in text.lines() {
+            let line = line.trim_end();
+            if let Some(header) = line.strip_prefix('#') {
+                if !pairs.is_empty() || !name.is_empty() {
+                    sections.insert(name, InfoDict::from_pairs(pairs.drain(..)));
+                }
+                name = header.trim().to_ascii_lowercase();
+            } else if let Some(pair) = line.split_once(':') {
+                pairs.push(pair);
+            }
+        }
+        if !pairs.is_empty() || !name.is_empty() {
+            sections.insert(name, InfoDict::from_pairs(pairs));
+        }
+
+        fn typed<T>(
+            sections: &HashMap<String, InfoDict>,
+            name: &str,
+            f: impl FnOnce(&InfoDict) -> RedisResult<T>,
+        ) -> RedisResult<Option<T>> {
+            sections
+                .get(name)
+                .map(|dict| {
+                    f(dict).map_err(|err| {
+                        err.with_conversion_segment(ConversionPathSegment::Key(name.into()))
+                    })
+                })
+                .transpose()
+        }
+
+        Ok(ServerInfo {
+            server: typed(&sections, "server", ServerSection::from_dict)?,
+            clients: typed(&sections, "clients", ClientsSection::from_dict)?,
+            memory: typed(&sections, "memory", MemorySection::from_dict)?,
+            persistence: typed(&sections, "persistence", PersistenceSection::from_dict)?,
+            stats: typed(&sections, "stats", StatsSection::from_dict)?,
+            replication: typed(&sections, "replication", ReplicationSection::from_dict)?,
+            keyspace: typed(&sections, "keyspace", |dict| {
+                compound_fields(
+                    dict,
+                    |key| key.strip_prefix("db")?.parse().ok(),
+                    KeyspaceInfo::from_dict,
+                )
+            })?,
+            commandstats: typed(&sections, "commandstats", |dict| {
+                compound_fields(
+                    dict,
+                    |key| key.strip_prefix("cmdstat_").map(str::to_string),
+                    CommandStats::from_dict,
+                )
+            })?,
+            sections,
+        })
+    }
+
+    /// Returns the fields of a section by its lowercase name, e.g. `cpu`.
+    pub fn section(&self, name: &str) -> Option<&InfoDict> {
+        self.sections.get(name)
+    }
+
+    /// Returns the names of all sections in the reply.
+    pub fn section_names(&self) -> impl Iterator<Item = &str> {
+        self.sections.keys().map(String::as_str)
+    }
+}
+
+// Converts the compound fields of a section whose keys are accepted by `key`.
+fn compound_fields<K: std::hash::Hash + Eq, T>(
+    dict: &InfoDict,
+    key: impl Fn(&str) -> Option<K>,
+    f: impl Fn(&InfoDict) -> RedisResult<T>,
+) -> RedisResult<HashMap<K, T>> {
+    dict.iter()
+        .filter_map(|(name, value)| Some((name, key(name)?, value)))
+        .map(|(name, key, value)| {
+            let item = compound(value).and_then(|dict| f(&dict)).map_err(|err| {
+                err.with_conversion_segment(ConversionPathSegment::Key(name.clone()))
+            })?;
+            Ok((key, item))
+        })
+        .collect()
+}
+
+impl FromRedisValue for ServerInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<ServerInfo> {
+        let s: String = from_redis_value(v)?;
+        ServerInfo::parse(&s)
+    }
+}
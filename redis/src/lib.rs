@@ -62,7 +62,11 @@
 //! * `cluster-async`: enables async redis cluster support (optional)
 //! * `tokio-comp`: enables support for tokio (optional)
 //! * `connection-manager`: enables support for automatic reconnection (optional)
+//! * `cache-aio`: enables client side caching for async multiplexed connections (optional)
 //! * `keep-alive`: enables keep-alive option on socket by means of `socket2` crate (optional)
+//! * `derive`: enables the `FromRedisValue` and `ToRedisArgs` derive macros (optional)
+//! * `serde`: enables deserializing values into serde types with `from_value` (optional)
+//! * `num-bigint`: enables converting RESP3 big numbers into `num_bigint::BigInt` (optional)
 //!
 //! ## Connection Parameters
 //!
@@ -85,6 +89,10 @@
 //!
 //! `unix:///<path>[?db=<db>][&pass=<password>][&user=<username>]]`
 //!
+//! Both URL formats accept a `protocol=resp3` query parameter which makes
+//! the connection negotiate RESP3 through `HELLO 3` during setup.  The
+//! default stays RESP2.
+//!
 //! ## Executing Low-Level Commands
 //!
 //! To execute low-level commands you can use the `cmd` function which allows
@@ -109,7 +117,7 @@
 //! fn do_something(con: &mut redis::Connection) -> redis::RedisResult<usize> {
 //!     // This will result in a server error: "unknown command `MEMORY USAGE`"
 //!     // because "USAGE" is technically a sub-command of "MEMORY".
-//!     redis::cmd("MEMORY USAGE").arg("my_key").query(con)?;
+//!     let _: () = redis::cmd("MEMORY USAGE").arg("my_key").query(con)?;
 //!
 //!     // However, this will work as you'd expect
 //!     redis::cmd("MEMORY").arg("USAGE").arg("my_key").query(con)
@@ -318,6 +326,26 @@ assert_eq!(result, 3);
 "##
 )]
 //!
+//! # Functions
+//!
+//! Redis 7 function libraries are supported through the `Function` type,
+//! which works like `Script`: it loads the library when the server does not
+//! know the function yet and calls it with `FCALL` or `FCALL_RO`.
+//!
+//! ```rust,no_run
+//! # fn do_something() -> redis::RedisResult<()> {
+//! # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+//! # let mut con = client.get_connection().unwrap();
+//! let function = redis::Function::new(r"#!lua name=mylib
+//!     redis.register_function('add', function(keys, args)
+//!         return tonumber(args[1]) + tonumber(args[2])
+//!     end)
+//! ", "add");
+//! let result : isize = function.arg(1).arg(2).invoke(&mut con)?;
+//! assert_eq!(result, 3);
+//! # Ok(()) }
+//! ```
+//!
 #![cfg_attr(
     feature = "aio",
     doc = r##"
@@ -339,9 +367,9 @@ use redis::AsyncCommands;
 let client = redis::Client::open("redis://127.0.0.1/").unwrap();
 let mut con = client.get_async_connection().await?;
 
-con.set("key1", b"foo").await?;
+let _: () = con.set("key1", b"foo").await?;
 
-redis::cmd("SET").arg(&["key2", "bar"]).query_async(&mut con).await?;
+let _: () = redis::cmd("SET").arg(&["key2", "bar"]).query_async(&mut con).await?;
 
 let result = redis::cmd("MGET")
  .arg(&["key1", "key2"])
@@ -365,13 +393,19 @@ assert_eq!(result, Ok(("foo".to_string(), b"bar".to_vec())));
 pub use crate::client::Client;
 pub use crate::cmd::{cmd, pack_command, pipe, Arg, Cmd, Iter};
 pub use crate::commands::{
-    Commands, ControlFlow, Direction, LposOptions, PubSubCommands, SetOptions,
+    BitField, BitFieldOffset, BitFieldOverflow, BitFieldType, Commands, ControlFlow, CopyOptions,
+    Direction, HSetExOptions, LposOptions, MigrateOptions, PubSubCommands, RestoreOptions,
+    SetOptions, SortOptions, ZAggregate, ZAggregateOptions, ZRangeOptions,
 };
 pub use crate::connection::{
     parse_redis_url, transaction, Connection, ConnectionAddr, ConnectionInfo, ConnectionLike,
     IntoConnectionInfo, Msg, PubSub, RedisConnectionInfo, TlsMode,
 };
-pub use crate::parser::{parse_redis_value, Parser};
+pub use crate::encoder::{encode_value, write_error, write_value};
+pub use crate::function::{
+    Function, FunctionInfo, FunctionInvocation, FunctionRestorePolicy, LibraryInfo,
+};
+pub use crate::parser::{parse_redis_value, parse_redis_value_ref, Parser};
 pub use crate::pipeline::Pipeline;
 
 #[cfg(feature = "script")]
@@ -383,21 +417,31 @@ pub use crate::script::{Script, ScriptInvocation};
 pub use crate::types::{
     // utility functions
     from_redis_value,
+    from_owned_redis_value,
+    from_redis_value_ref,
 
     // error kinds
     ErrorKind,
 
     // conversion traits
     FromRedisValue,
+    FromRedisValueRef,
 
     // utility types
     InfoDict,
     NumericBehavior,
+    ProtocolVersion,
+    ParserLimits,
     Expiry,
     SetExpiry,
     ExistenceCheck,
+    ExpireOption,
+    ExpiryTime,
+    HashFieldExpireStatus,
+    HashFieldPersistStatus,
 
     // error and result types
+    ConversionPathSegment,
     RedisError,
     RedisResult,
     RedisWrite,
@@ -405,12 +449,19 @@ pub use crate::types::{
 
     // low level values
     Value,
+    ValueRef,
+    PushKind,
+    PushInfo,
+    VerbatimFormat,
 };
 
 #[cfg(feature = "aio")]
 #[cfg_attr(docsrs, doc(cfg(feature = "aio")))]
 pub use crate::{
-    cmd::AsyncIter, commands::AsyncCommands, parser::parse_redis_value_async, types::RedisFuture,
+    cmd::AsyncIter,
+    commands::AsyncCommands,
+    parser::{parse_redis_value_async, ServerCodec},
+    types::RedisFuture,
 };
 
 mod macros;
@@ -430,10 +481,27 @@ pub use crate::commands::JsonCommands;
 #[cfg(all(feature = "json", feature = "aio"))]
 pub use crate::commands::JsonAsyncCommands;
 
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use crate::de::from_value;
+
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use redis_derive::{FromRedisValue, ToRedisArgs};
+
+// Used by the code generated by the derive macros.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "json")]
+    pub use serde_json;
+}
+
 #[cfg(feature = "geospatial")]
 #[cfg_attr(docsrs, doc(cfg(feature = "geospatial")))]
 pub mod geo;
 
+pub mod info;
+
 #[cfg(feature = "cluster")]
 #[cfg_attr(docsrs, doc(cfg(feature = "cluster")))]
 pub mod cluster;
@@ -472,6 +540,10 @@ mod client;
 mod cmd;
 mod commands;
 mod connection;
+#[cfg(feature = "serde")]
+mod de;
+mod encoder;
+mod function;
 mod parser;
 mod script;
 mod types;
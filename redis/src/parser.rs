@@ -3,7 +3,10 @@ use std::{
     str,
 };
 
-use crate::types::{make_extension_error, ErrorKind, RedisError, RedisResult, Value};
+use crate::types::{
+    make_extension_error, ErrorKind, ParserLimits, PushKind, RedisError, RedisResult, Value,
+    ValueRef, VerbatimFormat,
+};
 
 use combine::{
     any,
@@ -53,10 +56,130 @@ where
     }
 }
 
-const MAX_RECURSE_DEPTH: usize = 100;
+/// Signals that a reply exceeded one of the [`ParserLimits`].  It is raised
+/// as a combine error and turned into `ErrorKind::ParserLimitExceeded`.
+#[derive(Debug)]
+struct LimitExceeded(&'static str);
+
+impl std::fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+impl From<LimitExceeded> for RedisError {
+    fn from(err: LimitExceeded) -> RedisError {
+        RedisError::from((
+            ErrorKind::ParserLimitExceeded,
+            "Parser limit exceeded",
+            err.0.to_string(),
+        ))
+    }
+}
+
+fn parse_error<P: std::fmt::Display>(err: combine::easy::Errors<u8, String, P>) -> RedisError {
+    let limit = err.errors.iter().find_map(|err| match err {
+        combine::easy::Error::Other(err) => err.downcast_ref::<LimitExceeded>(),
+        _ => None,
+    });
+    match limit {
+        Some(limit) => LimitExceeded(limit.0).into(),
+        None => RedisError::from((ErrorKind::ResponseError, "parse error", err.to_string())),
+    }
+}
+
+fn err_parser(line: &str) -> RedisError {
+    let desc = "An error was signalled by the server";
+    let mut pieces = line.splitn(2, ' ');
+    let kind = match pieces.next().unwrap() {
+        "ERR" => ErrorKind::ResponseError,
+        "EXECABORT" => ErrorKind::ExecAbortError,
+        "LOADING" => ErrorKind::BusyLoadingError,
+        "NOSCRIPT" => ErrorKind::NoScriptError,
+        "MOVED" => ErrorKind::Moved,
+        "ASK" => ErrorKind::Ask,
+        "TRYAGAIN" => ErrorKind::TryAgain,
+        "CLUSTERDOWN" => ErrorKind::ClusterDown,
+        "CROSSSLOT" => ErrorKind::CrossSlot,
+        "MASTERDOWN" => ErrorKind::MasterDown,
+        "READONLY" => ErrorKind::ReadOnly,
+        "NOTBUSY" => ErrorKind::NotBusy,
+        "NOPERM" => ErrorKind::NoPermission,
+        "WRONGPASS" => ErrorKind::WrongPassword,
+        "NOAUTH" => ErrorKind::NoAuth,
+        "WRONGTYPE" => ErrorKind::WrongType,
+        "OOM" => ErrorKind::OutOfMemory,
+        "NOREPLICAS" => ErrorKind::NoReplicas,
+        "BUSYKEY" => ErrorKind::BusyKey,
+        "BUSY" => ErrorKind::Busy,
+        "NOGROUP" => ErrorKind::NoGroup,
+        "BUSYGROUP" => ErrorKind::BusyGroup,
+        "UNBLOCKED" => ErrorKind::Unblocked,
+        code => return make_extension_error(code, pieces.next()),
+    };
+    match pieces.next() {
+        Some(detail) => RedisError::from((kind, desc, detail.to_string())),
+        None => RedisError::from((kind, desc)),
+    }
+}
+
+fn is_aggregate(b: u8) -> bool {
+    matches!(b, b'*' | b'%' | b'~' | b'|' | b'>')
+}
+
+fn into_pairs<T>(values: Vec<T>) -> Vec<(T, T)> {
+    let mut pairs = Vec::with_capacity(values.len() / 2);
+    let mut it = values.into_iter();
+    while let (Some(key), Some(value)) = (it.next(), it.next()) {
+        pairs.push((key, value));
+    }
+    pairs
+}
+
+fn push_kind(name: Option<&[u8]>) -> PushKind {
+    match name {
+        Some(name) => PushKind::from(String::from_utf8_lossy(name).as_ref()),
+        None => PushKind::Other(String::new()),
+    }
+}
+
+fn push_from_values(mut values: Vec<Value>) -> Value {
+    let kind = push_kind(match values.first() {
+        Some(Value::Data(kind)) => Some(kind),
+        Some(Value::Status(kind)) => Some(kind.as_bytes()),
+        _ => None,
+    });
+    if !values.is_empty() {
+        values.remove(0);
+    }
+    Value::Push { kind, data: values }
+}
+
+fn is_big_number(line: &str) -> bool {
+    let digits = line.strip_prefix(['-', '+']).unwrap_or(line);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Splits the `fmt:` prefix off the text of a verbatim string.
+fn split_verbatim(text: &str) -> Option<(VerbatimFormat, &str)> {
+    match text.split_once(':') {
+        Some((format, text)) if format.len() == 3 => {
+            let format = match format {
+                "mkd" => VerbatimFormat::Markdown,
+                "txt" => VerbatimFormat::Text,
+                other => VerbatimFormat::Unknown(other.to_string()),
+            };
+            Some((format, text))
+        }
+        _ => None,
+    }
+}
 
 fn value<'a, I>(
     count: Option<usize>,
+    limits: ParserLimits,
 ) -> impl combine::Parser<I, Output = RedisResult<Value>, PartialState = AnySendSyncPartialState>
 where
     I: RangeStream<Token = u8, Range = &'a [u8]>,
@@ -66,11 +189,13 @@ where
 
     opaque!(any_send_sync_partial_state(
         any()
-            .then_partial(move |&mut b| {
-                if b == b'*' && count > MAX_RECURSE_DEPTH {
-                    combine::unexpected_any("Maximum recursion depth exceeded").left()
+            .and_then(move |b| {
+                if is_aggregate(b) && count > limits.max_nesting_depth {
+                    Err(StreamErrorFor::<I>::other(LimitExceeded(
+                        "Maximum recursion depth exceeded",
+                    )))
                 } else {
-                    combine::value(b).right()
+                    Ok(b)
                 }
             })
             .then_partial(move |&mut b| {
@@ -102,55 +227,169 @@ where
                     })
                 };
 
-                let data = || {
-                    int().then_partial(move |size| {
-                        if *size < 0 {
-                            combine::value(Value::Nil).left()
+                let length = |max: usize, desc: &'static str| {
+                    int().and_then(move |length| {
+                        if length > 0 && length as u64 > max as u64 {
+                            Err(StreamErrorFor::<I>::other(LimitExceeded(desc)))
                         } else {
-                            take(*size as usize)
-                                .map(|bs: &[u8]| Value::Data(bs.to_vec()))
-                                .skip(crlf())
-                                .right()
+                            Ok(length)
                         }
                     })
                 };
 
+                let data = || {
+                    length(limits.max_bulk_length, "Bulk string too long").then_partial(
+                        move |size| {
+                            if *size < 0 {
+                                combine::value(Value::Nil).left()
+                            } else {
+                                take(*size as usize)
+                                    .map(|bs: &[u8]| Value::Data(bs.to_vec()))
+                                    .skip(crlf())
+                                    .right()
+                            }
+                        },
+                    )
+                };
+
                 let bulk = || {
-                    int().then_partial(move |&mut length| {
-                        if length < 0 {
-                            combine::value(Value::Nil).map(Ok).left()
-                        } else {
-                            let length = length as usize;
-                            combine::count_min_max(length, length, value(Some(count + 1)))
+                    length(limits.max_array_length, "Array too long").then_partial(
+                        move |&mut length| {
+                            if length < 0 {
+                                combine::value(Value::Nil).map(Ok).left()
+                            } else {
+                                let length = length as usize;
+                                combine::count_min_max(
+                                    length,
+                                    length,
+                                    value(Some(count + 1), limits),
+                                )
                                 .map(|result: ResultExtend<_, _>| result.0.map(Value::Bulk))
                                 .right()
+                            }
+                        },
+                    )
+                };
+
+                let aggregate = |multiplier: usize, extra: usize| {
+                    length(limits.max_array_length, "Array too long")
+                        .and_then(move |length| {
+                            if length < 0 {
+                                return Ok(None);
+                            }
+                            // The announced length fits the limit, the number of
+                            // elements it stands for may still overflow.
+                            (length as usize)
+                                .checked_mul(multiplier)
+                                .and_then(|length| length.checked_add(extra))
+                                .map(Some)
+                                .ok_or_else(|| {
+                                    StreamErrorFor::<I>::other(LimitExceeded("Array too long"))
+                                })
+                        })
+                        .then_partial(move |&mut length| match length {
+                            None => combine::value(None).map(Ok).left(),
+                            Some(length) => combine::count_min_max(
+                                length,
+                                length,
+                                value(Some(count + 1), limits),
+                            )
+                            .map(|result: ResultExtend<Vec<Value>, _>| result.0.map(Some))
+                            .right(),
+                        })
+                };
+
+                let map = || {
+                    aggregate(2, 0).map(|result| {
+                        result.map(|values| values.map(into_pairs).map_or(Value::Nil, Value::Map))
+                    })
+                };
+
+                let set = || {
+                    aggregate(1, 0)
+                        .map(|result| result.map(|values| values.map_or(Value::Nil, Value::Set)))
+                };
+
+                let attribute = || {
+                    aggregate(2, 1).map(|result| {
+                        result.map(|values| match values {
+                            Some(mut values) => {
+                                let data = Box::new(values.pop().unwrap_or(Value::Nil));
+                                Value::Attribute {
+                                    data,
+                                    attributes: into_pairs(values),
+                                }
+                            }
+                            None => Value::Nil,
+                        })
+                    })
+                };
+
+                let push = || {
+                    aggregate(1, 0).map(|result| {
+                        result.map(|values| values.map_or(Value::Nil, push_from_values))
+                    })
+                };
+
+                let null = || line().map(|_| Value::Nil);
+
+                let double = || {
+                    line().and_then(|line| match line.trim().parse::<f64>() {
+                        Err(_) => Err(StreamErrorFor::<I>::message_static_message(
+                            "Expected double, got garbage",
+                        )),
+                        Ok(value) => Ok(Value::Double(value)),
+                    })
+                };
+
+                let boolean = || {
+                    line().and_then(|line| match line {
+                        "t" => Ok(Value::Boolean(true)),
+                        "f" => Ok(Value::Boolean(false)),
+                        _ => Err(StreamErrorFor::<I>::message_static_message(
+                            "Expected boolean, got garbage",
+                        )),
+                    })
+                };
+
+                let big_number = || {
+                    line().and_then(|line| {
+                        let line = line.trim();
+                        if is_big_number(line) {
+                            Ok(Value::BigNumber(line.to_string()))
+                        } else {
+                            Err(StreamErrorFor::<I>::message_static_message(
+                                "Expected big number, got garbage",
+                            ))
                         }
                     })
                 };
 
-                let error = || {
-                    line().map(|line: &str| {
-                        let desc = "An error was signalled by the server";
-                        let mut pieces = line.splitn(2, ' ');
-                        let kind = match pieces.next().unwrap() {
-                            "ERR" => ErrorKind::ResponseError,
-                            "EXECABORT" => ErrorKind::ExecAbortError,
-                            "LOADING" => ErrorKind::BusyLoadingError,
-                            "NOSCRIPT" => ErrorKind::NoScriptError,
-                            "MOVED" => ErrorKind::Moved,
-                            "ASK" => ErrorKind::Ask,
-                            "TRYAGAIN" => ErrorKind::TryAgain,
-                            "CLUSTERDOWN" => ErrorKind::ClusterDown,
-                            "CROSSSLOT" => ErrorKind::CrossSlot,
-                            "MASTERDOWN" => ErrorKind::MasterDown,
-                            "READONLY" => ErrorKind::ReadOnly,
-                            "NOTBUSY" => ErrorKind::NotBusy,
-                            code => return make_extension_error(code, pieces.next()),
-                        };
-                        match pieces.next() {
-                            Some(detail) => RedisError::from((kind, desc, detail.to_string())),
-                            None => RedisError::from((kind, desc)),
+                let verbatim = || {
+                    data().and_then(|value| match value {
+                        Value::Data(bytes) => {
+                            let text = String::from_utf8(bytes)
+                                .map_err(|err| StreamErrorFor::<I>::other(err.utf8_error()))?;
+                            match split_verbatim(&text) {
+                                Some((format, text)) => Ok(Value::VerbatimString {
+                                    format,
+                                    text: text.to_string(),
+                                }),
+                                None => Err(StreamErrorFor::<I>::message_static_message(
+                                    "Expected verbatim string format prefix",
+                                )),
+                            }
                         }
+                        other => Ok(other),
+                    })
+                };
+
+                let error = || line().map(err_parser);
+
+                let blob_error = || {
+                    data().map(|value| match value {
+                        Value::Data(bytes) => err_parser(&String::from_utf8_lossy(&bytes)),
+                        _ => err_parser("ERR"),
                     })
                 };
 
@@ -160,6 +399,16 @@ where
                     b'$' => data().map(Ok),
                     b'*' => bulk(),
                     b'-' => error().map(Err),
+                    b'%' => map(),
+                    b'~' => set(),
+                    b'|' => attribute(),
+                    b'>' => push(),
+                    b'_' => null().map(Ok),
+                    b',' => double().map(Ok),
+                    b'#' => boolean().map(Ok),
+                    b'(' => big_number().map(Ok),
+                    b'=' => verbatim().map(Ok),
+                    b'!' => blob_error().map(Err),
                     b => combine::unexpected_any(combine::error::Token(b))
                 )
             })
@@ -170,6 +419,10 @@ where
 mod aio_support {
     use super::*;
 
+    use crate::cmd::Cmd;
+    use crate::encoder::{write_error, write_value};
+    use crate::types::ProtocolVersion;
+
     use bytes::{Buf, BytesMut};
     use tokio::io::AsyncRead;
     use tokio_util::codec::{Decoder, Encoder};
@@ -177,9 +430,18 @@ mod aio_support {
     #[derive(Default)]
     pub struct ValueCodec {
         state: AnySendSyncPartialState,
+        limits: ParserLimits,
     }
 
     impl ValueCodec {
+        /// Creates a codec which enforces the given limits on replies.
+        pub fn with_limits(limits: ParserLimits) -> ValueCodec {
+            ValueCodec {
+                state: Default::default(),
+                limits,
+            }
+        }
+
         fn decode_stream(
             &mut self,
             bytes: &mut BytesMut,
@@ -189,18 +451,17 @@ mod aio_support {
                 let buffer = &bytes[..];
                 let mut stream =
                     combine::easy::Stream(combine::stream::MaybePartialStream(buffer, !eof));
-                match combine::stream::decode_tokio(value(None), &mut stream, &mut self.state) {
+                match combine::stream::decode_tokio(
+                    value(None, self.limits),
+                    &mut stream,
+                    &mut self.state,
+                ) {
                     Ok(x) => x,
                     Err(err) => {
-                        let err = err
-                            .map_position(|pos| pos.translate_position(buffer))
-                            .map_range(|range| format!("{range:?}"))
-                            .to_string();
-                        return Err(RedisError::from((
-                            ErrorKind::ResponseError,
-                            "parse error",
-                            err,
-                        )));
+                        return Err(parse_error(
+                            err.map_position(|pos| pos.translate_position(buffer))
+                                .map_range(|range| format!("{range:?}")),
+                        ));
                     }
                 }
             };
@@ -234,6 +495,168 @@ mod aio_support {
         }
     }
 
+    /// A codec for the server side of a connection, useful to implement
+    /// proxies or test fakes.
+    ///
+    /// It decodes the commands sent by clients into [`Cmd`]s and encodes
+    /// replies with [`write_value`](crate::write_value) in the protocol
+    /// version set with [`ServerCodec::set_protocol`].  Besides the regular
+    /// arrays of bulk strings, inline commands such as `PING\r\n` are
+    /// accepted as well.
+    #[derive(Default)]
+    pub struct ServerCodec {
+        values: ValueCodec,
+        protocol: ProtocolVersion,
+        // Whether `values` is in the middle of parsing a command.
+        pending: bool,
+    }
+
+    impl ServerCodec {
+        /// Creates a new codec which encodes replies with RESP2.
+        pub fn new() -> ServerCodec {
+            ServerCodec::default()
+        }
+
+        /// Creates a new codec which rejects commands exceeding the given
+        /// limits.
+        pub fn with_limits(limits: ParserLimits) -> ServerCodec {
+            ServerCodec {
+                values: ValueCodec::with_limits(limits),
+                ..Default::default()
+            }
+        }
+
+        /// Returns the protocol version replies are encoded with.
+        pub fn protocol(&self) -> ProtocolVersion {
+            self.protocol
+        }
+
+        /// Sets the protocol version replies are encoded with, usually after
+        /// the client sent `HELLO`.
+        pub fn set_protocol(&mut self, protocol: ProtocolVersion) {
+            self.protocol = protocol;
+        }
+
+        fn decode_stream(&mut self, bytes: &mut BytesMut, eof: bool) -> RedisResult<Option<Cmd>> {
+            loop {
+                if !self.pending && bytes.first().map_or(false, |b| *b != b'*') {
+                    match decode_inline(bytes) {
+                        Some(Some(cmd)) => return Ok(Some(cmd)),
+                        Some(None) => continue,
+                        None => return Ok(None),
+                    }
+                }
+
+                let len = bytes.len();
+                let value = if eof {
+                    self.values.decode_eof(bytes)?
+                } else {
+                    self.values.decode(bytes)?
+                };
+                match value {
+                    Some(value) => {
+                        self.pending = false;
+                        match value? {
+                            Value::Bulk(items) if items.is_empty() => continue,
+                            value => return into_cmd(value).map(Some),
+                        }
+                    }
+                    None => {
+                        self.pending |= bytes.len() != len;
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Splits the first line of `bytes` into an inline command.  Returns
+    /// `None` if the line is not complete yet and `Some(None)` for empty
+    /// lines.
+    fn decode_inline(bytes: &mut BytesMut) -> Option<Option<Cmd>> {
+        let end = bytes.iter().position(|b| *b == b'\n')?;
+        let line = bytes.split_to(end + 1);
+        let mut args = line[..end]
+            .split(|b| b.is_ascii_whitespace())
+            .filter(|arg| !arg.is_empty());
+        let mut cmd = Cmd::new();
+        args.next()
+            .map(|name| {
+                cmd.arg(name);
+                for arg in args {
+                    cmd.arg(arg);
+                }
+                cmd
+            })
+            .map_or(Some(None), |cmd| Some(Some(cmd)))
+    }
+
+    fn into_cmd(value: Value) -> RedisResult<Cmd> {
+        let mut cmd = Cmd::new();
+        match value {
+            Value::Bulk(items) => {
+                for item in items {
+                    match item {
+                        Value::Data(arg) => cmd.arg(arg),
+                        _ => fail!((
+                            ErrorKind::ResponseError,
+                            "Protocol error",
+                            "expected bulk strings as command arguments".to_string()
+                        )),
+                    };
+                }
+                Ok(cmd)
+            }
+            _ => fail!((
+                ErrorKind::ResponseError,
+                "Protocol error",
+                "expected an array of bulk strings".to_string()
+            )),
+        }
+    }
+
+    impl Decoder for ServerCodec {
+        type Item = Cmd;
+        type Error = RedisError;
+
+        fn decode(&mut self, bytes: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+            self.decode_stream(bytes, false)
+        }
+
+        fn decode_eof(&mut self, bytes: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+            self.decode_stream(bytes, true)
+        }
+    }
+
+    impl Encoder<Value> for ServerCodec {
+        type Error = RedisError;
+        fn encode(&mut self, item: Value, dst: &mut BytesMut) -> Result<(), Self::Error> {
+            let mut out = Vec::new();
+            write_value(&item, self.protocol, &mut out);
+            dst.extend_from_slice(&out);
+            Ok(())
+        }
+    }
+
+    impl Encoder<RedisResult<Value>> for ServerCodec {
+        type Error = RedisError;
+        fn encode(
+            &mut self,
+            item: RedisResult<Value>,
+            dst: &mut BytesMut,
+        ) -> Result<(), Self::Error> {
+            match item {
+                Ok(value) => self.encode(value, dst),
+                Err(err) => {
+                    let mut out = Vec::new();
+                    write_error(&err, &mut out);
+                    dst.extend_from_slice(&out);
+                    Ok(())
+                }
+            }
+        }
+    }
+
     /// Parses a redis value asynchronously.
     pub async fn parse_redis_value_async<R>(
         decoder: &mut combine::stream::Decoder<AnySendSyncPartialState, PointerOffset<[u8]>>,
@@ -242,7 +665,19 @@ mod aio_support {
     where
         R: AsyncRead + std::marker::Unpin,
     {
-        let result = combine::decode_tokio!(*decoder, *read, value(None), |input, _| {
+        parse_redis_value_async_with_limits(decoder, read, ParserLimits::default()).await
+    }
+
+    /// Parses a redis value asynchronously, enforcing the given limits.
+    pub(crate) async fn parse_redis_value_async_with_limits<R>(
+        decoder: &mut combine::stream::Decoder<AnySendSyncPartialState, PointerOffset<[u8]>>,
+        read: &mut R,
+        limits: ParserLimits,
+    ) -> RedisResult<Value>
+    where
+        R: AsyncRead + std::marker::Unpin,
+    {
+        let result = combine::decode_tokio!(*decoder, *read, value(None, limits), |input, _| {
             combine::stream::easy::Stream::from(input)
         });
         match result {
@@ -252,11 +687,10 @@ mod aio_support {
                     if err.is_unexpected_end_of_input() {
                         RedisError::from(io::Error::from(io::ErrorKind::UnexpectedEof))
                     } else {
-                        let err = err
-                            .map_range(|range| format!("{range:?}"))
-                            .map_position(|pos| pos.translate_position(decoder.buffer()))
-                            .to_string();
-                        RedisError::from((ErrorKind::ResponseError, "parse error", err))
+                        parse_error(
+                            err.map_range(|range| format!("{range:?}"))
+                                .map_position(|pos| pos.translate_position(decoder.buffer())),
+                        )
                     }
                 }
             }),
@@ -272,6 +706,7 @@ pub use self::aio_support::*;
 /// The internal redis response parser.
 pub struct Parser {
     decoder: combine::stream::decoder::Decoder<AnySendSyncPartialState, PointerOffset<[u8]>>,
+    limits: ParserLimits,
 }
 
 impl Default for Parser {
@@ -290,8 +725,15 @@ impl Parser {
     /// be invoked multiple times.  In other words: the stream does not have
     /// to be terminated.
     pub fn new() -> Parser {
+        Parser::with_limits(ParserLimits::default())
+    }
+
+    /// Creates a new parser which rejects replies exceeding the given limits
+    /// with an `ErrorKind::ParserLimitExceeded` error.
+    pub fn with_limits(limits: ParserLimits) -> Parser {
         Parser {
             decoder: combine::stream::decoder::Decoder::new(),
+            limits,
         }
     }
 
@@ -300,7 +742,7 @@ impl Parser {
     /// Parses synchronously into a single value from the reader.
     pub fn parse_value<T: Read>(&mut self, mut reader: T) -> RedisResult<Value> {
         let mut decoder = &mut self.decoder;
-        let result = combine::decode!(decoder, reader, value(None), |input, _| {
+        let result = combine::decode!(decoder, reader, value(None, self.limits), |input, _| {
             combine::stream::easy::Stream::from(input)
         });
         match result {
@@ -310,17 +752,26 @@ impl Parser {
                     if err.is_unexpected_end_of_input() {
                         RedisError::from(io::Error::from(io::ErrorKind::UnexpectedEof))
                     } else {
-                        let err = err
-                            .map_range(|range| format!("{range:?}"))
-                            .map_position(|pos| pos.translate_position(decoder.buffer()))
-                            .to_string();
-                        RedisError::from((ErrorKind::ResponseError, "parse error", err))
+                        parse_error(
+                            err.map_range(|range| format!("{range:?}"))
+                                .map_position(|pos| pos.translate_position(decoder.buffer())),
+                        )
                     }
                 }
             }),
             Ok(result) => result,
         }
     }
+
+    /// Parses the first value in `bytes` without copying its data.
+    ///
+    /// Bulk strings, status lines and verbatim strings of the returned
+    /// [`ValueRef`] point into `bytes`, which has to hold at least one
+    /// complete value; anything after it is ignored.  The limits of the
+    /// parser apply as for [`parse_value`](Self::parse_value).
+    pub fn parse_value_ref<'a>(&self, bytes: &'a [u8]) -> RedisResult<ValueRef<'a>> {
+        RefParser::new(bytes, self.limits).value(1)
+    }
 }
 
 /// Parses bytes into a redis value.
@@ -332,6 +783,202 @@ pub fn parse_redis_value(bytes: &[u8]) -> RedisResult<Value> {
     parser.parse_value(bytes)
 }
 
+/// Parses bytes into a redis value which borrows its data from `bytes`.
+///
+/// This is the borrowed counterpart of [`parse_redis_value`], see
+/// [`Parser::parse_value_ref`].
+pub fn parse_redis_value_ref(bytes: &[u8]) -> RedisResult<ValueRef<'_>> {
+    RefParser::new(bytes, ParserLimits::default()).value(1)
+}
+
+/// Reads a single [`ValueRef`] out of a complete buffer.
+///
+/// The streaming parser keeps its partial state between reads, which can't
+/// hold values borrowing from the input, so borrowed values are read here.
+/// It shares the limits, the error mapping and the decoding of the RESP3
+/// scalars with it.
+struct RefParser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    limits: ParserLimits,
+}
+
+impl<'a> RefParser<'a> {
+    fn new(bytes: &'a [u8], limits: ParserLimits) -> Self {
+        RefParser {
+            bytes,
+            pos: 0,
+            limits,
+        }
+    }
+
+    fn parse_error(desc: &str) -> RedisError {
+        RedisError::from((ErrorKind::ResponseError, "parse error", desc.to_string()))
+    }
+
+    fn eof() -> RedisError {
+        RedisError::from(io::Error::from(io::ErrorKind::UnexpectedEof))
+    }
+
+    fn take(&mut self, len: usize) -> RedisResult<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(Self::eof)?;
+        let rv = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(rv)
+    }
+
+    fn crlf(&mut self) -> RedisResult<()> {
+        match self.take(2)? {
+            b"\r\n" => Ok(()),
+            _ => Err(Self::parse_error("Expected CRLF")),
+        }
+    }
+
+    fn line(&mut self) -> RedisResult<&'a str> {
+        let len = self.bytes[self.pos..]
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or_else(Self::eof)?;
+        let line = self.take(len)?;
+        self.crlf()?;
+        str::from_utf8(line).map_err(|_| Self::parse_error("Invalid UTF-8 in line"))
+    }
+
+    fn int(&mut self) -> RedisResult<i64> {
+        self.line()?
+            .trim()
+            .parse()
+            .map_err(|_| Self::parse_error("Expected integer, got garbage"))
+    }
+
+    fn length(&mut self, max: usize, desc: &'static str) -> RedisResult<Option<usize>> {
+        let length = self.int()?;
+        if length < 0 {
+            Ok(None)
+        } else if length as u64 > max as u64 {
+            Err(LimitExceeded(desc).into())
+        } else {
+            Ok(Some(length as usize))
+        }
+    }
+
+    fn data(&mut self) -> RedisResult<Option<&'a [u8]>> {
+        match self.length(self.limits.max_bulk_length, "Bulk string too long")? {
+            Some(size) => {
+                let data = self.take(size)?;
+                self.crlf()?;
+                Ok(Some(data))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn aggregate(
+        &mut self,
+        multiplier: usize,
+        extra: usize,
+        count: usize,
+    ) -> RedisResult<Option<Vec<ValueRef<'a>>>> {
+        let length = match self.length(self.limits.max_array_length, "Array too long")? {
+            Some(length) => length
+                .checked_mul(multiplier)
+                .and_then(|length| length.checked_add(extra))
+                .ok_or(LimitExceeded("Array too long"))?,
+            None => return Ok(None),
+        };
+        // Every value takes at least three bytes, so don't trust the
+        // announced length for preallocation beyond that.
+        let mut values = Vec::with_capacity(length.min(self.bytes.len() / 3));
+        for _ in 0..length {
+            values.push(self.value(count + 1)?);
+        }
+        Ok(Some(values))
+    }
+
+    fn value(&mut self, count: usize) -> RedisResult<ValueRef<'a>> {
+        let b = self.take(1)?[0];
+        if is_aggregate(b) && count > self.limits.max_nesting_depth {
+            return Err(LimitExceeded("Maximum recursion depth exceeded").into());
+        }
+        match b {
+            b'+' => Ok(match self.line()? {
+                "OK" => ValueRef::Okay,
+                line => ValueRef::Status(line),
+            }),
+            b':' => Ok(ValueRef::Int(self.int()?)),
+            b'$' => Ok(self.data()?.map_or(ValueRef::Nil, ValueRef::Data)),
+            b'*' => Ok(self
+                .aggregate(1, 0, count)?
+                .map_or(ValueRef::Nil, ValueRef::Bulk)),
+            b'-' => Err(err_parser(self.line()?)),
+            b'%' => Ok(self
+                .aggregate(2, 0, count)?
+                .map_or(ValueRef::Nil, |values| ValueRef::Map(into_pairs(values)))),
+            b'~' => Ok(self
+                .aggregate(1, 0, count)?
+                .map_or(ValueRef::Nil, ValueRef::Set)),
+            b'|' => Ok(match self.aggregate(2, 1, count)? {
+                Some(mut values) => {
+                    let data = Box::new(values.pop().unwrap_or(ValueRef::Nil));
+                    ValueRef::Attribute {
+                        data,
+                        attributes: into_pairs(values),
+                    }
+                }
+                None => ValueRef::Nil,
+            }),
+            b'>' => Ok(match self.aggregate(1, 0, count)? {
+                Some(mut values) => {
+                    let kind = push_kind(match values.first() {
+                        Some(ValueRef::Data(kind)) => Some(kind),
+                        Some(ValueRef::Status(kind)) => Some(kind.as_bytes()),
+                        _ => None,
+                    });
+                    if !values.is_empty() {
+                        values.remove(0);
+                    }
+                    ValueRef::Push { kind, data: values }
+                }
+                None => ValueRef::Nil,
+            }),
+            b'_' => self.line().map(|_| ValueRef::Nil),
+            b',' => match self.line()?.trim().parse::<f64>() {
+                Ok(value) => Ok(ValueRef::Double(value)),
+                Err(_) => Err(Self::parse_error("Expected double, got garbage")),
+            },
+            b'#' => match self.line()? {
+                "t" => Ok(ValueRef::Boolean(true)),
+                "f" => Ok(ValueRef::Boolean(false)),
+                _ => Err(Self::parse_error("Expected boolean, got garbage")),
+            },
+            b'(' => match self.line()?.trim() {
+                line if is_big_number(line) => Ok(ValueRef::BigNumber(line)),
+                _ => Err(Self::parse_error("Expected big number, got garbage")),
+            },
+            b'=' => match self.data()? {
+                Some(bytes) => {
+                    let text = str::from_utf8(bytes)
+                        .map_err(|_| Self::parse_error("Invalid UTF-8 in verbatim string"))?;
+                    match split_verbatim(text) {
+                        Some((format, text)) => Ok(ValueRef::VerbatimString { format, text }),
+                        None => Err(Self::parse_error("Expected verbatim string format prefix")),
+                    }
+                }
+                None => Ok(ValueRef::Nil),
+            },
+            b'!' => Err(match self.data()? {
+                Some(bytes) => err_parser(&String::from_utf8_lossy(bytes)),
+                None => err_parser("ERR"),
+            }),
+            _ => Err(Self::parse_error("Unexpected type byte")),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -357,7 +1004,314 @@ mod tests {
         let bytes = b"*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n";
         match parse_redis_value(bytes) {
             Ok(_) => panic!("Expected Err"),
-            Err(e) => assert!(matches!(e.kind(), ErrorKind::ResponseError)),
+            Err(e) => assert!(matches!(e.kind(), ErrorKind::ParserLimitExceeded)),
+        }
+    }
+
+    #[test]
+    fn parse_resp3_scalars() {
+        assert_eq!(parse_redis_value(b"_\r\n").unwrap(), Value::Nil);
+        assert_eq!(
+            parse_redis_value(b",1.23\r\n").unwrap(),
+            Value::Double(1.23)
+        );
+        assert_eq!(
+            parse_redis_value(b",-inf\r\n").unwrap(),
+            Value::Double(f64::NEG_INFINITY)
+        );
+        assert_eq!(parse_redis_value(b"#t\r\n").unwrap(), Value::Boolean(true));
+        assert_eq!(parse_redis_value(b"#f\r\n").unwrap(), Value::Boolean(false));
+        assert_eq!(
+            parse_redis_value(b"(3492890328409238509324850943850943825024385\r\n").unwrap(),
+            Value::BigNumber("3492890328409238509324850943850943825024385".to_string())
+        );
+        assert_eq!(
+            parse_redis_value(b"(-12\r\n").unwrap(),
+            Value::BigNumber("-12".to_string())
+        );
+        assert!(parse_redis_value(b"(12a\r\n").is_err());
+        assert!(parse_redis_value(b"(-\r\n").is_err());
+        assert_eq!(
+            parse_redis_value(b"=15\r\ntxt:Some string\r\n").unwrap(),
+            Value::VerbatimString {
+                format: VerbatimFormat::Text,
+                text: "Some string".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_resp3_aggregates() {
+        assert_eq!(
+            parse_redis_value(b"%2\r\n+first\r\n:1\r\n+second\r\n:2\r\n").unwrap(),
+            Value::Map(vec![
+                (Value::Status("first".to_string()), Value::Int(1)),
+                (Value::Status("second".to_string()), Value::Int(2)),
+            ])
+        );
+        assert_eq!(
+            parse_redis_value(b"~2\r\n$3\r\nfoo\r\n$3\r\nbar\r\n").unwrap(),
+            Value::Set(vec![
+                Value::Data(b"foo".to_vec()),
+                Value::Data(b"bar".to_vec())
+            ])
+        );
+        assert_eq!(
+            parse_redis_value(b"|1\r\n+ttl\r\n:3600\r\n:7\r\n").unwrap(),
+            Value::Attribute {
+                data: Box::new(Value::Int(7)),
+                attributes: vec![(Value::Status("ttl".to_string()), Value::Int(3600))],
+            }
+        );
+        assert_eq!(
+            parse_redis_value(b">3\r\n$7\r\nmessage\r\n$2\r\nch\r\n$5\r\nhello\r\n").unwrap(),
+            Value::Push {
+                kind: PushKind::Message,
+                data: vec![Value::Data(b"ch".to_vec()), Value::Data(b"hello".to_vec())],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_resp3_blob_error() {
+        let err = parse_redis_value(b"!21\r\nSYNTAX invalid syntax\r\n").unwrap_err();
+        assert_eq!(err.code(), Some("SYNTAX"));
+        assert_eq!(err.detail(), Some("invalid syntax"));
+    }
+
+    #[test]
+    fn parse_structured_error_kinds() {
+        let cases = [
+            ("NOPERM", ErrorKind::NoPermission, false),
+            ("WRONGPASS", ErrorKind::WrongPassword, false),
+            ("NOAUTH", ErrorKind::NoAuth, false),
+            ("WRONGTYPE", ErrorKind::WrongType, false),
+            ("OOM", ErrorKind::OutOfMemory, false),
+            ("NOREPLICAS", ErrorKind::NoReplicas, true),
+            ("BUSYKEY", ErrorKind::BusyKey, false),
+            ("BUSY", ErrorKind::Busy, true),
+            ("NOGROUP", ErrorKind::NoGroup, false),
+            ("BUSYGROUP", ErrorKind::BusyGroup, false),
+            ("UNBLOCKED", ErrorKind::Unblocked, false),
+        ];
+        for (code, kind, retryable) in cases {
+            let err = parse_redis_value(format!("-{code} some detail\r\n").as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), kind);
+            assert_eq!(err.code(), Some(code));
+            assert_eq!(err.detail(), Some("some detail"));
+            assert_eq!(err.is_retryable(), retryable, "{code}");
+        }
+
+        let err = parse_redis_value(b"-SOMETHINGELSE detail\r\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ExtensionError);
+        assert!(!err.is_retryable());
+    }
+
+    #[cfg(feature = "aio")]
+    #[test]
+    fn server_codec() {
+        use crate::ProtocolVersion;
+        use tokio_util::codec::{Decoder, Encoder};
+
+        let args = |cmd: &crate::Cmd| {
+            cmd.args_iter()
+                .map(|arg| match arg {
+                    crate::Arg::Simple(arg) => arg.to_vec(),
+                    crate::Arg::Cursor => panic!("unexpected cursor"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut codec = ServerCodec::new();
+        let packed = crate::cmd("SET")
+            .arg("key")
+            .arg("value")
+            .get_packed_command();
+        let (first, second) = packed.split_at(10);
+
+        // Commands may arrive in several chunks.
+        let mut bytes = bytes::BytesMut::from(first);
+        assert!(codec.decode(&mut bytes).unwrap().is_none());
+        bytes.extend_from_slice(second);
+        bytes.extend_from_slice(b"PING  hello\r\n\r\n*0\r\n");
+        let cmd = codec.decode(&mut bytes).unwrap().unwrap();
+        assert_eq!(
+            args(&cmd),
+            vec![b"SET".to_vec(), b"key".to_vec(), b"value".to_vec()]
+        );
+
+        // Inline commands are split on whitespace.
+        let cmd = codec.decode(&mut bytes).unwrap().unwrap();
+        assert_eq!(args(&cmd), vec![b"PING".to_vec(), b"hello".to_vec()]);
+        // Empty lines and empty arrays are skipped.
+        assert!(codec.decode(&mut bytes).unwrap().is_none());
+        assert!(bytes.is_empty());
+
+        let mut bytes = bytes::BytesMut::from(&b"*1\r\n:1\r\n"[..]);
+        assert!(codec.decode(&mut bytes).is_err());
+
+        let mut out = bytes::BytesMut::new();
+        codec.encode(Value::Nil, &mut out).unwrap();
+        codec.set_protocol(ProtocolVersion::RESP3);
+        codec.encode(Value::Nil, &mut out).unwrap();
+        codec
+            .encode(
+                Err(RedisError::from((
+                    ErrorKind::ResponseError,
+                    "desc",
+                    "oops".to_string(),
+                ))),
+                &mut out,
+            )
+            .unwrap();
+        assert_eq!(&out[..], b"$-1\r\n_\r\n-ERR oops\r\n");
+    }
+
+    #[test]
+    fn parser_limits() {
+        let limits = ParserLimits {
+            max_bulk_length: 4,
+            max_array_length: 2,
+            max_nesting_depth: 2,
+        };
+        let parse = |bytes: &[u8]| Parser::with_limits(limits).parse_value(bytes);
+
+        assert_eq!(
+            parse(b"$4\r\nfour\r\n").unwrap(),
+            Value::Data(b"four".to_vec())
+        );
+        let err = parse(b"$5\r\nfive!\r\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ParserLimitExceeded);
+        assert_eq!(err.detail(), Some("Bulk string too long"));
+        // The limit is checked before the announced data has arrived.
+        let err = parse(b"$1000000000\r\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ParserLimitExceeded);
+
+        assert!(parse(b"*2\r\n:1\r\n:2\r\n").is_ok());
+        let err = parse(b"*3\r\n:1\r\n:2\r\n:3\r\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ParserLimitExceeded);
+        assert_eq!(err.detail(), Some("Array too long"));
+        let err = parse(b"%3\r\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ParserLimitExceeded);
+
+        assert!(parse(b"*1\r\n*1\r\n:1\r\n").is_ok());
+        let err = parse(b"*1\r\n*1\r\n*1\r\n:1\r\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ParserLimitExceeded);
+
+        // Regular protocol errors are still reported as such.
+        let err = parse(b"?\r\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ResponseError);
+    }
+
+    #[test]
+    fn parse_borrowed_value() {
+        let bytes = b"*3\r\n$5\r\nhello\r\n$-1\r\n+OK\r\n";
+        let value = parse_redis_value_ref(bytes).unwrap();
+        assert_eq!(
+            value,
+            ValueRef::Bulk(vec![
+                ValueRef::Data(b"hello"),
+                ValueRef::Nil,
+                ValueRef::Okay
+            ])
+        );
+        // The data points into the input buffer instead of a copy.
+        match value {
+            ValueRef::Bulk(ref items) => match items[0] {
+                ValueRef::Data(data) => assert_eq!(data.as_ptr(), bytes[8..].as_ptr()),
+                _ => panic!("Expected data"),
+            },
+            _ => panic!("Expected bulk"),
+        }
+        assert_eq!(value.to_value(), parse_redis_value(bytes).unwrap());
+
+        assert_eq!(
+            parse_redis_value_ref(b"=15\r\ntxt:Some string\r\n").unwrap(),
+            ValueRef::VerbatimString {
+                format: VerbatimFormat::Text,
+                text: "Some string"
+            }
+        );
+        assert_eq!(
+            parse_redis_value_ref(b"(-123\r\n").unwrap(),
+            ValueRef::BigNumber("-123")
+        );
+    }
+
+    #[test]
+    fn parse_borrowed_value_errors() {
+        let err = parse_redis_value_ref(b"-ERR unknown command\r\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ResponseError);
+        assert_eq!(err.detail(), Some("unknown command"));
+
+        let err = parse_redis_value_ref(b"$10\r\nhello\r\n").unwrap_err();
+        assert!(err.is_io_error());
+        let err = parse_redis_value_ref(b"*1\r\n").unwrap_err();
+        assert!(err.is_io_error());
+
+        let err = parse_redis_value_ref(b"(12a\r\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ResponseError);
+    }
+
+    #[test]
+    fn parse_borrowed_value_limits() {
+        let parser = Parser::with_limits(ParserLimits {
+            max_bulk_length: 4,
+            max_array_length: 2,
+            max_nesting_depth: 2,
+        });
+
+        let err = parser.parse_value_ref(b"$5\r\nfive!\r\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ParserLimitExceeded);
+        assert_eq!(err.detail(), Some("Bulk string too long"));
+        let err = parser
+            .parse_value_ref(b"*3\r\n:1\r\n:2\r\n:3\r\n")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ParserLimitExceeded);
+        assert_eq!(err.detail(), Some("Array too long"));
+        assert!(parser.parse_value_ref(b"*1\r\n*1\r\n:1\r\n").is_ok());
+        let err = parser
+            .parse_value_ref(b"*1\r\n*1\r\n*1\r\n:1\r\n")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ParserLimitExceeded);
+
+        let err = parse_redis_value_ref(&b"*1\r\n".repeat(101)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ParserLimitExceeded);
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[test]
+    fn parser_limits_element_count_overflow() {
+        // 2^31 pairs are within the limit, their 2^32 elements don't fit a usize.
+        let err = Parser::with_limits(ParserLimits {
+            max_array_length: usize::MAX,
+            ..Default::default()
+        })
+        .parse_value(&b"%2147483648\r\n"[..])
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ParserLimitExceeded);
+    }
+
+    #[cfg(feature = "aio")]
+    #[test]
+    fn codec_parser_limits() {
+        use tokio_util::codec::Decoder;
+        let mut codec = ValueCodec::with_limits(ParserLimits {
+            max_bulk_length: 4,
+            ..Default::default()
+        });
+
+        let mut bytes = bytes::BytesMut::from(&b"$100\r\n"[..]);
+        let err = codec.decode(&mut bytes).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ParserLimitExceeded);
+    }
+
+    #[test]
+    fn test_max_recursion_depth_resp3() {
+        let bytes = b"%1\r\n:1\r\n".repeat(101);
+        match parse_redis_value(&bytes) {
+            Ok(_) => panic!("Expected Err"),
+            Err(e) => assert!(matches!(e.kind(), ErrorKind::ParserLimitExceeded)),
         }
     }
 }
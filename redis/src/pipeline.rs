@@ -3,7 +3,8 @@
 use crate::cmd::{cmd, cmd_len, Cmd};
 use crate::connection::ConnectionLike;
 use crate::types::{
-    from_redis_value, ErrorKind, FromRedisValue, HashSet, RedisResult, ToRedisArgs, Value,
+    from_owned_redis_value, ErrorKind, FromRedisValue, HashSet, RedisError, RedisResult,
+    ToRedisArgs, Value,
 };
 
 /// Represents a redis command pipeline.
@@ -80,20 +81,51 @@ impl Pipeline {
         write_pipeline(out, &self.commands, self.transaction_mode)
     }
 
+    /// Records which command of the pipeline caused `err`.
+    ///
+    /// Connections report the position of the failed reply, which for
+    /// transactions is shifted by the reply to `MULTI`.
+    fn annotate_error(&self, mut err: RedisError) -> RedisError {
+        let Some(index) = err.pipeline_index() else {
+            return err;
+        };
+        let (index, command) = if !self.transaction_mode {
+            (
+                Some(index),
+                self.commands.get(index).and_then(Cmd::command_name),
+            )
+        } else if index == 0 {
+            (None, Some("MULTI".to_string()))
+        } else if index <= self.commands.len() {
+            (Some(index - 1), self.commands[index - 1].command_name())
+        } else {
+            (None, Some("EXEC".to_string()))
+        };
+        let context = err.context_mut();
+        context.pipeline_index = index;
+        context.command = context.command.take().or(command);
+        err
+    }
+
     fn execute_pipelined(&self, con: &mut dyn ConnectionLike) -> RedisResult<Value> {
-        Ok(self.make_pipeline_results(con.req_packed_commands(
-            &encode_pipeline(&self.commands, false),
-            0,
-            self.commands.len(),
-        )?))
+        Ok(self.make_pipeline_results(
+            con.req_packed_commands(
+                &encode_pipeline(&self.commands, false),
+                0,
+                self.commands.len(),
+            )
+            .map_err(|err| self.annotate_error(err))?,
+        ))
     }
 
     fn execute_transaction(&self, con: &mut dyn ConnectionLike) -> RedisResult<Value> {
-        let mut resp = con.req_packed_commands(
-            &encode_pipeline(&self.commands, true),
-            self.commands.len() + 1,
-            1,
-        )?;
+        let mut resp = con
+            .req_packed_commands(
+                &encode_pipeline(&self.commands, true),
+                self.commands.len() + 1,
+                1,
+            )
+            .map_err(|err| self.annotate_error(err))?;
         match resp.pop() {
             Some(Value::Nil) => Ok(Value::Nil),
             Some(Value::Bulk(items)) => Ok(self.make_pipeline_results(items)),
@@ -129,15 +161,13 @@ impl Pipeline {
                 "This connection does not support pipelining."
             ));
         }
-        from_redis_value(
-            &(if self.commands.is_empty() {
-                Value::Bulk(vec![])
-            } else if self.transaction_mode {
-                self.execute_transaction(con)?
-            } else {
-                self.execute_pipelined(con)?
-            }),
-        )
+        from_owned_redis_value(if self.commands.is_empty() {
+            Value::Bulk(vec![])
+        } else if self.transaction_mode {
+            self.execute_transaction(con)?
+        } else {
+            self.execute_pipelined(con)?
+        })
     }
 
     #[cfg(feature = "aio")]
@@ -147,7 +177,8 @@ impl Pipeline {
     {
         let value = con
             .req_packed_commands(self, 0, self.commands.len())
-            .await?;
+            .await
+            .map_err(|err| self.annotate_error(err))?;
         Ok(self.make_pipeline_results(value))
     }
 
@@ -158,7 +189,8 @@ impl Pipeline {
     {
         let mut resp = con
             .req_packed_commands(self, self.commands.len() + 1, 1)
-            .await?;
+            .await
+            .map_err(|err| self.annotate_error(err))?;
         match resp.pop() {
             Some(Value::Nil) => Ok(Value::Nil),
             Some(Value::Bulk(items)) => Ok(self.make_pipeline_results(items)),
@@ -178,13 +210,13 @@ impl Pipeline {
         C: crate::aio::ConnectionLike,
     {
         let v = if self.commands.is_empty() {
-            return from_redis_value(&Value::Bulk(vec![]));
+            return from_owned_redis_value(Value::Bulk(vec![]));
         } else if self.transaction_mode {
             self.execute_transaction_async(con).await?
         } else {
             self.execute_pipelined_async(con).await?
         };
-        from_redis_value(&v)
+        from_owned_redis_value(v)
     }
 
     /// This is a shortcut to `query()` that does not return a value and
@@ -324,3 +356,82 @@ macro_rules! implement_pipeline_commands {
 }
 
 implement_pipeline_commands!(Pipeline);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::pipe;
+
+    /// Fails the reply at `index` the way connections do.
+    struct FailingConnection {
+        index: usize,
+    }
+
+    impl ConnectionLike for FailingConnection {
+        fn req_packed_command(&mut self, _cmd: &[u8]) -> RedisResult<Value> {
+            unreachable!()
+        }
+
+        fn req_packed_commands(
+            &mut self,
+            _cmd: &[u8],
+            _offset: usize,
+            _count: usize,
+        ) -> RedisResult<Vec<Value>> {
+            Err(RedisError::from((
+                ErrorKind::ResponseError,
+                "An error was signalled by the server",
+            ))
+            .with_pipeline_index(self.index))
+        }
+
+        fn get_db(&self) -> i64 {
+            0
+        }
+
+        fn check_connection(&mut self) -> bool {
+            true
+        }
+
+        fn is_open(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_pipeline_error_context() {
+        let mut pipeline = pipe();
+        pipeline
+            .cmd("set")
+            .arg("key")
+            .arg("secret")
+            .cmd("config")
+            .arg("set")
+            .arg("requirepass")
+            .arg("hunter2");
+
+        let err = pipeline
+            .query::<()>(&mut FailingConnection { index: 1 })
+            .unwrap_err();
+        assert_eq!(err.command(), Some("CONFIG SET"));
+        assert_eq!(err.pipeline_index(), Some(1));
+        assert_eq!(err.node_address(), None);
+        let message = err.to_string();
+        assert!(message.ends_with("(command: CONFIG SET, pipeline index: 1)"));
+        assert!(!message.contains("hunter2"));
+
+        // Transactions shift the replies by the one to MULTI.
+        pipeline.atomic();
+        let err = pipeline
+            .query::<()>(&mut FailingConnection { index: 1 })
+            .unwrap_err();
+        assert_eq!(err.command(), Some("SET"));
+        assert_eq!(err.pipeline_index(), Some(0));
+
+        let err = pipeline
+            .query::<()>(&mut FailingConnection { index: 3 })
+            .unwrap_err();
+        assert_eq!(err.command(), Some("EXEC"));
+        assert_eq!(err.pipeline_index(), None);
+    }
+}
@@ -58,6 +58,7 @@
 //!                 db: 1,
 //!                 username: Some(String::from("foo")),
 //!                 password: Some(String::from("bar")),
+//!                 ..Default::default()
 //!             }),
 //!         }),
 //!     )
@@ -93,6 +94,7 @@
 //!             db: 0,
 //!             username: Some(String::from("user")),
 //!             password: Some(String::from("pass")),
+//!             ..Default::default()
 //!         }),
 //!     }),
 //!     redis::sentinel::SentinelServerType::Master,
@@ -210,7 +212,7 @@ fn random_replica_index(max: NonZeroUsize) -> usize {
 }
 
 fn try_connect_to_first_replica(
-    addresses: &Vec<ConnectionInfo>,
+    addresses: &[ConnectionInfo],
     start_index: Option<usize>,
 ) -> Result<Client, crate::RedisError> {
     if addresses.is_empty() {
@@ -339,7 +341,7 @@ fn get_valid_replicas_addresses(
 }
 
 #[cfg(feature = "aio")]
-async fn async_get_valid_replicas_addresses<'a>(
+async fn async_get_valid_replicas_addresses(
     replicas: Vec<HashMap<String, String>>,
     node_connection_info: &SentinelNodeConnectionInfo,
 ) -> Vec<ConnectionInfo> {
@@ -602,15 +604,15 @@ impl Sentinel {
         self.async_try_all_sentinels(sentinel_masters_cmd()).await
     }
 
-    async fn async_get_sentinel_replicas<'a>(
+    async fn async_get_sentinel_replicas(
         &mut self,
-        service_name: &'a str,
+        service_name: &str,
     ) -> RedisResult<Vec<HashMap<String, String>>> {
         self.async_try_all_sentinels(sentinel_replicas_cmd(service_name))
             .await
     }
 
-    async fn async_find_master_address<'a>(
+    async fn async_find_master_address(
         &mut self,
         service_name: &str,
         node_connection_info: &SentinelNodeConnectionInfo,
@@ -619,7 +621,7 @@ impl Sentinel {
         async_find_valid_master(masters, service_name, node_connection_info).await
     }
 
-    async fn async_find_valid_replica_addresses<'a>(
+    async fn async_find_valid_replica_addresses(
         &mut self,
         service_name: &str,
         node_connection_info: &SentinelNodeConnectionInfo,
@@ -661,7 +663,7 @@ impl Sentinel {
     /// There is no guarantee that we'll actually be connecting to a different replica
     /// in the next call, but in a static set of replicas (no replicas added or
     /// removed), on average we'll choose each replica the same number of times.
-    pub async fn async_replica_rotate_for<'a>(
+    pub async fn async_replica_rotate_for(
         &mut self,
         service_name: &str,
         node_connection_info: Option<&SentinelNodeConnectionInfo>,
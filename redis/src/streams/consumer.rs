@@ -0,0 +1,502 @@
+use super::{
+    StreamClaimReply, StreamId, StreamPendingCountReply, StreamPendingId, StreamReadOptions,
+    StreamReadReply,
+};
+use crate::cmd::{cmd, Cmd};
+use crate::commands::ControlFlow;
+use crate::connection::ConnectionLike;
+use crate::pipeline::Pipeline;
+use crate::types::{from_redis_value, ErrorKind, HashMap, RedisResult};
+
+use std::time::{Duration, Instant};
+
+/// A managed consumer of a stream consumer group.
+///
+/// The consumer takes care of the bookkeeping a reliable worker needs:
+///
+/// * the group is created with `MKSTREAM` on first use,
+/// * entries that were delivered to this consumer but never acknowledged,
+///   e.g. because the worker crashed, are processed before new entries,
+/// * optionally, entries that have been pending for too long on any
+///   consumer of the group are periodically claimed,
+/// * optionally, entries that were delivered too many times are moved to
+///   a dead letter stream instead of being processed again.
+///
+/// Example:
+///
+/// ```rust,no_run
+/// use redis::ControlFlow;
+/// use redis::streams::StreamConsumer;
+/// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+/// # let mut con = client.get_connection().unwrap();
+/// let mut consumer = StreamConsumer::new("orders", "billing", "worker-1")
+///     .reclaim_idle(60_000)
+///     .dead_letter("orders:dead", 5);
+/// consumer.run(&mut con, |entry| {
+///     println!("processing {}", entry.id);
+///     Ok(ControlFlow::<()>::Continue)
+/// }).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct StreamConsumer {
+    key: String,
+    group: String,
+    consumer: String,
+    start_id: String,
+    count: usize,
+    block: usize,
+    reclaim_idle: Option<usize>,
+    // (stream key, maximum number of deliveries)
+    dead_letter: Option<(String, usize)>,
+    group_created: bool,
+    // start of the next read of this consumer's own pending entries, `None`
+    // once all of them were read
+    pending_start: Option<String>,
+    last_reclaim: Option<Instant>,
+}
+
+impl StreamConsumer {
+    /// Creates a consumer named `consumer` of the consumer group `group`
+    /// of the stream stored at `key`.
+    pub fn new(key: &str, group: &str, consumer: &str) -> StreamConsumer {
+        StreamConsumer {
+            key: key.to_string(),
+            group: group.to_string(),
+            consumer: consumer.to_string(),
+            start_id: "$".to_string(),
+            count: 10,
+            block: 5000,
+            reclaim_idle: None,
+            dead_letter: None,
+            group_created: false,
+            pending_start: Some("-".to_string()),
+            last_reclaim: None,
+        }
+    }
+
+    /// Sets the ID the group starts reading from when it is created by the
+    /// consumer.  Defaults to `$`, i.e. only entries added afterwards.
+    pub fn start_id(mut self, id: &str) -> StreamConsumer {
+        self.start_id = id.to_string();
+        self
+    }
+
+    /// Sets the maximum number of entries fetched per batch.  Defaults to 10.
+    pub fn count(mut self, count: usize) -> StreamConsumer {
+        self.count = count;
+        self
+    }
+
+    /// Sets how long, in milliseconds, a read blocks waiting for new
+    /// entries.  Defaults to 5000.
+    pub fn block(mut self, ms: usize) -> StreamConsumer {
+        self.block = ms;
+        self
+    }
+
+    /// Claims entries that have been pending on any consumer of the group
+    /// for at least `ms` milliseconds.  The pending entries are checked at
+    /// most once every `ms` milliseconds.
+    pub fn reclaim_idle(mut self, ms: usize) -> StreamConsumer {
+        self.reclaim_idle = Some(ms);
+        self
+    }
+
+    /// Moves entries that would be delivered more than `max_deliveries`
+    /// times to the stream stored at `key` and acknowledges them, instead of
+    /// processing them again.  The fields of the entry are copied to a new
+    /// entry of the dead letter stream.
+    ///
+    /// Both keys are written in one transaction, so they need to be in the
+    /// same hash slot on a cluster.
+    pub fn dead_letter(mut self, key: &str, max_deliveries: usize) -> StreamConsumer {
+        self.dead_letter = Some((key.to_string(), max_deliveries));
+        self
+    }
+
+    /// Creates the consumer group, and the stream if it doesn't exist yet.
+    /// An already existing group is not an error.
+    pub fn create_group(&mut self, con: &mut dyn ConnectionLike) -> RedisResult<()> {
+        let result = self.create_group_cmd().query(con);
+        self.group_created(result)
+    }
+
+    /// Fetches the next batch of entries for this consumer, blocking for
+    /// new entries if there are none.  The batch may be empty.
+    ///
+    /// Entries left pending on this consumer come first.  Once there are
+    /// none, idle entries of other consumers are claimed if
+    /// [`reclaim_idle`](Self::reclaim_idle) is set, and new entries are read
+    /// otherwise.
+    ///
+    /// The entries must be acknowledged with [`ack`](Self::ack) once they
+    /// were processed.
+    pub fn next_batch(&mut self, con: &mut dyn ConnectionLike) -> RedisResult<Vec<StreamId>> {
+        if !self.group_created {
+            self.create_group(con)?;
+        }
+        if let Some(cmd) = self.own_pending_cmd() {
+            let pending: StreamPendingCountReply = cmd.query(con)?;
+            self.advance_own_pending(&pending);
+            let claimed = self.claim(con, 0, &pending.ids)?;
+            if !claimed.is_empty() {
+                return Ok(claimed);
+            }
+        }
+        if let Some((min_idle, cmd)) = self.reclaim_cmd() {
+            let pending: StreamPendingCountReply = cmd.query(con)?;
+            let claimed = self.claim(con, min_idle, &pending.ids)?;
+            if !claimed.is_empty() {
+                return Ok(claimed);
+            }
+        }
+        let reply: Option<StreamReadReply> = self.read_cmd().query(con)?;
+        Ok(Self::read_entries(reply))
+    }
+
+    /// Acknowledges an entry that was processed.
+    pub fn ack(&self, con: &mut dyn ConnectionLike, id: &str) -> RedisResult<()> {
+        self.ack_cmd(id).query(con)
+    }
+
+    /// Processes entries with `handler` until it returns
+    /// [`ControlFlow::Break`], acknowledging every entry the handler
+    /// returned `Ok` for.
+    ///
+    /// Errors of the handler and of the connection are returned right away
+    /// and leave the entry pending, so that it is delivered again on the
+    /// next call.
+    pub fn run<U, F>(&mut self, con: &mut dyn ConnectionLike, mut handler: F) -> RedisResult<U>
+    where
+        F: FnMut(&StreamId) -> RedisResult<ControlFlow<U>>,
+    {
+        loop {
+            match self.run_batch(con, &mut handler) {
+                Ok(None) => {}
+                Ok(Some(value)) => {
+                    self.restart_own_pending();
+                    return Ok(value);
+                }
+                Err(err) => {
+                    self.restart_own_pending();
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    fn run_batch<U, F>(
+        &mut self,
+        con: &mut dyn ConnectionLike,
+        handler: &mut F,
+    ) -> RedisResult<Option<U>>
+    where
+        F: FnMut(&StreamId) -> RedisResult<ControlFlow<U>>,
+    {
+        for entry in self.next_batch(con)? {
+            let flow = handler(&entry)?;
+            self.ack(con, &entry.id)?;
+            if let ControlFlow::Break(value) = flow {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    fn claim(
+        &self,
+        con: &mut dyn ConnectionLike,
+        min_idle: usize,
+        pending: &[StreamPendingId],
+    ) -> RedisResult<Vec<StreamId>> {
+        let Some(cmd) = self.claim_cmd(min_idle, pending) else {
+            return Ok(vec![]);
+        };
+        let claimed: StreamClaimReply = cmd.query(con)?;
+        let (entries, dead_letters) = self.sort_claimed(pending, claimed)?;
+        if let Some(pipe) = dead_letters {
+            pipe.query::<()>(con)?;
+        }
+        Ok(entries)
+    }
+
+    fn create_group_cmd(&self) -> Cmd {
+        Cmd::xgroup_create_mkstream(&self.key, &self.group, &self.start_id)
+    }
+
+    fn group_created(&mut self, result: RedisResult<()>) -> RedisResult<()> {
+        match result {
+            Err(err) if err.kind() != ErrorKind::BusyGroup => Err(err),
+            _ => {
+                self.group_created = true;
+                Ok(())
+            }
+        }
+    }
+
+    fn own_pending_cmd(&self) -> Option<Cmd> {
+        self.pending_start.as_ref().map(|start| {
+            Cmd::xpending_consumer_count(
+                &self.key,
+                &self.group,
+                start,
+                "+",
+                self.count,
+                &self.consumer,
+            )
+        })
+    }
+
+    fn advance_own_pending(&mut self, pending: &StreamPendingCountReply) {
+        // `(` makes the start of the next range exclusive.
+        self.pending_start = pending.ids.last().map(|last| format!("({}", last.id));
+    }
+
+    fn restart_own_pending(&mut self) {
+        self.pending_start = Some("-".to_string());
+    }
+
+    fn reclaim_cmd(&mut self) -> Option<(usize, Cmd)> {
+        let min_idle = self.reclaim_idle?;
+        let now = Instant::now();
+        if let Some(last) = self.last_reclaim {
+            if now.duration_since(last) < Duration::from_millis(min_idle as u64) {
+                return None;
+            }
+        }
+        self.last_reclaim = Some(now);
+        let cmd = Cmd::xpending_idle_count(&self.key, &self.group, min_idle, "-", "+", self.count);
+        Some((min_idle, cmd))
+    }
+
+    fn claim_cmd(&self, min_idle: usize, pending: &[StreamPendingId]) -> Option<Cmd> {
+        if pending.is_empty() {
+            return None;
+        }
+        let ids: Vec<&str> = pending.iter().map(|p| p.id.as_str()).collect();
+        Some(Cmd::xclaim(
+            &self.key,
+            &self.group,
+            &self.consumer,
+            min_idle,
+            &ids,
+        ))
+    }
+
+    // Splits claimed entries into the ones to process and a transaction
+    // moving the rest to the dead letter stream.
+    fn sort_claimed(
+        &self,
+        pending: &[StreamPendingId],
+        claimed: StreamClaimReply,
+    ) -> RedisResult<(Vec<StreamId>, Option<Pipeline>)> {
+        let Some((dead_key, max_deliveries)) = &self.dead_letter else {
+            return Ok((claimed.ids, None));
+        };
+        let delivered: HashMap<&str, usize> = pending
+            .iter()
+            .map(|p| (p.id.as_str(), p.times_delivered))
+            .collect();
+        let mut entries = vec![];
+        let mut pipe = Pipeline::new();
+        pipe.atomic();
+        let mut dead = 0;
+        for entry in claimed.ids {
+            // Claiming the entry counted as another delivery.
+            let deliveries = delivered.get(entry.id.as_str()).copied().unwrap_or(0) + 1;
+            if deliveries <= *max_deliveries {
+                entries.push(entry);
+                continue;
+            }
+            if !entry.is_empty() {
+                let mut xadd = cmd("XADD");
+                xadd.arg(dead_key).arg("*");
+                for (field, value) in &entry.map {
+                    xadd.arg(field).arg(from_redis_value::<Vec<u8>>(value)?);
+                }
+                pipe.add_command(xadd).ignore();
+            }
+            pipe.add_command(self.ack_cmd(&entry.id)).ignore();
+            dead += 1;
+        }
+        Ok((entries, if dead > 0 { Some(pipe) } else { None }))
+    }
+
+    fn read_cmd(&self) -> Cmd {
+        let opts = StreamReadOptions::default()
+            .group(&self.group, &self.consumer)
+            .count(self.count)
+            .block(self.block);
+        Cmd::xread_options(&[&self.key], &[">"], &opts)
+    }
+
+    fn read_entries(reply: Option<StreamReadReply>) -> Vec<StreamId> {
+        reply
+            .map(|reply| reply.keys.into_iter().flat_map(|key| key.ids).collect())
+            .unwrap_or_default()
+    }
+
+    fn ack_cmd(&self, id: &str) -> Cmd {
+        Cmd::xack(&self.key, &self.group, &[id])
+    }
+}
+
+#[cfg(feature = "aio")]
+impl StreamConsumer {
+    /// Asynchronously creates the consumer group, and the stream if it
+    /// doesn't exist yet.  An already existing group is not an error.
+    pub async fn create_group_async<C>(&mut self, con: &mut C) -> RedisResult<()>
+    where
+        C: crate::aio::ConnectionLike,
+    {
+        let result = self.create_group_cmd().query_async(con).await;
+        self.group_created(result)
+    }
+
+    /// Asynchronously fetches the next batch of entries for this consumer,
+    /// see [`next_batch`](Self::next_batch).
+    pub async fn next_batch_async<C>(&mut self, con: &mut C) -> RedisResult<Vec<StreamId>>
+    where
+        C: crate::aio::ConnectionLike,
+    {
+        if !self.group_created {
+            self.create_group_async(con).await?;
+        }
+        if let Some(cmd) = self.own_pending_cmd() {
+            let pending: StreamPendingCountReply = cmd.query_async(con).await?;
+            self.advance_own_pending(&pending);
+            let claimed = self.claim_async(con, 0, &pending.ids).await?;
+            if !claimed.is_empty() {
+                return Ok(claimed);
+            }
+        }
+        if let Some((min_idle, cmd)) = self.reclaim_cmd() {
+            let pending: StreamPendingCountReply = cmd.query_async(con).await?;
+            let claimed = self.claim_async(con, min_idle, &pending.ids).await?;
+            if !claimed.is_empty() {
+                return Ok(claimed);
+            }
+        }
+        let reply: Option<StreamReadReply> = self.read_cmd().query_async(con).await?;
+        Ok(Self::read_entries(reply))
+    }
+
+    /// Asynchronously acknowledges an entry that was processed.
+    pub async fn ack_async<C>(&self, con: &mut C, id: &str) -> RedisResult<()>
+    where
+        C: crate::aio::ConnectionLike,
+    {
+        self.ack_cmd(id).query_async(con).await
+    }
+
+    /// Asynchronously processes entries with `handler`, see
+    /// [`run`](Self::run).
+    pub async fn run_async<C, U, F>(&mut self, con: &mut C, mut handler: F) -> RedisResult<U>
+    where
+        C: crate::aio::ConnectionLike,
+        F: FnMut(&StreamId) -> RedisResult<ControlFlow<U>>,
+    {
+        loop {
+            match self.run_batch_async(con, &mut handler).await {
+                Ok(None) => {}
+                Ok(Some(value)) => {
+                    self.restart_own_pending();
+                    return Ok(value);
+                }
+                Err(err) => {
+                    self.restart_own_pending();
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    /// Turns the consumer into an endless stream of entries.  Errors are
+    /// yielded as they occur, and the stream keeps reading afterwards.
+    ///
+    /// The entries are not acknowledged by the stream, use
+    /// [`ack_async`](Self::ack_async) on a clone of the consumer once they
+    /// were processed.
+    ///
+    /// The stream spends most of its time in a blocking `XREADGROUP`.  A
+    /// multiplexed connection answers requests in order, so acknowledgements
+    /// sent on a clone of the stream's connection would wait for that read
+    /// to return.  Send them on a separate connection instead.
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use redis::streams::StreamConsumer;
+    /// # async fn run() -> redis::RedisResult<()> {
+    /// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    /// let read_con = client.get_multiplexed_async_connection().await?;
+    /// let mut ack_con = client.get_multiplexed_async_connection().await?;
+    /// let consumer = StreamConsumer::new("orders", "billing", "worker-1");
+    /// let acker = consumer.clone();
+    /// let mut entries = Box::pin(consumer.into_stream(read_con));
+    /// while let Some(entry) = entries.next().await {
+    ///     let entry = entry?;
+    ///     println!("processing {}", entry.id);
+    ///     acker.ack_async(&mut ack_con, &entry.id).await?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn into_stream<C>(self, con: C) -> impl futures_util::Stream<Item = RedisResult<StreamId>>
+    where
+        C: crate::aio::ConnectionLike,
+    {
+        let state = (self, con, std::collections::VecDeque::new());
+        futures_util::stream::unfold(state, |(mut consumer, mut con, mut buffered)| async move {
+            loop {
+                if let Some(entry) = buffered.pop_front() {
+                    return Some((Ok(entry), (consumer, con, buffered)));
+                }
+                match consumer.next_batch_async(&mut con).await {
+                    Ok(batch) => buffered.extend(batch),
+                    Err(err) => {
+                        consumer.restart_own_pending();
+                        return Some((Err(err), (consumer, con, buffered)));
+                    }
+                }
+            }
+        })
+    }
+
+    async fn run_batch_async<C, U, F>(
+        &mut self,
+        con: &mut C,
+        handler: &mut F,
+    ) -> RedisResult<Option<U>>
+    where
+        C: crate::aio::ConnectionLike,
+        F: FnMut(&StreamId) -> RedisResult<ControlFlow<U>>,
+    {
+        for entry in self.next_batch_async(con).await? {
+            let flow = handler(&entry)?;
+            self.ack_async(con, &entry.id).await?;
+            if let ControlFlow::Break(value) = flow {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn claim_async<C>(
+        &self,
+        con: &mut C,
+        min_idle: usize,
+        pending: &[StreamPendingId],
+    ) -> RedisResult<Vec<StreamId>>
+    where
+        C: crate::aio::ConnectionLike,
+    {
+        let Some(cmd) = self.claim_cmd(min_idle, pending) else {
+            return Ok(vec![]);
+        };
+        let claimed: StreamClaimReply = cmd.query_async(con).await?;
+        let (entries, dead_letters) = self.sort_claimed(pending, claimed)?;
+        if let Some(pipe) = dead_letters {
+            pipe.query_async::<_, ()>(con).await?;
+        }
+        Ok(entries)
+    }
+}
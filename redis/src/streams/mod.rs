@@ -6,6 +6,15 @@ use crate::{
 
 use std::io::{Error, ErrorKind};
 
+mod consumer;
+#[cfg(feature = "aio")]
+mod tail;
+
+pub use consumer::StreamConsumer;
+#[cfg(feature = "aio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "aio")))]
+pub use tail::{StreamTail, StreamTailStart};
+
 // Stream Maxlen Enum
 
 /// Utility enum for passing `MAXLEN [= or ~] [COUNT]`
@@ -208,6 +217,179 @@ impl ToRedisArgs for StreamReadOptions {
     }
 }
 
+/// Utility enum for the trimming mode of `MAXLEN` and `MINID`.
+#[derive(PartialEq, Eq, Clone, Debug, Copy)]
+pub enum StreamTrimmingMode {
+    /// Trim to exactly the threshold (`=`).
+    Exact,
+    /// Allow the server to trim less for efficiency (`~`).
+    Approx,
+}
+
+impl ToRedisArgs for StreamTrimmingMode {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        match self {
+            StreamTrimmingMode::Exact => out.write_arg(b"="),
+            StreamTrimmingMode::Approx => out.write_arg(b"~"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+enum StreamTrimStrategy {
+    MaxLen(usize),
+    MinId(String),
+}
+
+/// Builder options for [`xtrim_options`] and the trimming part of
+/// [`StreamAddOptions`].
+///
+/// [`xtrim_options`]: ../trait.Commands.html#method.xtrim_options
+///
+#[derive(Clone, Debug)]
+pub struct StreamTrimOptions {
+    strategy: StreamTrimStrategy,
+    mode: StreamTrimmingMode,
+    limit: Option<usize>,
+}
+
+impl StreamTrimOptions {
+    /// Trims the stream to at most `max_entries` entries.
+    pub fn maxlen(mode: StreamTrimmingMode, max_entries: usize) -> Self {
+        Self {
+            strategy: StreamTrimStrategy::MaxLen(max_entries),
+            mode,
+            limit: None,
+        }
+    }
+
+    /// Trims all entries with an ID lower than `stream_id`.
+    pub fn minid<ID: Into<String>>(mode: StreamTrimmingMode, stream_id: ID) -> Self {
+        Self {
+            strategy: StreamTrimStrategy::MinId(stream_id.into()),
+            mode,
+            limit: None,
+        }
+    }
+
+    /// Set `LIMIT <count>` cmd arg, the maximum number of entries to evict.
+    /// The server only accepts this together with approximate trimming.
+    pub fn limit(mut self, count: usize) -> Self {
+        self.limit = Some(count);
+        self
+    }
+}
+
+impl ToRedisArgs for StreamTrimOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        match self.strategy {
+            StreamTrimStrategy::MaxLen(ref count) => {
+                out.write_arg(b"MAXLEN");
+                self.mode.write_redis_args(out);
+                out.write_arg(format!("{count}").as_bytes());
+            }
+            StreamTrimStrategy::MinId(ref id) => {
+                out.write_arg(b"MINID");
+                self.mode.write_redis_args(out);
+                out.write_arg(id.as_bytes());
+            }
+        }
+        if let Some(ref count) = self.limit {
+            out.write_arg(b"LIMIT");
+            out.write_arg(format!("{count}").as_bytes());
+        }
+    }
+}
+
+/// Builder options for [`xadd_options`] command.
+///
+/// [`xadd_options`]: ../trait.Commands.html#method.xadd_options
+///
+#[derive(Default, Clone, Debug)]
+pub struct StreamAddOptions {
+    /// Set the `NOMKSTREAM` cmd arg.
+    nomkstream: bool,
+    /// Set the `MAXLEN` or `MINID` trimming cmd args.
+    trim: Option<StreamTrimOptions>,
+}
+
+impl StreamAddOptions {
+    /// Set `NOMKSTREAM` cmd arg, so that no stream is created if the key
+    /// doesn't exist yet.
+    pub fn nomkstream(mut self) -> Self {
+        self.nomkstream = true;
+        self
+    }
+
+    /// Trims the stream while adding the message.
+    pub fn trim(mut self, trim: StreamTrimOptions) -> Self {
+        self.trim = Some(trim);
+        self
+    }
+}
+
+impl ToRedisArgs for StreamAddOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if self.nomkstream {
+            out.write_arg(b"NOMKSTREAM");
+        }
+        if let Some(ref trim) = self.trim {
+            trim.write_redis_args(out);
+        }
+    }
+}
+
+/// Builder options for [`xautoclaim_options`] command.
+///
+/// [`xautoclaim_options`]: ../trait.Commands.html#method.xautoclaim_options
+///
+#[derive(Default, Debug)]
+pub struct StreamAutoClaimOptions {
+    /// Set `COUNT <count>` cmd arg.
+    count: Option<usize>,
+    /// Set `JUSTID` cmd arg.
+    justid: bool,
+}
+
+impl StreamAutoClaimOptions {
+    /// Set `COUNT <count>` cmd arg, the upper limit of entries to claim.
+    pub fn count(mut self, n: usize) -> Self {
+        self.count = Some(n);
+        self
+    }
+
+    /// Set `JUSTID` cmd arg to true. The claimed entries of the reply
+    /// then only contain their IDs.
+    pub fn with_justid(mut self) -> Self {
+        self.justid = true;
+        self
+    }
+}
+
+impl ToRedisArgs for StreamAutoClaimOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if let Some(ref n) = self.count {
+            out.write_arg(b"COUNT");
+            out.write_arg(format!("{n}").as_bytes());
+        }
+        if self.justid {
+            out.write_arg(b"JUSTID");
+        }
+    }
+}
+
 /// Reply type used with [`xread`] or [`xread_options`] commands.
 ///
 /// [`xread`]: ../trait.Commands.html#method.xread
@@ -248,6 +430,23 @@ pub struct StreamClaimReply {
     pub ids: Vec<StreamId>,
 }
 
+/// Reply type used with [`xautoclaim`] and [`xautoclaim_options`] commands.
+///
+/// [`xautoclaim`]: ../trait.Commands.html#method.xautoclaim
+/// [`xautoclaim_options`]: ../trait.Commands.html#method.xautoclaim_options
+///
+#[derive(Default, Debug, Clone)]
+pub struct StreamAutoClaimReply {
+    /// The ID to pass as `start` to the next call, `0-0` once the
+    /// whole pending entries list was scanned.
+    pub next_stream_id: String,
+    /// The claimed messages. With `JUSTID` the messages only carry their ID.
+    pub claimed: Vec<StreamId>,
+    /// IDs of pending messages that no longer exist in the stream and
+    /// were removed from the pending entries list (Redis 7.0+).
+    pub deleted_ids: Vec<String>,
+}
+
 /// Reply type used with [`xpending`] command.
 ///
 /// Data returned here were fetched from the stream without
@@ -334,6 +533,94 @@ pub struct StreamInfoStreamReply {
     pub last_entry: StreamId,
 }
 
+/// Reply type used with [`xinfo_stream_full`] and [`xinfo_stream_full_count`]
+/// commands, containing the entries, consumer groups and pending entries
+/// lists of the stream stored at the specified key.
+///
+/// Fields added in later server versions are `None` on older servers.
+///
+/// [`xinfo_stream_full`]: ../trait.Commands.html#method.xinfo_stream_full
+/// [`xinfo_stream_full_count`]: ../trait.Commands.html#method.xinfo_stream_full_count
+///
+#[derive(Default, Debug, Clone)]
+pub struct StreamInfoStreamFullReply {
+    /// Number of elements of the stream.
+    pub length: usize,
+    /// Number of keys in the radix tree representing the stream.
+    pub radix_tree_keys: usize,
+    /// Number of nodes in the radix tree representing the stream.
+    pub radix_tree_nodes: usize,
+    /// The last generated ID that may not be the same as the last
+    /// entry ID in case some entry was deleted.
+    pub last_generated_id: String,
+    /// The maximal entry ID that was deleted from the stream.
+    pub max_deleted_entry_id: Option<String>,
+    /// The count of all entries added to the stream during its lifetime.
+    pub entries_added: Option<usize>,
+    /// The ID of the first entry recorded in the stream.
+    pub recorded_first_entry_id: Option<String>,
+    /// The entries of the stream, in ascending order.
+    pub entries: Vec<StreamId>,
+    /// The consumer groups of the stream.
+    pub groups: Vec<StreamInfoGroupFull>,
+}
+
+/// A group parsed from [`xinfo_stream_full`] command.
+///
+/// [`xinfo_stream_full`]: ../trait.Commands.html#method.xinfo_stream_full
+///
+#[derive(Default, Debug, Clone)]
+pub struct StreamInfoGroupFull {
+    /// The group name.
+    pub name: String,
+    /// Last ID delivered to this group.
+    pub last_delivered_id: String,
+    /// The logical read counter of the last entry delivered to the group.
+    pub entries_read: Option<usize>,
+    /// The number of entries that are still waiting to be delivered to
+    /// the group, if the server can determine it.
+    pub lag: Option<usize>,
+    /// Number of pending messages (delivered but not yet acknowledged) in the group.
+    pub pel_count: usize,
+    /// The pending entries list of the group.
+    pub pending: Vec<StreamInfoPendingEntry>,
+    /// The consumers of the group.
+    pub consumers: Vec<StreamInfoConsumerFull>,
+}
+
+/// A consumer parsed from [`xinfo_stream_full`] command.
+///
+/// [`xinfo_stream_full`]: ../trait.Commands.html#method.xinfo_stream_full
+///
+#[derive(Default, Debug, Clone)]
+pub struct StreamInfoConsumerFull {
+    /// Name of the consumer.
+    pub name: String,
+    /// UNIX timestamp in milliseconds of the last attempted interaction.
+    pub seen_time: usize,
+    /// UNIX timestamp in milliseconds of the last successful interaction.
+    pub active_time: Option<usize>,
+    /// Number of pending messages for this specific consumer.
+    pub pel_count: usize,
+    /// The pending entries list of the consumer.
+    pub pending: Vec<StreamInfoPendingEntry>,
+}
+
+/// A pending message parsed from [`xinfo_stream_full`] command.
+///
+/// [`xinfo_stream_full`]: ../trait.Commands.html#method.xinfo_stream_full
+#[derive(Default, Debug, Clone)]
+pub struct StreamInfoPendingEntry {
+    /// The ID of the message.
+    pub id: String,
+    /// The name of the consumer that owns the message.
+    pub consumer: String,
+    /// UNIX timestamp in milliseconds of the last delivery.
+    pub delivery_time: usize,
+    /// The number of times this message was delivered.
+    pub delivery_count: usize,
+}
+
 /// Reply type used with [`xinfo_consumer`] command, an array of every
 /// consumer in a specific consumer group.
 ///
@@ -429,7 +716,7 @@ impl StreamId {
     fn from_bulk_value(v: &Value) -> RedisResult<Self> {
         let mut stream_id = StreamId::default();
         if let Value::Bulk(ref values) = *v {
-            if let Some(v) = values.get(0) {
+            if let Some(v) = values.first() {
                 stream_id.id = from_redis_value(v)?;
             }
             if let Some(v) = values.get(1) {
@@ -451,7 +738,7 @@ impl StreamId {
 
     /// Does the message contain a particular field?
     pub fn contains_key(&self, key: &&str) -> bool {
-        self.map.get(*key).is_some()
+        self.map.contains_key(*key)
     }
 
     /// Returns how many field/value pairs exist in this message.
@@ -507,6 +794,39 @@ impl FromRedisValue for StreamClaimReply {
     }
 }
 
+impl FromRedisValue for StreamAutoClaimReply {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let values = match v {
+            Value::Bulk(values) if values.len() >= 2 => values,
+            _ => fail!((
+                crate::types::ErrorKind::TypeError,
+                "Response type not autoclaim compatible"
+            )),
+        };
+        let mut reply = StreamAutoClaimReply {
+            next_stream_id: from_redis_value(&values[0])?,
+            ..Default::default()
+        };
+        if let Value::Bulk(claimed) = &values[1] {
+            for entry in claimed {
+                match entry {
+                    // Redis 6.2 reports deleted messages as nil entries.
+                    Value::Nil => {}
+                    Value::Bulk(_) => reply.claimed.push(StreamId::from_bulk_value(entry)?),
+                    _ => reply.claimed.push(StreamId {
+                        id: from_redis_value(entry)?,
+                        ..Default::default()
+                    }),
+                }
+            }
+        }
+        if let Some(v) = values.get(2) {
+            reply.deleted_ids = from_redis_value(v)?;
+        }
+        Ok(reply)
+    }
+}
+
 type SPRInner = (
     usize,
     Option<String>,
@@ -623,6 +943,114 @@ impl FromRedisValue for StreamInfoStreamReply {
     }
 }
 
+impl FromRedisValue for StreamInfoStreamFullReply {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let map: HashMap<String, Value> = from_redis_value(v)?;
+        let mut reply = StreamInfoStreamFullReply::default();
+        if let Some(v) = &map.get("length") {
+            reply.length = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("radix-tree-keys") {
+            reply.radix_tree_keys = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("radix-tree-nodes") {
+            reply.radix_tree_nodes = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("last-generated-id") {
+            reply.last_generated_id = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("max-deleted-entry-id") {
+            reply.max_deleted_entry_id = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("entries-added") {
+            reply.entries_added = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("recorded-first-entry-id") {
+            reply.recorded_first_entry_id = from_redis_value(v)?;
+        }
+        if let Some(Value::Bulk(entries)) = &map.get("entries") {
+            for entry in entries {
+                reply.entries.push(StreamId::from_bulk_value(entry)?);
+            }
+        }
+        if let Some(v) = &map.get("groups") {
+            reply.groups = from_redis_value(v)?;
+        }
+        Ok(reply)
+    }
+}
+
+impl FromRedisValue for StreamInfoGroupFull {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let map: HashMap<String, Value> = from_redis_value(v)?;
+        let mut g = StreamInfoGroupFull::default();
+        if let Some(v) = &map.get("name") {
+            g.name = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("last-delivered-id") {
+            g.last_delivered_id = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("entries-read") {
+            g.entries_read = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("lag") {
+            g.lag = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("pel-count") {
+            g.pel_count = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("pending") {
+            let pending: Vec<Value> = from_redis_value(v)?;
+            for entry in &pending {
+                let (id, consumer, delivery_time, delivery_count) = from_redis_value(entry)?;
+                g.pending.push(StreamInfoPendingEntry {
+                    id,
+                    consumer,
+                    delivery_time,
+                    delivery_count,
+                });
+            }
+        }
+        if let Some(v) = &map.get("consumers") {
+            g.consumers = from_redis_value(v)?;
+        }
+        Ok(g)
+    }
+}
+
+impl FromRedisValue for StreamInfoConsumerFull {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let map: HashMap<String, Value> = from_redis_value(v)?;
+        let mut c = StreamInfoConsumerFull::default();
+        if let Some(v) = &map.get("name") {
+            c.name = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("seen-time") {
+            c.seen_time = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("active-time") {
+            c.active_time = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("pel-count") {
+            c.pel_count = from_redis_value(v)?;
+        }
+        if let Some(v) = &map.get("pending") {
+            // The consumer's own entries don't repeat the consumer name.
+            let pending: Vec<Value> = from_redis_value(v)?;
+            for entry in &pending {
+                let (id, delivery_time, delivery_count) = from_redis_value(entry)?;
+                c.pending.push(StreamInfoPendingEntry {
+                    id,
+                    consumer: c.name.clone(),
+                    delivery_time,
+                    delivery_count,
+                });
+            }
+        }
+        Ok(c)
+    }
+}
+
 impl FromRedisValue for StreamInfoConsumersReply {
     fn from_redis_value(v: &Value) -> RedisResult<Self> {
         let consumers: Vec<HashMap<String, Value>> = from_redis_value(v)?;
@@ -0,0 +1,157 @@
+use super::{StreamId, StreamRangeReply, StreamReadOptions, StreamReadReply};
+use crate::aio::ConnectionLike;
+use crate::cmd::Cmd;
+use crate::types::RedisResult;
+
+use futures_util::Stream;
+use std::collections::VecDeque;
+
+/// Where a [`StreamTail`] starts reading a stream.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum StreamTailStart {
+    /// Only entries added after the tail started reading (`$`).
+    Latest,
+    /// All entries of the stream (`0`).
+    Beginning,
+    /// The entries after the given ID.
+    After(String),
+}
+
+/// Tails one or more streams with `XREAD BLOCK`, yielding entries as they
+/// are added.
+///
+/// The tail remembers the last ID it yielded for each key and continues
+/// from there on every read.  Errors are yielded without ending the
+/// stream, so when it runs over a
+/// [`ConnectionManager`](crate::aio::ConnectionManager) it resumes from the
+/// last ID once the manager reconnected.
+///
+/// Example:
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use redis::streams::{StreamTail, StreamTailStart};
+/// # async fn run() -> redis::RedisResult<()> {
+/// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+/// let con = client.get_multiplexed_async_connection().await?;
+/// let tail = StreamTail::new("events", StreamTailStart::Beginning);
+/// let mut entries = Box::pin(tail.into_stream(con));
+/// while let Some(entry) = entries.next().await {
+///     let (key, entry) = entry?;
+///     println!("{key}: {}", entry.id);
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct StreamTail {
+    keys: Vec<String>,
+    // `None` until the last ID of a stream tailed from `Latest` is known
+    last_ids: Vec<Option<String>>,
+    count: Option<usize>,
+    block: usize,
+}
+
+impl StreamTail {
+    /// Creates a tail of the stream stored at `key`.
+    pub fn new(key: &str, start: StreamTailStart) -> StreamTail {
+        StreamTail {
+            keys: vec![],
+            last_ids: vec![],
+            count: None,
+            block: 5000,
+        }
+        .key(key, start)
+    }
+
+    /// Adds another stream to the tail.
+    pub fn key(mut self, key: &str, start: StreamTailStart) -> StreamTail {
+        self.keys.push(key.to_string());
+        self.last_ids.push(match start {
+            StreamTailStart::Latest => None,
+            StreamTailStart::Beginning => Some("0".to_string()),
+            StreamTailStart::After(id) => Some(id),
+        });
+        self
+    }
+
+    /// Sets the maximum number of entries read per stream and call.
+    pub fn count(mut self, n: usize) -> StreamTail {
+        self.count = Some(n);
+        self
+    }
+
+    /// Sets how long, in milliseconds, a read blocks waiting for new
+    /// entries before it is sent again.  Defaults to 5000.
+    pub fn block(mut self, ms: usize) -> StreamTail {
+        self.block = ms;
+        self
+    }
+
+    /// Turns the tail into an endless stream of keys and their entries.
+    pub fn into_stream<C>(self, con: C) -> impl Stream<Item = RedisResult<(String, StreamId)>>
+    where
+        C: ConnectionLike,
+    {
+        let state = (self, con, VecDeque::new());
+        futures_util::stream::unfold(state, |(mut tail, mut con, mut buffered)| async move {
+            loop {
+                if let Some(entry) = buffered.pop_front() {
+                    return Some((Ok(entry), (tail, con, buffered)));
+                }
+                match tail.next_batch(&mut con).await {
+                    Ok(batch) => buffered.extend(batch),
+                    Err(err) => return Some((Err(err), (tail, con, buffered))),
+                }
+            }
+        })
+    }
+
+    async fn next_batch<C>(&mut self, con: &mut C) -> RedisResult<Vec<(String, StreamId)>>
+    where
+        C: ConnectionLike,
+    {
+        self.resolve_latest(con).await?;
+        let mut opts = StreamReadOptions::default().block(self.block);
+        if let Some(n) = self.count {
+            opts = opts.count(n);
+        }
+        let ids: Vec<&str> = self.last_ids.iter().flatten().map(String::as_str).collect();
+        let reply: Option<StreamReadReply> = Cmd::xread_options(&self.keys, &ids, &opts)
+            .query_async(con)
+            .await?;
+
+        let mut batch = vec![];
+        for stream in reply.map(|reply| reply.keys).unwrap_or_default() {
+            let Some(idx) = self.keys.iter().position(|key| *key == stream.key) else {
+                continue;
+            };
+            if let Some(last) = stream.ids.last() {
+                self.last_ids[idx] = Some(last.id.clone());
+            }
+            batch.extend(stream.ids.into_iter().map(|id| (stream.key.clone(), id)));
+        }
+        Ok(batch)
+    }
+
+    // Sending `$` with every read would skip entries added between two
+    // reads, so it is replaced by the last ID of the stream once.
+    async fn resolve_latest<C>(&mut self, con: &mut C) -> RedisResult<()>
+    where
+        C: ConnectionLike,
+    {
+        for (key, last_id) in self.keys.iter().zip(self.last_ids.iter_mut()) {
+            if last_id.is_none() {
+                let last: StreamRangeReply = Cmd::xrevrange_count(key, "+", "-", 1)
+                    .query_async(con)
+                    .await?;
+                *last_id = Some(
+                    last.ids
+                        .into_iter()
+                        .next()
+                        .map_or_else(|| "0".to_string(), |entry| entry.id),
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -9,6 +9,9 @@ use std::io;
 use std::str::{from_utf8, Utf8Error};
 use std::string::FromUtf8Error;
 
+#[cfg(feature = "cluster")]
+use crate::cmd::Cmd;
+
 #[cfg(feature = "ahash")]
 pub(crate) use ahash::{AHashMap as HashMap, AHashSet as HashSet};
 #[cfg(not(feature = "ahash"))]
@@ -67,6 +70,113 @@ pub enum ExistenceCheck {
     XX,
 }
 
+/// Helper enum that is used to define the condition of expire commands
+#[derive(PartialEq, Eq, Clone, Debug, Copy)]
+pub enum ExpireOption {
+    /// Set the expiry unconditionally.
+    NONE,
+    /// NX -- Only set the expiry if there is none yet.
+    NX,
+    /// XX -- Only set the expiry if there already is one.
+    XX,
+    /// GT -- Only set the expiry if it is greater than the current one.
+    GT,
+    /// LT -- Only set the expiry if it is less than the current one.
+    LT,
+}
+
+impl ToRedisArgs for ExpireOption {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        match self {
+            ExpireOption::NONE => {}
+            ExpireOption::NX => out.write_arg(b"NX"),
+            ExpireOption::XX => out.write_arg(b"XX"),
+            ExpireOption::GT => out.write_arg(b"GT"),
+            ExpireOption::LT => out.write_arg(b"LT"),
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// The expiry of a key or hash field, as returned by the `TTL`, `EXPIRETIME`,
+/// `HTTL` and `HEXPIRETIME` families of commands.  Whether the value is the
+/// remaining time or a Unix timestamp, in seconds or milliseconds, depends on
+/// the command.
+#[derive(PartialEq, Eq, Clone, Debug, Copy)]
+pub enum ExpiryTime {
+    /// The key or field does not exist.
+    Missing,
+    /// The key or field exists but has no expiry.
+    NoExpiry,
+    /// The key or field expires.
+    Expires(u64),
+}
+
+impl FromRedisValue for ExpiryTime {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        match *v {
+            Value::Int(-2) => Ok(ExpiryTime::Missing),
+            Value::Int(-1) => Ok(ExpiryTime::NoExpiry),
+            Value::Int(n) if n >= 0 => Ok(ExpiryTime::Expires(n as u64)),
+            _ => invalid_type_error!(v, "Response type not expiry time compatible."),
+        }
+    }
+}
+
+/// The outcome of setting the expiry of a hash field with `HEXPIRE` and
+/// related commands.
+#[derive(PartialEq, Eq, Clone, Debug, Copy)]
+pub enum HashFieldExpireStatus {
+    /// The field does not exist.
+    NoSuchField,
+    /// The condition of the `ExpireOption` was not met.
+    ConditionNotMet,
+    /// The expiry was set.
+    Set,
+    /// The field was deleted right away, because the expiry is in the past.
+    Deleted,
+}
+
+impl FromRedisValue for HashFieldExpireStatus {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        match *v {
+            Value::Int(-2) => Ok(HashFieldExpireStatus::NoSuchField),
+            Value::Int(0) => Ok(HashFieldExpireStatus::ConditionNotMet),
+            Value::Int(1) => Ok(HashFieldExpireStatus::Set),
+            Value::Int(2) => Ok(HashFieldExpireStatus::Deleted),
+            _ => invalid_type_error!(v, "Response type not hash field expire status compatible."),
+        }
+    }
+}
+
+/// The outcome of removing the expiry of a hash field with `HPERSIST`.
+#[derive(PartialEq, Eq, Clone, Debug, Copy)]
+pub enum HashFieldPersistStatus {
+    /// The field does not exist.
+    NoSuchField,
+    /// The field has no expiry.
+    NoExpiry,
+    /// The expiry was removed.
+    Persisted,
+}
+
+impl FromRedisValue for HashFieldPersistStatus {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        match *v {
+            Value::Int(-2) => Ok(HashFieldPersistStatus::NoSuchField),
+            Value::Int(-1) => Ok(HashFieldPersistStatus::NoExpiry),
+            Value::Int(1) => Ok(HashFieldPersistStatus::Persisted),
+            _ => invalid_type_error!(v, "Response type not hash field persist status compatible."),
+        }
+    }
+}
+
 /// Helper enum that is used in some situations to describe
 /// the behavior of arguments in a numeric context.
 #[derive(PartialEq, Eq, Clone, Debug, Copy)]
@@ -129,6 +239,32 @@ pub enum ErrorKind {
     EmptySentinelList,
     /// Attempted to kill a script/function while they werent' executing
     NotBusy,
+    /// The server does not support the RESP3 protocol requested by the client
+    RESP3NotSupported,
+    /// A reply exceeded one of the configured parser limits
+    ParserLimitExceeded,
+    /// The user lacks the permissions for the command or keys (`NOPERM`)
+    NoPermission,
+    /// The username or password sent to `AUTH` or `HELLO` is invalid (`WRONGPASS`)
+    WrongPassword,
+    /// The command requires an authenticated connection (`NOAUTH`)
+    NoAuth,
+    /// The command was run against a key holding the wrong kind of value (`WRONGTYPE`)
+    WrongType,
+    /// The server reached its `maxmemory` limit and can't evict keys (`OOM`)
+    OutOfMemory,
+    /// Not enough replicas acknowledged a write (`NOREPLICAS`)
+    NoReplicas,
+    /// The target key of `RESTORE`, `COPY` or `RENAME` like commands already exists (`BUSYKEY`)
+    BusyKey,
+    /// The server is busy running a script or function (`BUSY`)
+    Busy,
+    /// The stream consumer group does not exist (`NOGROUP`)
+    NoGroup,
+    /// The stream consumer group already exists (`BUSYGROUP`)
+    BusyGroup,
+    /// A blocking command was unblocked by `CLIENT UNBLOCK` or a failover (`UNBLOCKED`)
+    Unblocked,
 
     #[cfg(feature = "json")]
     /// Error Serializing a struct to JSON form
@@ -136,7 +272,11 @@ pub enum ErrorKind {
 }
 
 /// Internal low-level redis value enum.
-#[derive(PartialEq, Eq, Clone)]
+///
+/// New protocol versions can add reply types, so matches on this enum need
+/// a wildcard arm.
+#[derive(PartialEq, Clone)]
+#[non_exhaustive]
 pub enum Value {
     /// A nil response from the server.
     Nil,
@@ -154,20 +294,214 @@ pub enum Value {
     Status(String),
     /// A status response which represents the string "OK".
     Okay,
+    /// A RESP3 map response.  Unlike a `Bulk` of alternating keys and
+    /// values the pairs are kept together.
+    Map(Vec<(Value, Value)>),
+    /// A RESP3 attribute response.  Attributes carry auxiliary data about
+    /// the reply that follows them.
+    Attribute {
+        /// The reply the attributes were attached to.
+        data: Box<Value>,
+        /// The attribute key/value pairs.
+        attributes: Vec<(Value, Value)>,
+    },
+    /// A RESP3 set response.
+    Set(Vec<Value>),
+    /// A RESP3 double response.
+    Double(f64),
+    /// A RESP3 boolean response.
+    Boolean(bool),
+    /// A RESP3 verbatim string response.
+    VerbatimString {
+        /// The format of the text.
+        format: VerbatimFormat,
+        /// The actual text.
+        text: String,
+    },
+    /// A RESP3 big number response, holding the decimal digits as sent by the
+    /// server.  Enable the `num-bigint` feature to convert it into a `BigInt`.
+    BigNumber(String),
+    /// A RESP3 push response.  These are out of band messages sent by the
+    /// server, such as pubsub messages or client tracking invalidations.
+    Push {
+        /// The kind of the push message.
+        kind: PushKind,
+        /// The remaining data of the push message.
+        data: Vec<Value>,
+    },
+}
+
+/// The format of a RESP3 verbatim string.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum VerbatimFormat {
+    /// A format which is not known to the library.
+    Unknown(String),
+    /// Markdown text (`mkd`).
+    Markdown,
+    /// Plain text (`txt`).
+    Text,
+}
+
+impl fmt::Display for VerbatimFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerbatimFormat::Markdown => f.write_str("mkd"),
+            VerbatimFormat::Text => f.write_str("txt"),
+            VerbatimFormat::Unknown(format) => f.write_str(format),
+        }
+    }
+}
+
+/// The kind of a RESP3 push message.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum PushKind {
+    /// A push kind which is not known to the library.
+    Other(String),
+    /// `invalidate`, sent when a key tracked by client side caching changed.
+    Invalidate,
+    /// `message`, a pubsub message.
+    Message,
+    /// `pmessage`, a pubsub message received through a pattern subscription.
+    PMessage,
+    /// `smessage`, a sharded pubsub message.
+    SMessage,
+    /// `unsubscribe`, the reply to an `UNSUBSCRIBE` command.
+    Unsubscribe,
+    /// `punsubscribe`, the reply to a `PUNSUBSCRIBE` command.
+    PUnsubscribe,
+    /// `sunsubscribe`, the reply to a `SUNSUBSCRIBE` command.
+    SUnsubscribe,
+    /// `subscribe`, the reply to a `SUBSCRIBE` command.
+    Subscribe,
+    /// `psubscribe`, the reply to a `PSUBSCRIBE` command.
+    PSubscribe,
+    /// `ssubscribe`, the reply to a `SSUBSCRIBE` command.
+    SSubscribe,
+}
+
+impl PushKind {
+    /// Returns true if push messages of this kind are also the reply to a
+    /// command sent by the client.
+    pub fn has_reply(&self) -> bool {
+        matches!(
+            self,
+            PushKind::Subscribe
+                | PushKind::PSubscribe
+                | PushKind::SSubscribe
+                | PushKind::Unsubscribe
+                | PushKind::PUnsubscribe
+                | PushKind::SUnsubscribe
+        )
+    }
+}
+
+/// A RESP3 push message which was received outside of the regular
+/// request/response flow.
+#[derive(PartialEq, Clone, Debug)]
+pub struct PushInfo {
+    /// The kind of the push message.
+    pub kind: PushKind,
+    /// The payload of the push message, without the leading kind.
+    pub data: Vec<Value>,
+}
+
+impl From<&str> for PushKind {
+    fn from(kind: &str) -> PushKind {
+        match kind {
+            "invalidate" => PushKind::Invalidate,
+            "message" => PushKind::Message,
+            "pmessage" => PushKind::PMessage,
+            "smessage" => PushKind::SMessage,
+            "unsubscribe" => PushKind::Unsubscribe,
+            "punsubscribe" => PushKind::PUnsubscribe,
+            "sunsubscribe" => PushKind::SUnsubscribe,
+            "subscribe" => PushKind::Subscribe,
+            "psubscribe" => PushKind::PSubscribe,
+            "ssubscribe" => PushKind::SSubscribe,
+            other => PushKind::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for PushKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PushKind::Other(kind) => kind,
+            PushKind::Invalidate => "invalidate",
+            PushKind::Message => "message",
+            PushKind::PMessage => "pmessage",
+            PushKind::SMessage => "smessage",
+            PushKind::Unsubscribe => "unsubscribe",
+            PushKind::PUnsubscribe => "punsubscribe",
+            PushKind::SUnsubscribe => "sunsubscribe",
+            PushKind::Subscribe => "subscribe",
+            PushKind::PSubscribe => "psubscribe",
+            PushKind::SSubscribe => "ssubscribe",
+        })
+    }
+}
+
+/// The version of the redis protocol a connection speaks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProtocolVersion {
+    /// RESP2, the default protocol of all redis versions.
+    #[default]
+    RESP2,
+    /// RESP3, available since redis 6.  Connections send `HELLO 3` during setup.
+    RESP3,
+}
+
+/// Limits enforced while parsing replies.  They protect the client
+/// against broken or hostile servers which announce huge or deeply nested
+/// values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParserLimits {
+    /// The maximum length of a bulk string in bytes.  Defaults to 512 MiB,
+    /// the largest string redis itself accepts.
+    pub max_bulk_length: usize,
+    /// The maximum number of elements of an aggregate reply.  For maps this
+    /// is the number of key/value pairs.  Defaults to 2^32 - 1, the largest
+    /// number of elements a redis list, set or hash can hold.
+    pub max_array_length: usize,
+    /// The maximum nesting depth of aggregate replies.  Defaults to 100.
+    pub max_nesting_depth: usize,
+}
+
+impl Default for ParserLimits {
+    fn default() -> Self {
+        ParserLimits {
+            max_bulk_length: 512 * 1024 * 1024,
+            max_array_length: u32::MAX as usize,
+            max_nesting_depth: 100,
+        }
+    }
 }
 
-pub struct MapIter<'a>(std::slice::Iter<'a, Value>);
+pub struct MapIter<'a>(MapIterInner<'a>);
+
+enum MapIterInner<'a> {
+    Flat(std::slice::Iter<'a, Value>),
+    Pairs(std::slice::Iter<'a, (Value, Value)>),
+}
 
 impl<'a> Iterator for MapIter<'a> {
     type Item = (&'a Value, &'a Value);
 
     fn next(&mut self) -> Option<Self::Item> {
-        Some((self.0.next()?, self.0.next()?))
+        match self.0 {
+            MapIterInner::Flat(ref mut iter) => Some((iter.next()?, iter.next()?)),
+            MapIterInner::Pairs(ref mut iter) => iter.next().map(|(k, v)| (k, v)),
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (low, high) = self.0.size_hint();
-        (low / 2, high.map(|h| h / 2))
+        match self.0 {
+            MapIterInner::Flat(ref iter) => {
+                let (low, high) = iter.size_hint();
+                (low / 2, high.map(|h| h / 2))
+            }
+            MapIterInner::Pairs(ref iter) => iter.size_hint(),
+        }
     }
 }
 
@@ -211,6 +545,7 @@ impl Value {
     pub fn as_sequence(&self) -> Option<&[Value]> {
         match self {
             Value::Bulk(items) => Some(&items[..]),
+            Value::Set(items) => Some(&items[..]),
             Value::Nil => Some(&[]),
             _ => None,
         }
@@ -219,10 +554,39 @@ impl Value {
     /// Returns an iterator of `(&Value, &Value)` if `self` is compatible with a map type
     pub fn as_map_iter(&self) -> Option<MapIter<'_>> {
         match self {
-            Value::Bulk(items) => Some(MapIter(items.iter())),
+            Value::Bulk(items) => Some(MapIter(MapIterInner::Flat(items.iter()))),
+            Value::Map(items) => Some(MapIter(MapIterInner::Pairs(items.iter()))),
             _ => None,
         }
     }
+
+    /// Returns the items of `self` if it is compatible with a sequence type,
+    /// otherwise gives `self` back.
+    fn into_sequence(self) -> Result<Vec<Value>, Value> {
+        match self {
+            Value::Bulk(items) => Ok(items),
+            Value::Set(items) => Ok(items),
+            Value::Nil => Ok(vec![]),
+            _ => Err(self),
+        }
+    }
+
+    /// Returns the key/value pairs of `self` if it is compatible with a map
+    /// type, otherwise gives `self` back.
+    fn into_map_pairs(self) -> Result<Vec<(Value, Value)>, Value> {
+        match self {
+            Value::Bulk(items) => {
+                let mut pairs = Vec::with_capacity(items.len() / 2);
+                let mut it = items.into_iter();
+                while let (Some(k), Some(v)) = (it.next(), it.next()) {
+                    pairs.push((k, v));
+                }
+                Ok(pairs)
+            }
+            Value::Map(items) => Ok(items),
+            _ => Err(self),
+        }
+    }
 }
 
 impl fmt::Debug for Value {
@@ -248,6 +612,31 @@ impl fmt::Debug for Value {
             }
             Value::Okay => write!(fmt, "ok"),
             Value::Status(ref s) => write!(fmt, "status({s:?})"),
+            Value::Map(ref values) => {
+                write!(fmt, "map(")?;
+                let mut is_first = true;
+                for (key, val) in values.iter() {
+                    if !is_first {
+                        write!(fmt, ", ")?;
+                    }
+                    write!(fmt, "{key:?}: {val:?}")?;
+                    is_first = false;
+                }
+                write!(fmt, ")")
+            }
+            Value::Attribute {
+                ref data,
+                ref attributes,
+            } => write!(fmt, "attribute({data:?}, {attributes:?})"),
+            Value::Set(ref values) => write!(fmt, "set({values:?})"),
+            Value::Double(val) => write!(fmt, "double({val:?})"),
+            Value::Boolean(val) => write!(fmt, "boolean({val:?})"),
+            Value::VerbatimString {
+                ref format,
+                ref text,
+            } => write!(fmt, "verbatim-string({format}, {text:?})"),
+            Value::BigNumber(ref n) => write!(fmt, "big-number({n})"),
+            Value::Push { ref kind, ref data } => write!(fmt, "push({kind}, {data:?})"),
         }
     }
 }
@@ -257,6 +646,15 @@ impl fmt::Debug for Value {
 /// struct.
 pub struct RedisError {
     repr: ErrorRepr,
+    context: Option<Box<ErrorContext>>,
+}
+
+/// Describes the request that caused an error.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ErrorContext {
+    pub(crate) command: Option<String>,
+    pub(crate) pipeline_index: Option<usize>,
+    pub(crate) node_address: Option<String>,
 }
 
 #[cfg(feature = "json")]
@@ -276,6 +674,58 @@ enum ErrorRepr {
     WithDescriptionAndDetail(ErrorKind, &'static str, String),
     ExtensionError(String, String),
     IoError(io::Error),
+    Conversion(Box<ConversionError>),
+}
+
+/// A step into a nested value.  A list of these describes where in a
+/// response a conversion failed, see [`RedisError::conversion_path`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConversionPathSegment {
+    /// The element at this index of a sequence.
+    Index(usize),
+    /// The value stored under this key of a map.
+    Key(String),
+    /// The element at this position of a tuple.
+    TuplePosition(usize),
+}
+
+impl fmt::Display for ConversionPathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionPathSegment::Index(index) => write!(f, "[{index}]"),
+            ConversionPathSegment::Key(key) => write!(f, "[{key:?}]"),
+            ConversionPathSegment::TuplePosition(position) => write!(f, ".{position}"),
+        }
+    }
+}
+
+/// A type error raised while converting a nested value.
+#[derive(Clone, Debug)]
+struct ConversionError {
+    desc: &'static str,
+    detail: Option<String>,
+    expected: Option<String>,
+    path: Vec<ConversionPathSegment>,
+}
+
+/// Returns the name of `T` without module paths, e.g. `Vec<String>`
+/// instead of `alloc::vec::Vec<alloc::string::String>`.
+fn short_type_name<T: ?Sized>() -> String {
+    let name = std::any::type_name::<T>();
+    let mut rv = String::with_capacity(name.len());
+    let mut token = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            token.push(c);
+        } else {
+            rv.push_str(token.rsplit("::").next().unwrap_or_default());
+            token.clear();
+            rv.push(c);
+        }
+    }
+    rv.push_str(token.rsplit("::").next().unwrap_or_default());
+    rv
 }
 
 impl PartialEq for RedisError {
@@ -289,6 +739,10 @@ impl PartialEq for RedisError {
                 &ErrorRepr::WithDescriptionAndDetail(kind_b, _, _),
             ) => kind_a == kind_b,
             (ErrorRepr::ExtensionError(a, _), ErrorRepr::ExtensionError(b, _)) => *a == *b,
+            (ErrorRepr::Conversion(_), ErrorRepr::Conversion(_)) => true,
+            (ErrorRepr::Conversion(_), _) | (_, ErrorRepr::Conversion(_)) => {
+                self.kind() == other.kind() && self.as_io_error().is_none()
+            }
             _ => false,
         }
     }
@@ -298,6 +752,7 @@ impl From<io::Error> for RedisError {
     fn from(err: io::Error) -> RedisError {
         RedisError {
             repr: ErrorRepr::IoError(err),
+            context: None,
         }
     }
 }
@@ -306,6 +761,7 @@ impl From<Utf8Error> for RedisError {
     fn from(_: Utf8Error) -> RedisError {
         RedisError {
             repr: ErrorRepr::WithDescription(ErrorKind::TypeError, "Invalid UTF-8"),
+            context: None,
         }
     }
 }
@@ -318,6 +774,7 @@ impl From<NulError> for RedisError {
                 "Value contains interior nul terminator",
                 err.to_string(),
             ),
+            context: None,
         }
     }
 }
@@ -331,6 +788,7 @@ impl From<native_tls::Error> for RedisError {
                 "TLS error",
                 err.to_string(),
             ),
+            context: None,
         }
     }
 }
@@ -344,6 +802,7 @@ impl From<rustls::Error> for RedisError {
                 "TLS error",
                 err.to_string(),
             ),
+            context: None,
         }
     }
 }
@@ -357,6 +816,7 @@ impl From<rustls::client::InvalidDnsNameError> for RedisError {
                 "TLS Error",
                 err.to_string(),
             ),
+            context: None,
         }
     }
 }
@@ -365,6 +825,7 @@ impl From<FromUtf8Error> for RedisError {
     fn from(_: FromUtf8Error) -> RedisError {
         RedisError {
             repr: ErrorRepr::WithDescription(ErrorKind::TypeError, "Cannot convert from UTF-8"),
+            context: None,
         }
     }
 }
@@ -373,6 +834,7 @@ impl From<(ErrorKind, &'static str)> for RedisError {
     fn from((kind, desc): (ErrorKind, &'static str)) -> RedisError {
         RedisError {
             repr: ErrorRepr::WithDescription(kind, desc),
+            context: None,
         }
     }
 }
@@ -381,6 +843,7 @@ impl From<(ErrorKind, &'static str, String)> for RedisError {
     fn from((kind, desc, detail): (ErrorKind, &'static str, String)) -> RedisError {
         RedisError {
             repr: ErrorRepr::WithDescriptionAndDetail(kind, desc, detail),
+            context: None,
         }
     }
 }
@@ -393,6 +856,7 @@ impl error::Error for RedisError {
             ErrorRepr::WithDescriptionAndDetail(_, desc, _) => desc,
             ErrorRepr::ExtensionError(_, _) => "extension error",
             ErrorRepr::IoError(ref err) => err.description(),
+            ErrorRepr::Conversion(ref err) => err.desc,
         }
     }
 
@@ -425,7 +889,46 @@ impl fmt::Display for RedisError {
                 detail.fmt(f)
             }
             ErrorRepr::IoError(ref err) => err.fmt(f),
+            ErrorRepr::Conversion(ref err) => {
+                err.desc.fmt(f)?;
+                f.write_str(" - ")?;
+                fmt::Debug::fmt(&ErrorKind::TypeError, f)?;
+                if let Some(ref expected) = err.expected {
+                    write!(f, ": expected {expected}")?;
+                }
+                if !err.path.is_empty() {
+                    f.write_str(" at ")?;
+                    for segment in &err.path {
+                        segment.fmt(f)?;
+                    }
+                }
+                if let Some(ref detail) = err.detail {
+                    f.write_str(": ")?;
+                    detail.fmt(f)?;
+                }
+                Ok(())
+            }
+        }?;
+
+        if let Some(ref context) = self.context {
+            let mut sep = " (";
+            if let Some(ref command) = context.command {
+                write!(f, "{sep}command: {command}")?;
+                sep = ", ";
+            }
+            if let Some(index) = context.pipeline_index {
+                write!(f, "{sep}pipeline index: {index}")?;
+                sep = ", ";
+            }
+            if let Some(ref address) = context.node_address {
+                write!(f, "{sep}node: {address}")?;
+                sep = ", ";
+            }
+            if sep != " (" {
+                f.write_str(")")?;
+            }
         }
+        Ok(())
     }
 }
 
@@ -444,6 +947,7 @@ impl RedisError {
             | ErrorRepr::WithDescriptionAndDetail(kind, _, _) => kind,
             ErrorRepr::ExtensionError(_, _) => ErrorKind::ExtensionError,
             ErrorRepr::IoError(_) => ErrorKind::IoError,
+            ErrorRepr::Conversion(_) => ErrorKind::TypeError,
         }
     }
 
@@ -452,10 +956,154 @@ impl RedisError {
         match self.repr {
             ErrorRepr::WithDescriptionAndDetail(_, _, ref detail)
             | ErrorRepr::ExtensionError(_, ref detail) => Some(detail.as_str()),
+            ErrorRepr::Conversion(ref err) => err.detail.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the path to the nested value that could not be converted,
+    /// starting at the outermost value.
+    ///
+    /// This is only set for type errors raised while converting the
+    /// elements of sequences, maps or tuples.
+    pub fn conversion_path(&self) -> Option<&[ConversionPathSegment]> {
+        match self.repr {
+            ErrorRepr::Conversion(ref err) if !err.path.is_empty() => Some(&err.path),
             _ => None,
         }
     }
 
+    /// Returns the name of the Rust type a failed conversion expected, if
+    /// known.
+    pub fn expected_type(&self) -> Option<&str> {
+        match self.repr {
+            ErrorRepr::Conversion(ref err) => err.expected.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Records that this error was raised while converting the nested
+    /// value at `segment`.
+    ///
+    /// Implementations of [`FromRedisValue`] for container types can use
+    /// this to report which element failed to convert.  Errors which are
+    /// not type errors are returned unchanged.
+    pub fn with_conversion_segment(self, segment: ConversionPathSegment) -> Self {
+        self.map_conversion(|err| err.path.insert(0, segment))
+    }
+
+    /// Records `T` as the type a failed conversion expected, unless a more
+    /// specific type was recorded already.
+    pub(crate) fn with_expected_type<T: ?Sized>(self) -> Self {
+        self.map_conversion(|err| {
+            if err.expected.is_none() {
+                err.expected = Some(short_type_name::<T>());
+            }
+        })
+    }
+
+    fn map_conversion<F: FnOnce(&mut ConversionError)>(self, f: F) -> Self {
+        let mut err = match self.repr {
+            ErrorRepr::Conversion(err) => err,
+            ErrorRepr::WithDescription(ErrorKind::TypeError, desc) => Box::new(ConversionError {
+                desc,
+                detail: None,
+                expected: None,
+                path: vec![],
+            }),
+            ErrorRepr::WithDescriptionAndDetail(ErrorKind::TypeError, desc, detail) => {
+                Box::new(ConversionError {
+                    desc,
+                    detail: Some(detail),
+                    expected: None,
+                    path: vec![],
+                })
+            }
+            repr => {
+                return RedisError {
+                    repr,
+                    context: self.context,
+                }
+            }
+        };
+        f(&mut err);
+        RedisError {
+            repr: ErrorRepr::Conversion(err),
+            context: self.context,
+        }
+    }
+
+    /// Returns the name of the command that failed, such as `GET` or
+    /// `CONFIG SET`, if known.
+    ///
+    /// This is recorded for errors of pipelines and cluster requests.  Only
+    /// the name is kept, never the arguments, so keys, values and passwords
+    /// don't end up in error messages.
+    pub fn command(&self) -> Option<&str> {
+        self.context.as_ref()?.command.as_deref()
+    }
+
+    /// Returns the position of the failed command within its pipeline, if
+    /// known.
+    pub fn pipeline_index(&self) -> Option<usize> {
+        self.context.as_ref()?.pipeline_index
+    }
+
+    /// Returns the address of the node that produced the error, if known.
+    ///
+    /// This is recorded for errors of cluster requests.
+    pub fn node_address(&self) -> Option<&str> {
+        self.context.as_ref()?.node_address.as_deref()
+    }
+
+    pub(crate) fn context_mut(&mut self) -> &mut ErrorContext {
+        self.context.get_or_insert_with(Default::default)
+    }
+
+    /// Records the name of `cmd` unless a command was recorded already.
+    #[cfg(feature = "cluster")]
+    pub(crate) fn with_command(self, cmd: &Cmd) -> Self {
+        self.with_command_name(cmd.command_name())
+    }
+
+    /// Records a command name unless one was recorded already.
+    #[cfg(feature = "cluster")]
+    pub(crate) fn with_command_name(mut self, name: Option<String>) -> Self {
+        if self.command().is_none() {
+            self.context_mut().command = name;
+        }
+        self
+    }
+
+    /// Records the position of the reply in a pipeline unless one was
+    /// recorded already.
+    pub(crate) fn with_pipeline_index(mut self, index: usize) -> Self {
+        let context = self.context_mut();
+        context.pipeline_index = context.pipeline_index.or(Some(index));
+        self
+    }
+
+    /// Forgets the position of the reply, for single commands which are sent
+    /// together with internal ones.
+    #[cfg(feature = "cache-aio")]
+    pub(crate) fn without_pipeline_index(mut self) -> Self {
+        if let Some(context) = &mut self.context {
+            context.pipeline_index = None;
+        }
+        self
+    }
+
+    /// Records the address of the node that replied unless one was recorded
+    /// already.
+    #[cfg(feature = "cluster")]
+    pub(crate) fn with_node_address(mut self, address: &str) -> Self {
+        let context = self.context_mut();
+        if context.node_address.is_none() {
+            context.node_address = Some(address.to_string());
+        }
+        self
+    }
+
     /// Returns the raw error code if available.
     pub fn code(&self) -> Option<&str> {
         match self.kind() {
@@ -471,6 +1119,17 @@ impl RedisError {
             ErrorKind::MasterDown => Some("MASTERDOWN"),
             ErrorKind::ReadOnly => Some("READONLY"),
             ErrorKind::NotBusy => Some("NOTBUSY"),
+            ErrorKind::NoPermission => Some("NOPERM"),
+            ErrorKind::WrongPassword => Some("WRONGPASS"),
+            ErrorKind::NoAuth => Some("NOAUTH"),
+            ErrorKind::WrongType => Some("WRONGTYPE"),
+            ErrorKind::OutOfMemory => Some("OOM"),
+            ErrorKind::NoReplicas => Some("NOREPLICAS"),
+            ErrorKind::BusyKey => Some("BUSYKEY"),
+            ErrorKind::Busy => Some("BUSY"),
+            ErrorKind::NoGroup => Some("NOGROUP"),
+            ErrorKind::BusyGroup => Some("BUSYGROUP"),
+            ErrorKind::Unblocked => Some("UNBLOCKED"),
             _ => match self.repr {
                 ErrorRepr::ExtensionError(ref code, _) => Some(code),
                 _ => None,
@@ -502,6 +1161,19 @@ impl RedisError {
             ErrorKind::NoValidReplicasFoundBySentinel => "no valid replicas found by sentinel",
             ErrorKind::EmptySentinelList => "empty sentinel list",
             ErrorKind::NotBusy => "not busy",
+            ErrorKind::RESP3NotSupported => "resp3 is not supported by server",
+            ErrorKind::ParserLimitExceeded => "parser limit exceeded",
+            ErrorKind::NoPermission => "no permission",
+            ErrorKind::WrongPassword => "wrong password",
+            ErrorKind::NoAuth => "authentication required",
+            ErrorKind::WrongType => "wrong type",
+            ErrorKind::OutOfMemory => "out of memory",
+            ErrorKind::NoReplicas => "not enough replicas",
+            ErrorKind::BusyKey => "target key exists",
+            ErrorKind::Busy => "busy",
+            ErrorKind::NoGroup => "no such consumer group",
+            ErrorKind::BusyGroup => "consumer group exists",
+            ErrorKind::Unblocked => "unblocked",
             #[cfg(feature = "json")]
             ErrorKind::Serialize => "serializing",
         }
@@ -621,15 +1293,26 @@ impl RedisError {
                 e.kind(),
                 format!("{ioerror_description}: {e}"),
             )),
+            ErrorRepr::Conversion(ref err) => ErrorRepr::Conversion(err.clone()),
         };
-        Self { repr }
+        Self {
+            repr,
+            context: self.context.clone(),
+        }
     }
 
+    /// Indicates that the request may succeed if it is sent again, possibly
+    /// after a short wait or to another node.
+    ///
+    /// This is the case for connection failures, cluster redirections and
+    /// transient server states such as `LOADING`, `BUSY` or `NOREPLICAS`.
+    /// Errors caused by the request itself, for instance `WRONGTYPE` or
+    /// `NOPERM`, are not retryable, and neither is `UNBLOCKED`, which means
+    /// that another client deliberately aborted the blocking command.
     // TODO: In addition to/instead of returning a bool here, consider a method
     // that returns an enum with more detail about _how_ to retry errors, e.g.,
     // `RetryImmediately`, `WaitAndRetry`, etc.
-    #[cfg(feature = "cluster")] // Used to avoid "unused method" warning
-    pub(crate) fn is_retryable(&self) -> bool {
+    pub fn is_retryable(&self) -> bool {
         match self.kind() {
             ErrorKind::BusyLoadingError => true,
             ErrorKind::Moved => true,
@@ -641,6 +1324,8 @@ impl RedisError {
             ErrorKind::ClusterDown => true,
             ErrorKind::MasterNameNotFoundBySentinel => true,
             ErrorKind::NoValidReplicasFoundBySentinel => true,
+            ErrorKind::NoReplicas => true,
+            ErrorKind::Busy => true,
 
             ErrorKind::ExtensionError => false,
             ErrorKind::ExecAbortError => false,
@@ -653,6 +1338,17 @@ impl RedisError {
             ErrorKind::ClientError => false,
             ErrorKind::EmptySentinelList => false,
             ErrorKind::NotBusy => false,
+            ErrorKind::Unblocked => false,
+            ErrorKind::RESP3NotSupported => false,
+            ErrorKind::ParserLimitExceeded => false,
+            ErrorKind::NoPermission => false,
+            ErrorKind::WrongPassword => false,
+            ErrorKind::NoAuth => false,
+            ErrorKind::WrongType => false,
+            ErrorKind::OutOfMemory => false,
+            ErrorKind::BusyKey => false,
+            ErrorKind::NoGroup => false,
+            ErrorKind::BusyGroup => false,
             #[cfg(feature = "json")]
             ErrorKind::Serialize => false,
         }
@@ -668,6 +1364,7 @@ pub fn make_extension_error(code: &str, detail: Option<&str>) -> RedisError {
                 None => "Unknown extension error encountered".to_string(),
             },
         ),
+        context: None,
     }
 }
 
@@ -719,6 +1416,15 @@ impl InfoDict {
         InfoDict { map }
     }
 
+    /// Creates an info dictionary from already split key, value pairs.
+    pub(crate) fn from_pairs<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> InfoDict {
+        let map = pairs
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), Value::Status(v.to_string())))
+            .collect();
+        InfoDict { map }
+    }
+
     /// Fetches a value by key and converts it into the given type.
     /// Typical types are `String`, `bool` and integer types.
     pub fn get<T: FromRedisValue>(&self, key: &str) -> Option<T> {
@@ -970,7 +1676,7 @@ impl ToRedisArgs for String {
     }
 }
 
-impl<'a> ToRedisArgs for &'a str {
+impl ToRedisArgs for &str {
     fn write_redis_args<W>(&self, out: &mut W)
     where
         W: ?Sized + RedisWrite,
@@ -992,7 +1698,7 @@ impl<T: ToRedisArgs> ToRedisArgs for Vec<T> {
     }
 }
 
-impl<'a, T: ToRedisArgs> ToRedisArgs for &'a [T] {
+impl<T: ToRedisArgs> ToRedisArgs for &[T] {
     fn write_redis_args<W>(&self, out: &mut W)
     where
         W: ?Sized + RedisWrite,
@@ -1210,11 +1916,29 @@ pub trait FromRedisValue: Sized {
     /// appropriate error is generated.
     fn from_redis_value(v: &Value) -> RedisResult<Self>;
 
+    /// Similar to `from_redis_value` but takes ownership of the value.  This
+    /// allows implementations to move data out of the value instead of
+    /// copying it, which is what `query` uses.  By default this falls back
+    /// to `from_redis_value`.
+    fn from_owned_redis_value(v: Value) -> RedisResult<Self> {
+        Self::from_redis_value(&v)
+    }
+
     /// Similar to `from_redis_value` but constructs a vector of objects
     /// from another vector of values.  This primarily exists internally
     /// to customize the behavior for vectors of tuples.
     fn from_redis_values(items: &[Value]) -> RedisResult<Vec<Self>> {
-        items.iter().map(FromRedisValue::from_redis_value).collect()
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| from_nested_value(item, || ConversionPathSegment::Index(i)))
+            .collect()
+    }
+
+    /// Owned version of `from_redis_values`.  By default this falls back to
+    /// `from_redis_values`.
+    fn from_owned_redis_values(items: Vec<Value>) -> RedisResult<Vec<Self>> {
+        Self::from_redis_values(&items)
     }
 
     /// Convert bytes to a single element vector.
@@ -1223,10 +1947,35 @@ pub trait FromRedisValue: Sized {
             .map(|rv| vec![rv])
             .ok()
     }
+
+    /// Owned version of `from_byte_vec`.
+    fn from_owned_byte_vec(vec: Vec<u8>) -> RedisResult<Vec<Self>> {
+        Self::from_owned_redis_value(Value::Data(vec)).map(|rv| vec![rv])
+    }
 }
 
 macro_rules! from_redis_value_for_num_internal {
-    ($t:ty, $v:expr) => {{
+    ($t:ty, $v:expr) => {
+        from_redis_value_for_num_internal!(int, $t, $v)
+    };
+    (@double int, $t:ty, $val:expr) => {{
+        let val = $val;
+        // `as` would silently truncate or saturate, so integers only accept
+        // doubles they represent exactly.
+        if val.is_finite()
+            && val.fract() == 0.0
+            && val >= <$t>::MIN as f64
+            && val < <$t>::MAX as f64 + 1.0
+        {
+            Some(val as $t)
+        } else {
+            None
+        }
+    }};
+    (@double float, $t:ty, $val:expr) => {
+        Some($val as $t)
+    };
+    ($kind:ident, $t:ty, $v:expr) => {{
         let v = $v;
         match *v {
             Value::Int(val) => Ok(val as $t),
@@ -1238,6 +1987,14 @@ macro_rules! from_redis_value_for_num_internal {
                 Ok(rv) => Ok(rv),
                 Err(_) => invalid_type_error!(v, "Could not convert from string."),
             },
+            Value::Double(val) => match from_redis_value_for_num_internal!(@double $kind, $t, val) {
+                Some(rv) => Ok(rv),
+                None => invalid_type_error!(v, "Could not convert from double."),
+            },
+            Value::BigNumber(ref n) => match n.parse::<$t>() {
+                Ok(rv) => Ok(rv),
+                Err(_) => invalid_type_error!(v, "Could not convert from big number."),
+            },
             _ => invalid_type_error!(v, "Response type not convertible to numeric."),
         }
     }};
@@ -1245,9 +2002,12 @@ macro_rules! from_redis_value_for_num_internal {
 
 macro_rules! from_redis_value_for_num {
     ($t:ty) => {
+        from_redis_value_for_num!(int, $t);
+    };
+    ($kind:ident, $t:ty) => {
         impl FromRedisValue for $t {
             fn from_redis_value(v: &Value) -> RedisResult<$t> {
-                from_redis_value_for_num_internal!($t, v)
+                from_redis_value_for_num_internal!($kind, $t, v)
             }
         }
     };
@@ -1262,6 +2022,10 @@ impl FromRedisValue for u8 {
     fn from_byte_vec(vec: &[u8]) -> Option<Vec<u8>> {
         Some(vec.to_vec())
     }
+
+    fn from_owned_byte_vec(vec: Vec<u8>) -> RedisResult<Vec<u8>> {
+        Ok(vec)
+    }
 }
 
 from_redis_value_for_num!(i8);
@@ -1273,8 +2037,8 @@ from_redis_value_for_num!(i64);
 from_redis_value_for_num!(u64);
 from_redis_value_for_num!(i128);
 from_redis_value_for_num!(u128);
-from_redis_value_for_num!(f32);
-from_redis_value_for_num!(f64);
+from_redis_value_for_num!(float, f32);
+from_redis_value_for_num!(float, f64);
 from_redis_value_for_num!(isize);
 from_redis_value_for_num!(usize);
 
@@ -1302,6 +2066,7 @@ impl FromRedisValue for bool {
                 }
             }
             Value::Okay => Ok(true),
+            Value::Boolean(val) => Ok(val),
             _ => invalid_type_error!(v, "Response type not bool compatible."),
         }
     }
@@ -1313,9 +2078,23 @@ impl FromRedisValue for CString {
             Value::Data(ref bytes) => Ok(CString::new(bytes.as_slice())?),
             Value::Okay => Ok(CString::new("OK")?),
             Value::Status(ref val) => Ok(CString::new(val.as_bytes())?),
+            Value::VerbatimString { ref text, .. } => Ok(CString::new(text.as_bytes())?),
             _ => invalid_type_error!(v, "Response type not CString compatible."),
         }
     }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<CString> {
+        match v {
+            Value::Data(bytes) => Ok(CString::new(bytes)?),
+            Value::Status(val) => Ok(CString::new(val)?),
+            Value::VerbatimString { text, .. } => Ok(CString::new(text)?),
+            _ => Self::from_redis_value(&v),
+        }
+    }
+
+    fn from_owned_redis_values(items: Vec<Value>) -> RedisResult<Vec<CString>> {
+        from_owned_nested_values(items)
+    }
 }
 
 impl FromRedisValue for String {
@@ -1324,9 +2103,25 @@ impl FromRedisValue for String {
             Value::Data(ref bytes) => Ok(from_utf8(bytes)?.to_string()),
             Value::Okay => Ok("OK".to_string()),
             Value::Status(ref val) => Ok(val.to_string()),
+            Value::VerbatimString { ref text, .. } => Ok(text.to_string()),
+            Value::Double(val) => Ok(val.to_string()),
+            Value::BigNumber(ref n) => Ok(n.clone()),
             _ => invalid_type_error!(v, "Response type not string compatible."),
         }
     }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<String> {
+        match v {
+            Value::Data(bytes) => Ok(String::from_utf8(bytes)?),
+            Value::Status(val) => Ok(val),
+            Value::VerbatimString { text, .. } => Ok(text),
+            _ => Self::from_redis_value(&v),
+        }
+    }
+
+    fn from_owned_redis_values(items: Vec<Value>) -> RedisResult<Vec<String>> {
+        from_owned_nested_values(items)
+    }
 }
 
 /// Implement `FromRedisValue` for `$Type` (which should use the generic parameter `$T`).
@@ -1351,11 +2146,42 @@ macro_rules! from_vec_from_redis_value {
                             format!("Conversion to {} failed.", std::any::type_name::<$Type>())
                         ),
                     },
-                    Value::Bulk(items) => FromRedisValue::from_redis_values(items).map($convert),
+                    Value::Bulk(items) | Value::Set(items) => {
+                        FromRedisValue::from_redis_values(items).map($convert)
+                    }
+                    // Maps are treated like the flat key/value bulks RESP2 returns.
+                    Value::Map(items) => {
+                        let items: Vec<Value> = items
+                            .iter()
+                            .flat_map(|(k, v)| [k.clone(), v.clone()])
+                            .collect();
+                        FromRedisValue::from_redis_values(&items).map($convert)
+                    }
                     Value::Nil => Ok($convert(Vec::new())),
                     _ => invalid_type_error!(v, "Response type not vector compatible."),
                 }
             }
+
+            fn from_owned_redis_value(v: Value) -> RedisResult<$Type> {
+                match v {
+                    Value::Data(bytes) => FromRedisValue::from_owned_byte_vec(bytes).map($convert),
+                    Value::Bulk(items) | Value::Set(items) => {
+                        FromRedisValue::from_owned_redis_values(items).map($convert)
+                    }
+                    Value::Map(items) => {
+                        let items: Vec<Value> = items
+                            .into_iter()
+                            .flat_map(|(k, v)| [k, v])
+                            .collect();
+                        FromRedisValue::from_owned_redis_values(items).map($convert)
+                    }
+                    _ => Self::from_redis_value(&v),
+                }
+            }
+
+            fn from_owned_redis_values(items: Vec<Value>) -> RedisResult<Vec<$Type>> {
+                from_owned_nested_values(items)
+            }
         }
     };
 }
@@ -1375,7 +2201,19 @@ impl<K: FromRedisValue + Eq + Hash, V: FromRedisValue, S: BuildHasher + Default>
                 .ok_or_else(|| {
                     invalid_type_error_inner!(v, "Response type not hashmap compatible")
                 })?
-                .map(|(k, v)| Ok((from_redis_value(k)?, from_redis_value(v)?)))
+                .map(|(k, v)| from_nested_pair(k, v))
+                .collect(),
+        }
+    }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<Self> {
+        match v {
+            Value::Nil => Self::from_redis_value(&v),
+            _ => v
+                .into_map_pairs()
+                .map_err(|v| invalid_type_error_inner!(v, "Response type not hashmap compatible"))?
+                .into_iter()
+                .map(|(k, v)| from_owned_nested_pair(k, v))
                 .collect(),
         }
     }
@@ -1393,7 +2231,19 @@ impl<K: FromRedisValue + Eq + Hash, V: FromRedisValue, S: BuildHasher + Default>
                 .ok_or_else(|| {
                     invalid_type_error_inner!(v, "Response type not hashmap compatible")
                 })?
-                .map(|(k, v)| Ok((from_redis_value(k)?, from_redis_value(v)?)))
+                .map(|(k, v)| from_nested_pair(k, v))
+                .collect(),
+        }
+    }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<Self> {
+        match v {
+            Value::Nil => Self::from_redis_value(&v),
+            _ => v
+                .into_map_pairs()
+                .map_err(|v| invalid_type_error_inner!(v, "Response type not hashmap compatible"))?
+                .into_iter()
+                .map(|(k, v)| from_owned_nested_pair(k, v))
                 .collect(),
         }
     }
@@ -1406,7 +2256,15 @@ where
     fn from_redis_value(v: &Value) -> RedisResult<BTreeMap<K, V>> {
         v.as_map_iter()
             .ok_or_else(|| invalid_type_error_inner!(v, "Response type not btreemap compatible"))?
-            .map(|(k, v)| Ok((from_redis_value(k)?, from_redis_value(v)?)))
+            .map(|(k, v)| from_nested_pair(k, v))
+            .collect()
+    }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<BTreeMap<K, V>> {
+        v.into_map_pairs()
+            .map_err(|v| invalid_type_error_inner!(v, "Response type not btreemap compatible"))?
+            .into_iter()
+            .map(|(k, v)| from_owned_nested_pair(k, v))
             .collect()
     }
 }
@@ -1418,7 +2276,20 @@ impl<T: FromRedisValue + Eq + Hash, S: BuildHasher + Default> FromRedisValue
         let items = v
             .as_sequence()
             .ok_or_else(|| invalid_type_error_inner!(v, "Response type not hashset compatible"))?;
-        items.iter().map(|item| from_redis_value(item)).collect()
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| from_nested_value(item, || ConversionPathSegment::Index(i)))
+            .collect()
+    }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<Self> {
+        v.into_sequence()
+            .map_err(|v| invalid_type_error_inner!(v, "Response type not hashset compatible"))?
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| from_owned_nested_value(item, || ConversionPathSegment::Index(i)))
+            .collect()
     }
 }
 
@@ -1430,7 +2301,20 @@ impl<T: FromRedisValue + Eq + Hash, S: BuildHasher + Default> FromRedisValue
         let items = v
             .as_sequence()
             .ok_or_else(|| invalid_type_error_inner!(v, "Response type not hashset compatible"))?;
-        items.iter().map(|item| from_redis_value(item)).collect()
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| from_nested_value(item, || ConversionPathSegment::Index(i)))
+            .collect()
+    }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<Self> {
+        v.into_sequence()
+            .map_err(|v| invalid_type_error_inner!(v, "Response type not hashset compatible"))?
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| from_owned_nested_value(item, || ConversionPathSegment::Index(i)))
+            .collect()
     }
 }
 
@@ -1442,7 +2326,20 @@ where
         let items = v
             .as_sequence()
             .ok_or_else(|| invalid_type_error_inner!(v, "Response type not btreeset compatible"))?;
-        items.iter().map(|item| from_redis_value(item)).collect()
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| from_nested_value(item, || ConversionPathSegment::Index(i)))
+            .collect()
+    }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<Self> {
+        v.into_sequence()
+            .map_err(|v| invalid_type_error_inner!(v, "Response type not btreeset compatible"))?
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| from_owned_nested_value(item, || ConversionPathSegment::Index(i)))
+            .collect()
     }
 }
 
@@ -1450,6 +2347,14 @@ impl FromRedisValue for Value {
     fn from_redis_value(v: &Value) -> RedisResult<Value> {
         Ok(v.clone())
     }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<Value> {
+        Ok(v)
+    }
+
+    fn from_owned_redis_values(items: Vec<Value>) -> RedisResult<Vec<Value>> {
+        Ok(items)
+    }
 }
 
 impl FromRedisValue for () {
@@ -1458,6 +2363,29 @@ impl FromRedisValue for () {
     }
 }
 
+/// Pairs up the flat `items` into tuples of `n` elements.
+macro_rules! from_flat_tuple_values {
+    ($items:expr, $n:expr, $($name:ident,)*) => {
+        $items
+            .chunks_exact($n)
+            .enumerate()
+            .map(|(index, chunk)| {
+                // this is pretty ugly too.  `position` is one past the
+                // position of the element that is being converted.
+                let mut position = 0;
+                match chunk {
+                    [$($name),*] => Ok(($({
+                        position += 1;
+                        from_nested_value($name, || ConversionPathSegment::TuplePosition(position - 1))
+                            .map_err(|err| err.with_conversion_segment(ConversionPathSegment::Index(index)))?
+                    },)*)),
+                    _ => unreachable!(),
+                }
+            })
+            .collect::<RedisResult<Vec<_>>>()
+    };
+}
+
 macro_rules! from_redis_value_for_tuple {
     () => ();
     ($($name:ident,)+) => (
@@ -1476,18 +2404,37 @@ macro_rules! from_redis_value_for_tuple {
                             invalid_type_error!(v, "Bulk response of wrong dimension")
                         }
 
-                        // this is pretty ugly too.  The { i += 1; i - 1} is rust's
-                        // postfix increment :)
+                        // this is pretty ugly too.  `i` is one past the position
+                        // of the element that is being converted.
                         let mut i = 0;
-                        Ok(($({let $name = (); from_redis_value(
-                             &items[{ i += 1; i - 1 }])?},)*))
+                        Ok(($({let $name = (); i += 1; from_nested_value(
+                             &items[i - 1], || ConversionPathSegment::TuplePosition(i - 1))?},)*))
                     }
                     _ => invalid_type_error!(v, "Not a bulk response")
                 }
             }
 
             #[allow(non_snake_case, unused_variables)]
-            fn from_redis_values(items: &[Value]) -> RedisResult<Vec<($($name,)*)>> {
+            fn from_owned_redis_value(v: Value) -> RedisResult<($($name,)*)> {
+                match v {
+                    Value::Bulk(items) => {
+                        // hacky way to count the tuple size
+                        let mut n = 0;
+                        $(let $name = (); n += 1;)*
+                        if items.len() != n {
+                            invalid_type_error!(Value::Bulk(items), "Bulk response of wrong dimension")
+                        }
+
+                        let mut it = items.into_iter().enumerate();
+                        Ok(($({let $name = (); let (i, item) = it.next().unwrap();
+                            from_owned_nested_value(item, || ConversionPathSegment::TuplePosition(i))?},)*))
+                    }
+                    _ => invalid_type_error!(v, "Not a bulk response")
+                }
+            }
+
+            #[allow(non_snake_case, unused_variables)]
+            fn from_owned_redis_values(items: Vec<Value>) -> RedisResult<Vec<($($name,)*)>> {
                 // hacky way to count the tuple size
                 let mut n = 0;
                 $(let $name = (); n += 1;)*
@@ -1495,20 +2442,56 @@ macro_rules! from_redis_value_for_tuple {
                     invalid_type_error!(items, "Bulk response of wrong dimension")
                 }
 
-                // this is pretty ugly too.  The { i += 1; i - 1} is rust's
-                // postfix increment :)
-                let mut rv = vec![];
-                if items.len() == 0 {
-                    return Ok(rv)
-                }
-                for chunk in items.chunks_exact(n) {
-                    match chunk {
-                        [$($name),*] => rv.push(($(from_redis_value($name)?),*),),
-                         _ => unreachable!(),
+                // RESP3 replies such as `ZRANGE ... WITHSCORES` nest every
+                // tuple in its own bulk instead of flattening them.  Flat
+                // replies of the same shape are still decoded as RESP2 would,
+                // so when every item is a bulk of the tuple size the first
+                // tuple picks the layout.  Only that one is converted twice,
+                // everything else is moved out of `items` once.
+                if is_nested_tuple_reply(&items, n) {
+                    let first: RedisResult<Vec<($($name,)*)>> =
+                        from_flat_tuple_values!(&items[..n], n, $($name,)*);
+                    if first.is_err() {
+                        return from_owned_nested_values(items);
                     }
                 }
+
+                let mut rv = Vec::with_capacity(items.len() / n);
+                let mut it = items.into_iter();
+                while it.len() > 0 {
+                    let index = rv.len();
+                    let mut position = 0;
+                    rv.push(($({let $name = (); position += 1; from_owned_nested_value(
+                        it.next().unwrap(), || ConversionPathSegment::TuplePosition(position - 1))
+                        .map_err(|err| err.with_conversion_segment(ConversionPathSegment::Index(index)))?},)*));
+                }
                 Ok(rv)
             }
+
+            #[allow(non_snake_case, unused_variables)]
+            fn from_redis_values(items: &[Value]) -> RedisResult<Vec<($($name,)*)>> {
+                // hacky way to count the tuple size
+                let mut n = 0;
+                $(let $name = (); n += 1;)*
+                if items.len() % n != 0 {
+                    invalid_type_error!(items, "Bulk response of wrong dimension")
+                }
+
+                let flat = from_flat_tuple_values!(items, n, $($name,)*);
+
+                // RESP3 replies such as `ZRANGE ... WITHSCORES` nest every
+                // tuple in its own bulk instead of flattening them.  Flat
+                // replies of the same shape are still decoded as RESP2 would,
+                // the nested reading is only tried when that fails.
+                match flat {
+                    Err(_) if is_nested_tuple_reply(items, n) => items
+                        .iter()
+                        .enumerate()
+                        .map(|(i, item)| from_nested_value(item, || ConversionPathSegment::Index(i)))
+                        .collect(),
+                    flat => flat,
+                }
+            }
         }
         from_redis_value_for_tuple_peel!($($name,)*);
     )
@@ -1537,6 +2520,17 @@ impl<T: FromRedisValue> FromRedisValue for Option<T> {
         }
         Ok(Some(from_redis_value(v)?))
     }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<Option<T>> {
+        if v == Value::Nil {
+            return Ok(None);
+        }
+        Ok(Some(from_owned_redis_value(v)?))
+    }
+
+    fn from_owned_redis_values(items: Vec<Value>) -> RedisResult<Vec<Option<T>>> {
+        from_owned_nested_values(items)
+    }
 }
 
 #[cfg(feature = "bytes")]
@@ -1547,10 +2541,317 @@ impl FromRedisValue for bytes::Bytes {
             _ => invalid_type_error!(v, "Not binary data"),
         }
     }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<Self> {
+        match v {
+            Value::Data(bytes_vec) => Ok(bytes_vec.into()),
+            _ => invalid_type_error!(v, "Not binary data"),
+        }
+    }
+
+    fn from_owned_redis_values(items: Vec<Value>) -> RedisResult<Vec<Self>> {
+        from_owned_nested_values(items)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl FromRedisValue for num_bigint::BigInt {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        match *v {
+            Value::BigNumber(ref n) => match n.parse() {
+                Ok(rv) => Ok(rv),
+                Err(_) => invalid_type_error!(v, "Could not convert from big number."),
+            },
+            Value::Int(val) => Ok(val.into()),
+            Value::Data(ref bytes) => match from_utf8(bytes)?.parse() {
+                Ok(rv) => Ok(rv),
+                Err(_) => invalid_type_error!(v, "Could not convert from string."),
+            },
+            _ => invalid_type_error!(v, "Response type not big number compatible."),
+        }
+    }
 }
 
 /// A shortcut function to invoke `FromRedisValue::from_redis_value`
 /// to make the API slightly nicer.
 pub fn from_redis_value<T: FromRedisValue>(v: &Value) -> RedisResult<T> {
-    FromRedisValue::from_redis_value(v)
+    FromRedisValue::from_redis_value(v).map_err(RedisError::with_expected_type::<T>)
+}
+
+/// A shortcut function to invoke `FromRedisValue::from_owned_redis_value`
+/// to make the API slightly nicer.
+pub fn from_owned_redis_value<T: FromRedisValue>(v: Value) -> RedisResult<T> {
+    FromRedisValue::from_owned_redis_value(v).map_err(RedisError::with_expected_type::<T>)
+}
+
+/// A low-level redis value which borrows its string and binary data from
+/// the buffer it was parsed from.
+///
+/// This mirrors [`Value`] but does not copy bulk strings, which matters for
+/// large blobs or replies such as big `MGET`s.  It is produced by
+/// [`Parser::parse_value_ref`](crate::Parser::parse_value_ref) and
+/// [`parse_redis_value_ref`](crate::parse_redis_value_ref), and converted
+/// with the [`FromRedisValueRef`] trait or into an owned [`Value`] with
+/// [`ValueRef::to_value`].
+#[derive(PartialEq, Clone, Debug)]
+#[non_exhaustive]
+pub enum ValueRef<'a> {
+    /// A nil response from the server.
+    Nil,
+    /// An integer response.
+    Int(i64),
+    /// Arbitrary binary data, borrowed from the parsed buffer.
+    Data(&'a [u8]),
+    /// A bulk response of more data.
+    Bulk(Vec<ValueRef<'a>>),
+    /// A status response.
+    Status(&'a str),
+    /// A status response which represents the string "OK".
+    Okay,
+    /// A RESP3 map response.
+    Map(Vec<(ValueRef<'a>, ValueRef<'a>)>),
+    /// A RESP3 attribute response.
+    Attribute {
+        /// The reply the attributes were attached to.
+        data: Box<ValueRef<'a>>,
+        /// The attribute key/value pairs.
+        attributes: Vec<(ValueRef<'a>, ValueRef<'a>)>,
+    },
+    /// A RESP3 set response.
+    Set(Vec<ValueRef<'a>>),
+    /// A RESP3 double response.
+    Double(f64),
+    /// A RESP3 boolean response.
+    Boolean(bool),
+    /// A RESP3 verbatim string response.
+    VerbatimString {
+        /// The format of the text.
+        format: VerbatimFormat,
+        /// The actual text.
+        text: &'a str,
+    },
+    /// A RESP3 big number response, holding the decimal digits.
+    BigNumber(&'a str),
+    /// A RESP3 push response.
+    Push {
+        /// The kind of the push message.
+        kind: PushKind,
+        /// The remaining data of the push message.
+        data: Vec<ValueRef<'a>>,
+    },
+}
+
+impl<'a> ValueRef<'a> {
+    /// Copies the borrowed data into an owned [`Value`].
+    pub fn to_value(&self) -> Value {
+        fn values(items: &[ValueRef<'_>]) -> Vec<Value> {
+            items.iter().map(ValueRef::to_value).collect()
+        }
+
+        fn pairs(items: &[(ValueRef<'_>, ValueRef<'_>)]) -> Vec<(Value, Value)> {
+            items
+                .iter()
+                .map(|(k, v)| (k.to_value(), v.to_value()))
+                .collect()
+        }
+
+        match *self {
+            ValueRef::Nil => Value::Nil,
+            ValueRef::Int(val) => Value::Int(val),
+            ValueRef::Data(bytes) => Value::Data(bytes.to_vec()),
+            ValueRef::Bulk(ref items) => Value::Bulk(values(items)),
+            ValueRef::Status(s) => Value::Status(s.to_string()),
+            ValueRef::Okay => Value::Okay,
+            ValueRef::Map(ref items) => Value::Map(pairs(items)),
+            ValueRef::Attribute {
+                ref data,
+                ref attributes,
+            } => Value::Attribute {
+                data: Box::new(data.to_value()),
+                attributes: pairs(attributes),
+            },
+            ValueRef::Set(ref items) => Value::Set(values(items)),
+            ValueRef::Double(val) => Value::Double(val),
+            ValueRef::Boolean(val) => Value::Boolean(val),
+            ValueRef::VerbatimString { ref format, text } => Value::VerbatimString {
+                format: format.clone(),
+                text: text.to_string(),
+            },
+            ValueRef::BigNumber(digits) => Value::BigNumber(digits.to_string()),
+            ValueRef::Push { ref kind, ref data } => Value::Push {
+                kind: kind.clone(),
+                data: values(data),
+            },
+        }
+    }
+}
+
+/// This trait is used to convert a borrowed [`ValueRef`] into a rust type.
+///
+/// Unlike [`FromRedisValue`] the result may borrow from the buffer the value
+/// was parsed from, as `&'a [u8]` and `&'a str` do.  Scalar types such as
+/// integers and `String` convert exactly like their [`FromRedisValue`]
+/// implementation.
+pub trait FromRedisValueRef<'a>: Sized {
+    /// Given a borrowed redis value this attempts to convert it into the
+    /// given destination type.
+    fn from_redis_value_ref(v: &ValueRef<'a>) -> RedisResult<Self>;
+}
+
+impl<'a> FromRedisValueRef<'a> for &'a [u8] {
+    fn from_redis_value_ref(v: &ValueRef<'a>) -> RedisResult<&'a [u8]> {
+        match *v {
+            ValueRef::Data(bytes) => Ok(bytes),
+            ValueRef::Okay => Ok(b"OK"),
+            ValueRef::Status(val) => Ok(val.as_bytes()),
+            ValueRef::VerbatimString { text, .. } => Ok(text.as_bytes()),
+            _ => invalid_type_error!(v, "Response type not binary data compatible."),
+        }
+    }
+}
+
+impl<'a> FromRedisValueRef<'a> for &'a str {
+    fn from_redis_value_ref(v: &ValueRef<'a>) -> RedisResult<&'a str> {
+        match *v {
+            ValueRef::Data(bytes) => Ok(from_utf8(bytes)?),
+            ValueRef::Okay => Ok("OK"),
+            ValueRef::Status(val) => Ok(val),
+            ValueRef::VerbatimString { text, .. } => Ok(text),
+            _ => invalid_type_error!(v, "Response type not string compatible."),
+        }
+    }
+}
+
+/// Scalars own their result anyway, so they are converted through their
+/// [`FromRedisValue`] implementation to keep the rules in one place.
+macro_rules! from_redis_value_ref_via_owned {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> FromRedisValueRef<'a> for $t {
+                fn from_redis_value_ref(v: &ValueRef<'a>) -> RedisResult<$t> {
+                    from_owned_redis_value(v.to_value())
+                }
+            }
+        )*
+    };
+}
+
+from_redis_value_ref_via_owned!(
+    u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, f32, f64, bool, String, Value
+);
+
+impl<'a, T: FromRedisValueRef<'a>> FromRedisValueRef<'a> for Option<T> {
+    fn from_redis_value_ref(v: &ValueRef<'a>) -> RedisResult<Option<T>> {
+        match *v {
+            ValueRef::Nil => Ok(None),
+            _ => from_redis_value_ref(v).map(Some),
+        }
+    }
+}
+
+impl<'a, T: FromRedisValueRef<'a>> FromRedisValueRef<'a> for Vec<T> {
+    fn from_redis_value_ref(v: &ValueRef<'a>) -> RedisResult<Vec<T>> {
+        match *v {
+            ValueRef::Bulk(ref items) | ValueRef::Set(ref items) => {
+                items.iter().map(from_redis_value_ref).collect()
+            }
+            ValueRef::Nil => Ok(vec![]),
+            _ => invalid_type_error!(v, "Response type not vector compatible."),
+        }
+    }
+}
+
+impl<'a, K, V, S> FromRedisValueRef<'a> for std::collections::HashMap<K, V, S>
+where
+    K: FromRedisValueRef<'a> + Eq + Hash,
+    V: FromRedisValueRef<'a>,
+    S: BuildHasher + Default,
+{
+    fn from_redis_value_ref(v: &ValueRef<'a>) -> RedisResult<std::collections::HashMap<K, V, S>> {
+        let pair = |k, v| Ok((from_redis_value_ref(k)?, from_redis_value_ref(v)?));
+        match *v {
+            ValueRef::Nil => Ok(Default::default()),
+            ValueRef::Bulk(ref items) if items.len() % 2 == 0 => items
+                .chunks_exact(2)
+                .map(|chunk| pair(&chunk[0], &chunk[1]))
+                .collect(),
+            ValueRef::Map(ref items) => items.iter().map(|(k, v)| pair(k, v)).collect(),
+            _ => invalid_type_error!(v, "Response type not hashmap compatible"),
+        }
+    }
+}
+
+impl<'a> FromRedisValueRef<'a> for ValueRef<'a> {
+    fn from_redis_value_ref(v: &ValueRef<'a>) -> RedisResult<ValueRef<'a>> {
+        Ok(v.clone())
+    }
+}
+
+impl<'a> FromRedisValueRef<'a> for () {
+    fn from_redis_value_ref(_v: &ValueRef<'a>) -> RedisResult<()> {
+        Ok(())
+    }
+}
+
+/// A shortcut function to invoke `FromRedisValueRef::from_redis_value_ref`
+/// to make the API slightly nicer.
+pub fn from_redis_value_ref<'a, T: FromRedisValueRef<'a>>(v: &ValueRef<'a>) -> RedisResult<T> {
+    FromRedisValueRef::from_redis_value_ref(v).map_err(RedisError::with_expected_type::<T>)
+}
+
+fn from_nested_value<T: FromRedisValue>(
+    v: &Value,
+    segment: impl FnOnce() -> ConversionPathSegment,
+) -> RedisResult<T> {
+    from_redis_value(v).map_err(|err| err.with_conversion_segment(segment()))
+}
+
+fn from_owned_nested_value<T: FromRedisValue>(
+    v: Value,
+    segment: impl FnOnce() -> ConversionPathSegment,
+) -> RedisResult<T> {
+    from_owned_redis_value(v).map_err(|err| err.with_conversion_segment(segment()))
+}
+
+/// Whether `items` could be a RESP3 reply that nests each tuple of `n`
+/// elements in its own bulk.
+fn is_nested_tuple_reply(items: &[Value], n: usize) -> bool {
+    n > 1
+        && items
+            .iter()
+            .all(|item| matches!(item, Value::Bulk(inner) if inner.len() == n))
+}
+
+fn from_owned_nested_values<T: FromRedisValue>(items: Vec<Value>) -> RedisResult<Vec<T>> {
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| from_owned_nested_value(item, || ConversionPathSegment::Index(i)))
+        .collect()
+}
+
+/// Describes a map key for a conversion path.
+fn key_segment(key: &Value) -> ConversionPathSegment {
+    ConversionPathSegment::Key(match key {
+        Value::Data(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        Value::Status(s) | Value::VerbatimString { text: s, .. } => s.clone(),
+        Value::Int(i) => i.to_string(),
+        _ => format!("{key:?}"),
+    })
+}
+
+fn from_nested_pair<K: FromRedisValue, V: FromRedisValue>(
+    k: &Value,
+    v: &Value,
+) -> RedisResult<(K, V)> {
+    let value = from_nested_value(v, || key_segment(k))?;
+    Ok((from_redis_value(k)?, value))
+}
+
+fn from_owned_nested_pair<K: FromRedisValue, V: FromRedisValue>(
+    k: Value,
+    v: Value,
+) -> RedisResult<(K, V)> {
+    let value = from_owned_nested_value(v, || key_segment(&k))?;
+    Ok((from_owned_redis_value(k)?, value))
 }
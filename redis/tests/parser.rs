@@ -43,6 +43,19 @@ impl ::quickcheck::Arbitrary for ArbitraryValue {
             Value::Status(ref status) => {
                 Box::new(status.shrink().map(Value::Status).map(ArbitraryValue))
             }
+            Value::Set(ref xs) => {
+                let ys = xs
+                    .iter()
+                    .map(|x| ArbitraryValue(x.clone()))
+                    .collect::<Vec<_>>();
+                Box::new(
+                    ys.shrink()
+                        .map(|xs| xs.into_iter().map(|x| x.0).collect())
+                        .map(Value::Set)
+                        .map(ArbitraryValue),
+                )
+            }
+            _ => Box::new(None.into_iter()),
         }
     }
 }
@@ -52,7 +65,7 @@ fn arbitrary_value(g: &mut Gen, recursive_size: usize) -> Value {
     if recursive_size == 0 {
         Value::Nil
     } else {
-        match u8::arbitrary(g) % 6 {
+        match u8::arbitrary(g) % 10 {
             0 => Value::Nil,
             1 => Value::Int(Arbitrary::arbitrary(g)),
             2 => Value::Data(Arbitrary::arbitrary(g)),
@@ -88,6 +101,39 @@ fn arbitrary_value(g: &mut Gen, recursive_size: usize) -> Value {
                 }
             }
             5 => Value::Okay,
+            6 => Value::Boolean(Arbitrary::arbitrary(g)),
+            7 => {
+                let val = f64::arbitrary(g);
+                // NaN never compares equal to itself
+                Value::Double(if val.is_nan() { 0.0 } else { val })
+            }
+            8 => {
+                let size = {
+                    let s = g.size();
+                    usize::arbitrary(g) % s
+                };
+                Value::Set(
+                    (0..size)
+                        .map(|_| arbitrary_value(g, recursive_size / size))
+                        .collect(),
+                )
+            }
+            9 => {
+                let size = {
+                    let s = g.size();
+                    usize::arbitrary(g) % s
+                };
+                Value::Map(
+                    (0..size)
+                        .map(|_| {
+                            (
+                                arbitrary_value(g, recursive_size / size),
+                                arbitrary_value(g, recursive_size / size),
+                            )
+                        })
+                        .collect(),
+                )
+            }
             _ => unreachable!(),
         }
     }
@@ -152,3 +198,19 @@ quickcheck! {
         );
     }
 }
+
+quickcheck! {
+    fn encode_parse_roundtrip(input: ArbitraryValue) -> () {
+        let encoded = redis::encode_value(&input.0, redis::ProtocolVersion::RESP3);
+        assert_eq!(redis::parse_redis_value(&encoded).unwrap(), input.0);
+    }
+}
+
+quickcheck! {
+    fn borrowed_parse(input: ArbitraryValue) -> () {
+        let encoded = redis::encode_value(&input.0, redis::ProtocolVersion::RESP3);
+        let result = redis::parse_redis_value_ref(&encoded);
+        assert!(result.as_ref().is_ok(), "{}", result.unwrap_err());
+        assert_eq!(result.unwrap().to_value(), input.0);
+    }
+}
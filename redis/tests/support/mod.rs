@@ -57,12 +57,14 @@ mod util;
 pub use self::cluster::*;
 
 #[cfg(any(feature = "cluster", feature = "cluster-async"))]
+#[allow(unused_imports)]
 pub use self::mock_cluster::*;
 
 #[cfg(feature = "sentinel")]
 mod sentinel;
 
 #[cfg(feature = "sentinel")]
+#[allow(unused_imports)]
 pub use self::sentinel::*;
 
 #[derive(PartialEq)]
@@ -230,7 +232,7 @@ impl RedisServer {
                 // prepare redis with TLS
                 redis_cmd
                     .arg("--tls-port")
-                    .arg(&port.to_string())
+                    .arg(port.to_string())
                     .arg("--port")
                     .arg("0")
                     .arg("--tls-cert-file")
@@ -496,6 +498,50 @@ where
         }
         Value::Okay => write!(writer, "+OK\r\n"),
         Value::Status(ref s) => write!(writer, "+{s}\r\n"),
+        Value::Map(ref values) => {
+            write!(writer, "%{}\r\n", values.len())?;
+            for (k, v) in values.iter() {
+                encode_value(k, writer)?;
+                encode_value(v, writer)?;
+            }
+            Ok(())
+        }
+        Value::Attribute {
+            ref data,
+            ref attributes,
+        } => {
+            write!(writer, "|{}\r\n", attributes.len())?;
+            for (k, v) in attributes.iter() {
+                encode_value(k, writer)?;
+                encode_value(v, writer)?;
+            }
+            encode_value(data, writer)
+        }
+        Value::Set(ref values) => {
+            write!(writer, "~{}\r\n", values.len())?;
+            for val in values.iter() {
+                encode_value(val, writer)?;
+            }
+            Ok(())
+        }
+        Value::Double(val) => write!(writer, ",{val}\r\n"),
+        Value::Boolean(val) => write!(writer, "#{}\r\n", if val { 't' } else { 'f' }),
+        Value::VerbatimString {
+            ref format,
+            ref text,
+        } => {
+            // format is always 3 bytes
+            write!(writer, "={}\r\n{format}:{text}\r\n", 3 + 1 + text.len())
+        }
+        Value::BigNumber(ref val) => write!(writer, "({val}\r\n"),
+        Value::Push { ref kind, ref data } => {
+            write!(writer, ">{}\r\n+{kind}\r\n", data.len() + 1)?;
+            for val in data.iter() {
+                encode_value(val, writer)?;
+            }
+            Ok(())
+        }
+        _ => unimplemented!("cannot encode {value:?}"),
     }
 }
 
@@ -521,7 +567,7 @@ pub fn build_keys_and_certs_for_tls(tempdir: &TempDir) -> TlsFilePaths {
             .arg("genrsa")
             .arg("-out")
             .arg(name)
-            .arg(&format!("{size}"))
+            .arg(format!("{size}"))
             .stdout(process::Stdio::null())
             .stderr(process::Stdio::null())
             .spawn()
@@ -722,7 +768,7 @@ pub(crate) mod mtls_test {
         let server = cluster
             .cluster
             .servers
-            .get(0)
+            .first()
             .expect("Expected at least 1 server");
         let tls_paths = server.tls_paths.as_ref();
         let nodes = clean_node_info(&cluster.nodes);
@@ -14,11 +14,11 @@ fn test_args() {
         redis::cmd("SET")
             .arg("key1")
             .arg(b"foo")
-            .query_async(&mut con)
+            .query_async::<_, ()>(&mut con)
             .await?;
         redis::cmd("SET")
             .arg(&["key2", "bar"])
-            .query_async(&mut con)
+            .query_async::<_, ()>(&mut con)
             .await?;
         let result = redis::cmd("MGET")
             .arg(&["key1", "key2"])
@@ -146,11 +146,11 @@ fn test_cmd(con: &MultiplexedConnection, i: i32) -> impl Future<Output = RedisRe
         redis::cmd("SET")
             .arg(&key[..])
             .arg(foo_val.as_bytes())
-            .query_async(&mut con)
+            .query_async::<_, ()>(&mut con)
             .await?;
         redis::cmd("SET")
             .arg(&[&key2, "bar"])
-            .query_async(&mut con)
+            .query_async::<_, ()>(&mut con)
             .await?;
         redis::cmd("MGET")
             .arg(&[&key_2, &key2_2])
@@ -274,7 +274,7 @@ fn test_async_scanning(batch_size: usize) {
                         redis::cmd("SADD")
                             .arg("foo")
                             .arg(x)
-                            .query_async(&mut con)
+                            .query_async::<_, ()>(&mut con)
                             .await?;
                         unseen.insert(x);
                     }
@@ -298,7 +298,7 @@ fn test_async_scanning(batch_size: usize) {
                     Ok(())
                 }
             })
-            .map_err(|err| panic!("{}", err))
+            .map_err::<(), _>(|err| panic!("{}", err))
             .await
     })
     .unwrap();
@@ -332,7 +332,7 @@ fn test_script() {
         script1
             .key("key1")
             .arg("foo")
-            .invoke_async(&mut con)
+            .invoke_async::<_, ()>(&mut con)
             .await?;
         let val: String = script2.key("key1").invoke_async(&mut con).await?;
         assert_eq!(val, "foo");
@@ -341,7 +341,7 @@ fn test_script() {
         script1
             .key("key1")
             .arg("bar")
-            .invoke_async(&mut con)
+            .invoke_async::<_, ()>(&mut con)
             .await?;
         let val: String = script2.key("key1").invoke_async(&mut con).await?;
         assert_eq!(val, "bar");
@@ -447,6 +447,7 @@ async fn invalid_password_issue_343() {
             db: 0,
             username: None,
             password: Some("asdcasc".to_string()),
+            ..Default::default()
         },
     };
     let client = redis::Client::open(coninfo).unwrap();
@@ -500,6 +501,138 @@ async fn test_issue_async_commands_scan_broken() {
     assert_eq!(keys.len(), 100);
 }
 
+#[tokio::test]
+async fn multiplexed_connection_routes_push_messages() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (client_stream, mut server_stream) = tokio::io::duplex(4096);
+    let server = tokio::spawn(async move {
+        // CLIENT SETINFO pipeline sent during the connection setup
+        let setinfo = [
+            cmd("CLIENT")
+                .arg("SETINFO")
+                .arg("LIB-NAME")
+                .arg("redis-rs")
+                .get_packed_command(),
+            cmd("CLIENT")
+                .arg("SETINFO")
+                .arg("LIB-VER")
+                .arg(env!("CARGO_PKG_VERSION"))
+                .get_packed_command(),
+        ]
+        .concat();
+        let mut buf = vec![0; setinfo.len()];
+        server_stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, setinfo);
+        server_stream.write_all(b"+OK\r\n+OK\r\n").await.unwrap();
+
+        // PING, answered after an unsolicited push message
+        let ping = cmd("PING").get_packed_command();
+        let mut buf = vec![0; ping.len()];
+        server_stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, ping);
+        server_stream
+            .write_all(b">2\r\n$10\r\ninvalidate\r\n*1\r\n$3\r\nfoo\r\n+PONG\r\n")
+            .await
+            .unwrap();
+        server_stream
+    });
+
+    let (push_sender, mut push_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let (mut con, driver) = MultiplexedConnection::new_with_push_sender(
+        &Default::default(),
+        client_stream,
+        Some(push_sender),
+    )
+    .await
+    .unwrap();
+    tokio::spawn(driver);
+
+    let pong: String = cmd("PING").query_async(&mut con).await.unwrap();
+    assert_eq!(pong, "PONG");
+    assert_eq!(
+        push_receiver.recv().await,
+        Some(redis::PushInfo {
+            kind: redis::PushKind::Invalidate,
+            data: vec![redis::Value::Bulk(vec![redis::Value::Data(
+                b"foo".to_vec()
+            )])],
+        })
+    );
+    drop(server.await.unwrap());
+}
+
+#[tokio::test]
+async fn multiplexed_connection_waits_for_all_subscribe_confirmations() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (client_stream, mut server_stream) = tokio::io::duplex(4096);
+    let server = tokio::spawn(async move {
+        let setinfo = [
+            cmd("CLIENT")
+                .arg("SETINFO")
+                .arg("LIB-NAME")
+                .arg("redis-rs")
+                .get_packed_command(),
+            cmd("CLIENT")
+                .arg("SETINFO")
+                .arg("LIB-VER")
+                .arg(env!("CARGO_PKG_VERSION"))
+                .get_packed_command(),
+        ]
+        .concat();
+        let mut buf = vec![0; setinfo.len()];
+        server_stream.read_exact(&mut buf).await.unwrap();
+        server_stream.write_all(b"+OK\r\n+OK\r\n").await.unwrap();
+
+        // One confirmation per channel
+        let subscribe = cmd("SUBSCRIBE").arg(&["a", "b", "c"]).get_packed_command();
+        let mut buf = vec![0; subscribe.len()];
+        server_stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, subscribe);
+        for (channel, count) in [("a", 1), ("b", 2), ("c", 3)] {
+            server_stream
+                .write_all(
+                    format!(">3\r\n$9\r\nsubscribe\r\n$1\r\n{channel}\r\n:{count}\r\n").as_bytes(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let get = cmd("GET").arg("foo").get_packed_command();
+        let mut buf = vec![0; get.len()];
+        server_stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, get);
+        server_stream.write_all(b"$3\r\nbar\r\n").await.unwrap();
+        server_stream
+    });
+
+    let (push_sender, mut push_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let (mut con, driver) = MultiplexedConnection::new_with_push_sender(
+        &Default::default(),
+        client_stream,
+        Some(push_sender),
+    )
+    .await
+    .unwrap();
+    tokio::spawn(driver);
+
+    cmd("SUBSCRIBE")
+        .arg(&["a", "b", "c"])
+        .query_async::<_, ()>(&mut con)
+        .await
+        .unwrap();
+    let value: String = cmd("GET").arg("foo").query_async(&mut con).await.unwrap();
+    assert_eq!(value, "bar");
+    for _ in 0..3 {
+        assert_eq!(
+            push_receiver.recv().await.map(|push| push.kind),
+            Some(redis::PushKind::Subscribe)
+        );
+    }
+    drop(server.await.unwrap());
+}
+
 mod pub_sub {
     use std::collections::HashMap;
     use std::time::Duration;
@@ -516,7 +649,9 @@ mod pub_sub {
             pubsub_conn.subscribe("phonewave").await?;
             let mut pubsub_stream = pubsub_conn.on_message();
             let mut publish_conn = ctx.async_connection().await?;
-            publish_conn.publish("phonewave", "banana").await?;
+            publish_conn
+                .publish::<_, _, ()>("phonewave", "banana")
+                .await?;
 
             let msg_payload: String = pubsub_stream.next().await.unwrap().get_payload()?;
             assert_eq!("banana".to_string(), msg_payload);
@@ -601,7 +736,7 @@ mod pub_sub {
             redis::cmd("SET")
                 .arg("foo")
                 .arg("bar")
-                .query_async(&mut conn)
+                .query_async::<_, ()>(&mut conn)
                 .await?;
 
             let res: String = redis::cmd("GET").arg("foo").query_async(&mut conn).await?;
@@ -640,6 +775,123 @@ mod pub_sub {
     }
 }
 
+#[test]
+#[cfg(feature = "cache-aio")]
+fn test_client_side_caching() {
+    let ctx = TestContext::new();
+    if ctx.get_version() < (6, 0, 0) {
+        return;
+    }
+    let mut info = ctx.server.connection_info();
+    info.redis.protocol = redis::ProtocolVersion::RESP3;
+    let client = redis::Client::open(info).unwrap();
+
+    block_on_all(async move {
+        let mut cached = client
+            .get_multiplexed_async_connection_with_cache(Default::default())
+            .await?;
+        let mut other = ctx.multiplexed_async_connection().await?;
+
+        cached.set::<_, _, ()>("key", 1).await?;
+        assert_eq!(cached.get::<_, i32>("key").await?, 1);
+        assert_eq!(cached.get::<_, i32>("key").await?, 1);
+
+        // the write of another connection invalidates the cached reply
+        other.set::<_, _, ()>("key", 2).await?;
+        let mut value = 1;
+        for _ in 0..100 {
+            value = cached.get("key").await?;
+            if value == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(value, 2);
+
+        // pipelined writes of the connection itself are read right away
+        redis::pipe()
+            .set("key", 3)
+            .ignore()
+            .query_async::<_, ()>(&mut cached)
+            .await?;
+        assert_eq!(cached.get::<_, i32>("key").await?, 3);
+
+        Ok::<_, redis::RedisError>(())
+    })
+    .unwrap();
+}
+
+#[test]
+#[cfg(feature = "streams")]
+fn test_stream_consumer_into_stream() {
+    use redis::streams::{StreamConsumer, StreamPendingReply};
+
+    let ctx = TestContext::new();
+    block_on_all(async move {
+        let mut con = ctx.multiplexed_async_connection().await?;
+        let _: String = con.xadd("k1", "1-0", &[("hello", "world")]).await?;
+
+        let consumer = StreamConsumer::new("k1", "g1", "c1")
+            .start_id("0")
+            .block(10);
+        let acker = consumer.clone();
+        let read_con = ctx.multiplexed_async_connection().await?;
+        let mut entries = Box::pin(consumer.into_stream(read_con));
+
+        let entry = entries.next().await.unwrap()?;
+        assert_eq!(entry.id, "1-0");
+        assert_eq!(entry.get("hello"), Some("world".to_string()));
+
+        let pending: StreamPendingReply = con.xpending("k1", "g1").await?;
+        assert_eq!(pending.count(), 1);
+        acker.ack_async(&mut con, &entry.id).await?;
+        let pending: StreamPendingReply = con.xpending("k1", "g1").await?;
+        assert_eq!(pending.count(), 0);
+
+        Ok::<_, redis::RedisError>(())
+    })
+    .unwrap();
+}
+
+#[test]
+#[cfg(feature = "streams")]
+fn test_stream_tail() {
+    use redis::streams::{StreamTail, StreamTailStart};
+
+    let ctx = TestContext::new();
+    block_on_all(async move {
+        let mut con = ctx.multiplexed_async_connection().await?;
+        let _: String = con.xadd("k1", "1-0", &[("hello", "world")]).await?;
+        let _: String = con.xadd("k1", "2-0", &[("hello", "world")]).await?;
+
+        let tail = StreamTail::new("k1", StreamTailStart::Beginning)
+            .key("k2", StreamTailStart::Latest)
+            .block(10);
+        let mut entries = Box::pin(tail.into_stream(con.clone()));
+        let (key, entry) = entries.next().await.unwrap()?;
+        assert_eq!((key.as_str(), entry.id.as_str()), ("k1", "1-0"));
+        let (_, entry) = entries.next().await.unwrap()?;
+        assert_eq!(entry.id, "2-0");
+
+        // wait until the tail blocks for new entries
+        let next = tokio::time::timeout(std::time::Duration::from_millis(50), entries.next());
+        assert!(next.await.is_err());
+
+        let _: String = con.xadd("k2", "5-0", &[("hello", "world")]).await?;
+        let _: String = con.xadd("k1", "3-0", &[("hello", "world")]).await?;
+        let mut received = vec![];
+        for _ in 0..2 {
+            let (key, entry) = entries.next().await.unwrap()?;
+            received.push(format!("{key} {}", entry.id));
+        }
+        received.sort();
+        assert_eq!(received, vec!["k1 3-0", "k2 5-0"]);
+
+        Ok::<_, redis::RedisError>(())
+    })
+    .unwrap();
+}
+
 #[cfg(feature = "connection-manager")]
 async fn wait_for_server_to_become_ready(client: redis::Client) {
     let millisecond = std::time::Duration::from_millis(1);
@@ -710,7 +962,7 @@ mod mtls_test {
             redis::cmd("SET")
                 .arg("key1")
                 .arg(b"foo")
-                .query_async(&mut con)
+                .query_async::<_, ()>(&mut con)
                 .await?;
             let result = redis::cmd("GET").arg(&["key1"]).query_async(&mut con).await;
             assert_eq!(result, Ok("foo".to_string()));
@@ -731,7 +983,7 @@ mod mtls_test {
             redis::cmd("SET")
                 .arg("key1")
                 .arg(b"foo")
-                .query_async(&mut con)
+                .query_async::<_, ()>(&mut con)
                 .await?;
             let result = redis::cmd("GET").arg(&["key1"]).query_async(&mut con).await;
             assert_eq!(result, Ok("foo".to_string()));
@@ -1,8 +1,11 @@
 #![allow(clippy::let_unit_value)]
 
 use redis::{
-    Commands, ConnectionInfo, ConnectionLike, ControlFlow, ErrorKind, ExistenceCheck, Expiry,
-    PubSubCommands, RedisResult, SetExpiry, SetOptions, ToRedisArgs,
+    BitField, BitFieldOffset, BitFieldOverflow, BitFieldType, Commands, ConnectionInfo,
+    ConnectionLike, ControlFlow, CopyOptions, ErrorKind, ExistenceCheck, ExpireOption, Expiry,
+    ExpiryTime, HSetExOptions, HashFieldExpireStatus, HashFieldPersistStatus, MigrateOptions,
+    PubSubCommands, RedisResult, RestoreOptions, SetExpiry, SetOptions, SortOptions, ToRedisArgs,
+    ZAggregate, ZAggregateOptions, ZRangeOptions,
 };
 
 use std::collections::{BTreeMap, BTreeSet};
@@ -161,6 +164,256 @@ fn test_getex() {
     assert_eq!(delayed_get, 420usize);
 }
 
+#[test]
+fn test_expire_options() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if ctx.get_version().0 < 7 {
+        return;
+    }
+
+    assert_eq!(con.expire_time("foo"), Ok(ExpiryTime::Missing));
+    let _: () = con.set("foo", 42).unwrap();
+    assert_eq!(con.expire_time("foo"), Ok(ExpiryTime::NoExpiry));
+    assert_eq!(con.pttl("foo"), Ok(ExpiryTime::NoExpiry));
+
+    assert_eq!(con.expire_options("foo", 100, ExpireOption::XX), Ok(false));
+    assert_eq!(con.expire_options("foo", 100, ExpireOption::NX), Ok(true));
+    assert_eq!(con.expire_options("foo", 50, ExpireOption::GT), Ok(false));
+    assert_eq!(
+        con.pexpire_options("foo", 50_000, ExpireOption::LT),
+        Ok(true)
+    );
+    assert!(matches!(
+        con.ttl("foo"),
+        Ok(ExpiryTime::Expires(ttl)) if ttl <= 50
+    ));
+
+    assert_eq!(
+        con.expire_at_options("foo", 4_000_000_000, ExpireOption::GT),
+        Ok(true)
+    );
+    assert_eq!(
+        con.expire_time("foo"),
+        Ok(ExpiryTime::Expires(4_000_000_000))
+    );
+    assert_eq!(
+        con.pexpire_at_options("foo", 3_000_000_000_000, ExpireOption::GT),
+        Ok(false)
+    );
+    assert_eq!(
+        con.pexpire_time("foo"),
+        Ok(ExpiryTime::Expires(4_000_000_000_000))
+    );
+}
+
+#[test]
+fn test_expire_options_args() {
+    assert_eq!(
+        redis::Cmd::expire_options("foo", 10, ExpireOption::NONE).get_packed_command(),
+        redis::Cmd::expire("foo", 10).get_packed_command()
+    );
+    assert_eq!(
+        redis::Cmd::pexpire_at_options("foo", 10, ExpireOption::XX).get_packed_command(),
+        redis::cmd("PEXPIREAT")
+            .arg("foo")
+            .arg(10)
+            .arg("XX")
+            .get_packed_command()
+    );
+}
+
+#[test]
+fn test_key_lifecycle() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    assert_eq!(con.random_key(), Ok(None::<String>));
+    let _: () = con.set("foo", 42).unwrap();
+    assert_eq!(con.random_key(), Ok("foo".to_string()));
+    assert_eq!(con.touch(&["foo", "missing"]), Ok(1));
+
+    let dumped: Vec<u8> = con.dump("foo").unwrap();
+    assert_eq!(con.dump("missing"), Ok(None::<Vec<u8>>));
+    let _: () = con
+        .restore("bar", 0, &dumped, &RestoreOptions::default())
+        .unwrap();
+    assert_eq!(con.get("bar"), Ok(42));
+    assert!(con
+        .restore::<_, _, ()>("bar", 0, &dumped, &RestoreOptions::default())
+        .is_err());
+    let _: () = con
+        .restore(
+            "bar",
+            4_000_000_000_000,
+            &dumped,
+            &RestoreOptions::default().replace().abs_ttl(),
+        )
+        .unwrap();
+    assert_eq!(con.pttl::<_, i64>("bar").map(|ttl| ttl > 0), Ok(true));
+
+    assert_eq!(con.move_key("bar", 1), Ok(true));
+    assert_eq!(con.exists("bar"), Ok(false));
+
+    let help: Vec<String> = con.object_help().unwrap();
+    assert!(!help.is_empty());
+
+    if ctx.get_version().0 < 6 {
+        return;
+    }
+
+    let _: () = con.set("baz", 1).unwrap();
+    assert_eq!(con.copy("foo", "baz", &CopyOptions::default()), Ok(false));
+    assert_eq!(
+        con.copy("foo", "baz", &CopyOptions::default().replace()),
+        Ok(true)
+    );
+    assert_eq!(con.get("baz"), Ok(42));
+    assert_eq!(
+        con.copy("foo", "bar", &CopyOptions::default().db(1)),
+        Ok(false)
+    );
+    assert_eq!(
+        con.copy("foo", "bar", &CopyOptions::default().db(1).replace()),
+        Ok(true)
+    );
+}
+
+#[test]
+fn test_key_lifecycle_args() {
+    let empty = CopyOptions::default();
+    assert_eq!(ToRedisArgs::to_redis_args(&empty).len(), 0);
+    assert_args!(
+        &CopyOptions::default().db(2).replace(),
+        "DB",
+        "2",
+        "REPLACE"
+    );
+
+    let empty = RestoreOptions::default();
+    assert_eq!(ToRedisArgs::to_redis_args(&empty).len(), 0);
+    let opts = RestoreOptions::default()
+        .replace()
+        .abs_ttl()
+        .idle_time(10)
+        .freq(5);
+    assert_args!(&opts, "REPLACE", "ABSTTL", "IDLETIME", "10", "FREQ", "5");
+
+    let empty = MigrateOptions::default();
+    assert_eq!(ToRedisArgs::to_redis_args(&empty).len(), 0);
+    let opts = MigrateOptions::default().copy().replace().auth("secret");
+    assert_args!(&opts, "COPY", "REPLACE", "AUTH", "secret");
+    let opts = MigrateOptions::default().auth2("user", "secret");
+    assert_args!(&opts, "AUTH2", "user", "secret");
+
+    assert_eq!(
+        redis::Cmd::migrate("host", 6379, "foo", 0, 5000, &opts).get_packed_command(),
+        redis::cmd("MIGRATE")
+            .arg("host")
+            .arg(6379)
+            .arg("foo")
+            .arg(0)
+            .arg(5000)
+            .arg("AUTH2")
+            .arg("user")
+            .arg("secret")
+            .get_packed_command()
+    );
+    assert_eq!(
+        redis::Cmd::migrate("host", 6379, &["a", "b"], 0, 5000, &opts).get_packed_command(),
+        redis::cmd("MIGRATE")
+            .arg("host")
+            .arg(6379)
+            .arg("")
+            .arg(0)
+            .arg(5000)
+            .arg("AUTH2")
+            .arg("user")
+            .arg("secret")
+            .arg("KEYS")
+            .arg("a")
+            .arg("b")
+            .get_packed_command()
+    );
+}
+
+#[test]
+fn test_migrate_without_keys() {
+    // The server rejects this, the command itself is still assembled.
+    let keys: &[&str] = &[];
+    assert_eq!(
+        redis::Cmd::migrate("host", 6379, keys, 0, 5000, &MigrateOptions::default())
+            .get_packed_command(),
+        redis::cmd("MIGRATE")
+            .arg("host")
+            .arg(6379)
+            .arg("")
+            .arg(0)
+            .arg(5000)
+            .arg("KEYS")
+            .get_packed_command()
+    );
+}
+
+#[test]
+fn test_generated_commands() {
+    // Generated from the command spec, these need no server to check.
+    assert_eq!(
+        redis::Cmd::substr("key", 0, -1).get_packed_command(),
+        redis::cmd("SUBSTR")
+            .arg("key")
+            .arg(0)
+            .arg(-1)
+            .get_packed_command()
+    );
+    assert_eq!(
+        redis::Cmd::client_tracking(
+            "ON",
+            None,
+            Some(&["a:", "b:"][..]),
+            true,
+            false,
+            false,
+            true
+        )
+        .get_packed_command(),
+        redis::cmd("CLIENT")
+            .arg("TRACKING")
+            .arg("ON")
+            .arg("PREFIX")
+            .arg("a:")
+            .arg("PREFIX")
+            .arg("b:")
+            .arg("BCAST")
+            .arg("NOLOOP")
+            .get_packed_command()
+    );
+    assert_eq!(
+        redis::Cmd::failover(Some(("host", 6379, true)), false, Some(100)).get_packed_command(),
+        redis::cmd("FAILOVER")
+            .arg("TO")
+            .arg("host")
+            .arg(6379)
+            .arg("FORCE")
+            .arg("TIMEOUT")
+            .arg(100)
+            .get_packed_command()
+    );
+    assert_eq!(
+        redis::Cmd::flushall(None).get_packed_command(),
+        redis::cmd("FLUSHALL").get_packed_command()
+    );
+}
+
+#[test]
+fn test_migrate_options_debug() {
+    let opts = MigrateOptions::default().auth2("user", "secret");
+    let debug = format!("{opts:?}");
+    assert!(debug.contains("user"));
+    assert!(!debug.contains("secret"));
+}
+
 #[test]
 fn test_info() {
     let ctx = TestContext::new();
@@ -204,6 +457,134 @@ fn test_hash_ops() {
     assert_eq!(h.get("key_2"), Some(&2i32));
 }
 
+#[test]
+fn test_hash_field_expiration() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if ctx.get_version() < (7, 4, 0) {
+        return;
+    }
+
+    let _: () = con
+        .hset_multiple("hash", &[("a", 1), ("b", 2), ("c", 3)])
+        .unwrap();
+
+    assert_eq!(
+        con.hexpire("hash", 100, ExpireOption::NONE, &["a", "b", "missing"]),
+        Ok(vec![
+            HashFieldExpireStatus::Set,
+            HashFieldExpireStatus::Set,
+            HashFieldExpireStatus::NoSuchField
+        ])
+    );
+    assert_eq!(
+        con.hexpire("hash", 100, ExpireOption::NX, "a"),
+        Ok(vec![HashFieldExpireStatus::ConditionNotMet])
+    );
+    assert_eq!(
+        con.hpexpire("hash", 200_000, ExpireOption::GT, "b"),
+        Ok(vec![HashFieldExpireStatus::Set])
+    );
+
+    let ttls: Vec<ExpiryTime> = con.httl("hash", &["a", "c", "missing"]).unwrap();
+    assert!(matches!(ttls[0], ExpiryTime::Expires(ttl) if ttl <= 100));
+    assert_eq!(ttls[1..], [ExpiryTime::NoExpiry, ExpiryTime::Missing]);
+    let ttls: Vec<ExpiryTime> = con.hpttl("hash", "b").unwrap();
+    assert!(matches!(ttls[0], ExpiryTime::Expires(ttl) if ttl > 100_000));
+
+    assert_eq!(
+        con.hexpire_at("hash", 4_000_000_000, ExpireOption::XX, &["a", "c"]),
+        Ok(vec![
+            HashFieldExpireStatus::Set,
+            HashFieldExpireStatus::ConditionNotMet
+        ])
+    );
+    assert_eq!(
+        con.hexpire_time("hash", "a"),
+        Ok(vec![ExpiryTime::Expires(4_000_000_000)])
+    );
+    assert_eq!(
+        con.hpexpire_time("hash", "a"),
+        Ok(vec![ExpiryTime::Expires(4_000_000_000_000)])
+    );
+
+    assert_eq!(
+        con.hpersist("hash", &["a", "c", "missing"]),
+        Ok(vec![
+            HashFieldPersistStatus::Persisted,
+            HashFieldPersistStatus::NoExpiry,
+            HashFieldPersistStatus::NoSuchField
+        ])
+    );
+
+    assert_eq!(
+        con.hpexpire_at("hash", 1, ExpireOption::NONE, "c"),
+        Ok(vec![HashFieldExpireStatus::Deleted])
+    );
+    assert_eq!(con.hexists("hash", "c"), Ok(false));
+
+    if ctx.get_version() < (8, 0, 0) {
+        return;
+    }
+
+    let values: Vec<i32> = con.hget_ex("hash", &["a", "b"], Expiry::EX(50)).unwrap();
+    assert_eq!(values, vec![1, 2]);
+    assert_eq!(con.httl("hash", "a"), Ok(vec![ExpiryTime::Expires(50)]));
+
+    let opts = HSetExOptions::default()
+        .conditional_set(ExistenceCheck::NX)
+        .with_expiration(SetExpiry::PX(10_000));
+    assert_eq!(con.hset_ex("hash", &opts, &[("d", 4), ("e", 5)]), Ok(true));
+    assert_eq!(con.hset_ex("hash", &opts, &[("d", 4)]), Ok(false));
+    let ttls: Vec<ExpiryTime> = con.hpttl("hash", &["d", "e"]).unwrap();
+    assert!(ttls
+        .iter()
+        .all(|ttl| matches!(ttl, ExpiryTime::Expires(ttl) if *ttl <= 10_000)));
+}
+
+#[test]
+fn test_hash_field_expiration_args() {
+    assert_eq!(ToRedisArgs::to_redis_args(&ExpireOption::NONE).len(), 0);
+    assert_args!(&ExpireOption::LT, "LT");
+
+    let empty = HSetExOptions::default();
+    assert_eq!(ToRedisArgs::to_redis_args(&empty).len(), 0);
+
+    let opts = HSetExOptions::default()
+        .conditional_set(ExistenceCheck::XX)
+        .with_expiration(SetExpiry::KEEPTTL);
+    assert_args!(&opts, "FXX", "KEEPTTL");
+
+    let opts = HSetExOptions::default()
+        .conditional_set(ExistenceCheck::NX)
+        .with_expiration(SetExpiry::EXAT(100));
+    assert_args!(&opts, "FNX", "EXAT", "100");
+
+    let cmd = redis::Cmd::hexpire("hash", 10, ExpireOption::GT, &["a", "b"]);
+    assert_eq!(
+        cmd.get_packed_command(),
+        redis::cmd("HEXPIRE")
+            .arg("hash")
+            .arg(10)
+            .arg("GT")
+            .arg("FIELDS")
+            .arg(2)
+            .arg(&["a", "b"])
+            .get_packed_command()
+    );
+    assert_eq!(
+        redis::Cmd::hget_ex("hash", "a", Expiry::PERSIST).get_packed_command(),
+        redis::cmd("HGETEX")
+            .arg("hash")
+            .arg("PERSIST")
+            .arg("FIELDS")
+            .arg(1)
+            .arg("a")
+            .get_packed_command()
+    );
+}
+
 // Requires redis-server >= 4.0.0.
 // Not supported with the current appveyor/windows binary deployed.
 #[cfg(not(target_os = "windows"))]
@@ -246,6 +627,60 @@ fn test_set_ops() {
     assert!(set.contains(&3i32));
 }
 
+#[test]
+fn test_set_membership_and_intersection_card() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if ctx.get_version().0 < 7 {
+        return;
+    }
+
+    assert_eq!(con.sadd("foo", &[1, 2, 3]), Ok(3));
+    assert_eq!(con.sadd("bar", &[2, 3, 4]), Ok(3));
+
+    assert_eq!(
+        con.smismember("foo", &[1, 4, 3]),
+        Ok(vec![true, false, true])
+    );
+    assert_eq!(con.sintercard(&["foo", "bar"]), Ok(2));
+    assert_eq!(con.sintercard_limit(&["foo", "bar"], 1), Ok(1));
+}
+
+#[test]
+fn test_hash_field_lookups() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if ctx.get_version().0 < 7 {
+        return;
+    }
+
+    let _: () = con
+        .hset_multiple("hash", &[("a", "one"), ("b", "three")])
+        .unwrap();
+
+    assert_eq!(con.hstrlen("hash", "b"), Ok(5));
+    assert_eq!(con.hstrlen("hash", "missing"), Ok(0));
+
+    let field: String = con.hrandfield("hash").unwrap();
+    assert!(field == "a" || field == "b");
+    let mut fields: Vec<String> = con.hrandfield_count("hash", 5).unwrap();
+    fields.sort();
+    assert_eq!(fields, vec!["a", "b"]);
+    let fields: Vec<String> = con.hrandfield_count("hash", -5).unwrap();
+    assert_eq!(fields.len(), 5);
+    let pairs: HashMap<String, String> = con.hrandfield_count_withvalues("hash", 2).unwrap();
+    assert_eq!(pairs.get("a").map(String::as_str), Some("one"));
+    assert_eq!(pairs.get("b").map(String::as_str), Some("three"));
+
+    let _: () = con
+        .mset(&[("key1", "ohmytext"), ("key2", "mynewtext")])
+        .unwrap();
+    assert_eq!(con.lcs("key1", "key2"), Ok("mytext".to_string()));
+    assert_eq!(con.lcs_len("key1", "key2"), Ok(6));
+}
+
 #[test]
 fn test_scan() {
     let ctx = TestContext::new();
@@ -841,6 +1276,47 @@ fn test_script_load() {
     assert_eq!(hash, Ok(script.get_hash().to_string()));
 }
 
+#[test]
+fn test_function() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+    if ctx.get_version().0 < 7 {
+        return;
+    }
+
+    let function = redis::Function::new(
+        r"#!lua name=testlib
+        redis.register_function('getarg', function(keys, args)
+            return {redis.call('GET', keys[1]), args[1]}
+        end)
+    ",
+        "getarg",
+    );
+
+    let _: () = con.set("my_key", "foo").unwrap();
+    // The library is loaded on the first call.
+    let response = function.key("my_key").arg(42).invoke(&mut con);
+    assert_eq!(response, Ok(("foo".to_string(), 42)));
+
+    let libraries: Vec<redis::LibraryInfo> = con.function_list().unwrap();
+    assert_eq!(libraries.len(), 1);
+    assert_eq!(libraries[0].name, "testlib");
+    assert_eq!(libraries[0].functions[0].name, "getarg");
+    assert_eq!(libraries[0].code, None);
+
+    let response: (String, i32) = con.fcall("getarg", "my_key", 1).unwrap();
+    assert_eq!(response, ("foo".to_string(), 1));
+
+    let dump: Vec<u8> = con.function_dump().unwrap();
+    let _: () = con.function_flush().unwrap();
+    let _: () = con
+        .function_restore_policy(dump, redis::FunctionRestorePolicy::Append)
+        .unwrap();
+    let _: () = con.function_delete("testlib").unwrap();
+    let libraries: Vec<redis::LibraryInfo> = con.function_list().unwrap();
+    assert!(libraries.is_empty());
+}
+
 #[test]
 fn test_tuple_args() {
     let ctx = TestContext::new();
@@ -898,7 +1374,7 @@ fn test_auto_m_versions() {
     assert_eq!(con.mset(&[("key1", 1), ("key2", 2)]), Ok(()));
     assert_eq!(con.get(&["key1", "key2"]), Ok((1, 2)));
     assert_eq!(con.get(vec!["key1", "key2"]), Ok((1, 2)));
-    assert_eq!(con.get(&vec!["key1", "key2"]), Ok((1, 2)));
+    assert_eq!(con.get(vec!["key1", "key2"]), Ok((1, 2)));
 }
 
 #[test]
@@ -1009,6 +1485,166 @@ fn test_bit_operations() {
     assert_eq!(con.getbit("bitvec", 10), Ok(true));
 }
 
+#[test]
+fn test_bitfield() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let field = BitField::default()
+        .set(BitFieldType::Unsigned(8), BitFieldOffset::Index(0), 200)
+        .incr_by(BitFieldType::Unsigned(8), BitFieldOffset::Index(0), 100)
+        .overflow(BitFieldOverflow::Sat)
+        .incr_by(BitFieldType::Signed(4), BitFieldOffset::Bits(8), 10)
+        .overflow(BitFieldOverflow::Fail)
+        .incr_by(BitFieldType::Signed(4), BitFieldOffset::Bits(8), 10);
+    let values: Vec<Option<i64>> = con.bitfield("bits", &field).unwrap();
+    assert_eq!(values, vec![Some(0), Some(44), Some(7), None]);
+
+    let field = BitField::default()
+        .get(BitFieldType::Unsigned(8), BitFieldOffset::Index(0))
+        .get(BitFieldType::Signed(4), BitFieldOffset::Bits(8));
+    let values: Vec<Option<i64>> = con.bitfield("bits", &field).unwrap();
+    assert_eq!(values, vec![Some(44), Some(7)]);
+    if ctx.get_version().0 >= 6 {
+        let values: Vec<Option<i64>> = con.bitfield_ro("bits", &field).unwrap();
+        assert_eq!(values, vec![Some(44), Some(7)]);
+    }
+
+    // 44 is 0b00101100, 7 is 0b0111
+    assert_eq!(con.bitpos("bits", true), Ok(2));
+    assert_eq!(con.bitpos_range("bits", true, 1, 1), Ok(9));
+    assert_eq!(con.bitpos("missing", false), Ok(0));
+}
+
+#[test]
+fn test_bitfield_args() {
+    let empty = BitField::default();
+    assert_eq!(ToRedisArgs::to_redis_args(&empty).len(), 0);
+
+    let field = BitField::default()
+        .get(BitFieldType::Signed(5), BitFieldOffset::Bits(100))
+        .overflow(BitFieldOverflow::Wrap)
+        .set(BitFieldType::Unsigned(8), BitFieldOffset::Index(2), -1)
+        .incr_by(BitFieldType::Signed(64), BitFieldOffset::Bits(0), 1);
+    assert_args!(
+        &field, "GET", "i5", "100", "OVERFLOW", "WRAP", "SET", "u8", "#2", "-1", "INCRBY", "i64",
+        "0", "1"
+    );
+}
+
+#[test]
+fn test_zrange_options() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+    if ctx.get_version().0 < 7 {
+        return;
+    }
+
+    let _: () = con
+        .zadd_multiple("zset", &[(1, "a"), (2, "b"), (3, "c"), (4, "d")])
+        .unwrap();
+
+    let opts = ZRangeOptions::default().by_score().limit(1, 2);
+    let members: Vec<String> = con.zrange_options("zset", "(1", "+inf", &opts).unwrap();
+    assert_eq!(members, vec!["c", "d"]);
+
+    let opts = ZRangeOptions::default().by_score().rev().with_scores();
+    let members: Vec<(String, f64)> = con.zrange_options("zset", 2, "-inf", &opts).unwrap();
+    assert_eq!(
+        members,
+        vec![("b".to_string(), 2.0), ("a".to_string(), 1.0)]
+    );
+
+    let opts = ZRangeOptions::default().by_lex();
+    let members: Vec<String> = con.zrange_options("zset", "[b", "(d", &opts).unwrap();
+    assert_eq!(members, vec!["b", "c"]);
+
+    assert_eq!(con.zrangestore("dst", "zset", 0, 1), Ok(2));
+    let opts = ZRangeOptions::default().by_score().rev();
+    assert_eq!(con.zrangestore_options("dst", "zset", 4, 3, &opts), Ok(2));
+    let members: Vec<String> = con.zrange("dst", 0, -1).unwrap();
+    assert_eq!(members, vec!["c", "d"]);
+}
+
+#[test]
+fn test_zset_operations() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+    if ctx.get_version().0 < 7 {
+        return;
+    }
+
+    let _: () = con
+        .zadd_multiple("{z}1", &[(1, "a"), (2, "b"), (3, "c")])
+        .unwrap();
+    let _: () = con.zadd_multiple("{z}2", &[(10, "b"), (20, "c")]).unwrap();
+
+    let members: Vec<String> = con.zinter(&["{z}1", "{z}2"]).unwrap();
+    assert_eq!(members, vec!["b", "c"]);
+    let members: Vec<(String, f64)> = con.zunion_withscores(&["{z}1", "{z}2"]).unwrap();
+    assert_eq!(
+        members,
+        vec![
+            ("a".to_string(), 1.0),
+            ("b".to_string(), 12.0),
+            ("c".to_string(), 23.0)
+        ]
+    );
+
+    let opts = ZAggregateOptions::default()
+        .weights(&[2.0, 1.0])
+        .aggregate(ZAggregate::Min)
+        .with_scores();
+    let members: Vec<(String, f64)> = con.zinter_options(&["{z}1", "{z}2"], &opts).unwrap();
+    assert_eq!(
+        members,
+        vec![("b".to_string(), 4.0), ("c".to_string(), 6.0)]
+    );
+    let opts = ZAggregateOptions::default().aggregate(ZAggregate::Max);
+    let members: Vec<String> = con.zunion_options(&["{z}1", "{z}2"], &opts).unwrap();
+    assert_eq!(members, vec!["a", "b", "c"]);
+
+    let members: Vec<(String, f64)> = con.zdiff_withscores(&["{z}1", "{z}2"]).unwrap();
+    assert_eq!(members, vec![("a".to_string(), 1.0)]);
+    assert_eq!(con.zdiffstore("{z}3", &["{z}1", "{z}2"]), Ok(1));
+    let members: Vec<String> = con.zdiff(&["{z}3", "{z}1"]).unwrap();
+    assert!(members.is_empty());
+
+    assert_eq!(con.zintercard(&["{z}1", "{z}2"]), Ok(2));
+    assert_eq!(con.zintercard_limit(&["{z}1", "{z}2"], 1), Ok(1));
+    let scores: Vec<Option<f64>> = con.zscore_multiple("{z}1", &["a", "x"]).unwrap();
+    assert_eq!(scores, vec![Some(1.0), None]);
+}
+
+#[test]
+fn test_zset_options_args() {
+    let empty = ZRangeOptions::default();
+    assert_eq!(ToRedisArgs::to_redis_args(&empty).len(), 0);
+    let opts = ZRangeOptions::default()
+        .by_score()
+        .rev()
+        .limit(5, 10)
+        .with_scores();
+    assert_args!(&opts, "BYSCORE", "REV", "LIMIT", "5", "10", "WITHSCORES");
+    assert_args!(&ZRangeOptions::default().by_lex(), "BYLEX");
+
+    let empty = ZAggregateOptions::default();
+    assert_eq!(ToRedisArgs::to_redis_args(&empty).len(), 0);
+    let opts = ZAggregateOptions::default()
+        .weights(&[1.5, 2.0])
+        .aggregate(ZAggregate::Sum)
+        .with_scores();
+    assert_args!(
+        &opts,
+        "WEIGHTS",
+        "1.5",
+        "2.0",
+        "AGGREGATE",
+        "SUM",
+        "WITHSCORES"
+    );
+}
+
 #[test]
 fn test_redis_server_down() {
     let mut ctx = TestContext::new();
@@ -1339,6 +1975,92 @@ fn test_set_options_options() {
     assert_args!(&opts, "EX", "1000");
 }
 
+#[test]
+fn test_sort() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if ctx.get_version().0 < 7 {
+        return;
+    }
+
+    let _: () = con.rpush("ids", &[3, 1, 2]).unwrap();
+    let _: () = con
+        .mset(&[
+            ("weight_1", "30"),
+            ("weight_2", "10"),
+            ("weight_3", "20"),
+            ("name_1", "one"),
+            ("name_3", "three"),
+        ])
+        .unwrap();
+
+    assert_eq!(con.sort("ids", &SortOptions::default()), Ok(vec![1, 2, 3]));
+    assert_eq!(
+        con.sort("ids", &SortOptions::default().desc().limit(0, 2)),
+        Ok(vec![3, 2])
+    );
+    assert_eq!(
+        con.sort("ids", &SortOptions::default().by("weight_*")),
+        Ok(vec![2, 3, 1])
+    );
+
+    let opts = SortOptions::default().by("weight_*").get("#").get("name_*");
+    let rows: Vec<Option<String>> = con.sort("ids", &opts).unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            Some("2".to_string()),
+            None,
+            Some("3".to_string()),
+            Some("three".to_string()),
+            Some("1".to_string()),
+            Some("one".to_string()),
+        ]
+    );
+
+    let _: () = con.sadd("names", &["b", "c", "a"]).unwrap();
+    let opts = SortOptions::default().alpha().store("sorted");
+    assert_eq!(con.sort("names", &opts), Ok(3));
+    let sorted: Vec<String> = con.lrange("sorted", 0, -1).unwrap();
+    assert_eq!(sorted, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_sort_options_args() {
+    let empty = SortOptions::default();
+    assert_eq!(ToRedisArgs::to_redis_args(&empty).len(), 0);
+
+    let opts = SortOptions::default()
+        .by("weight_*")
+        .limit(5, 10)
+        .get("#")
+        .get("name_*")
+        .desc()
+        .alpha()
+        .store("dest");
+    assert_args!(
+        &opts, "BY", "weight_*", "LIMIT", "5", "10", "GET", "#", "GET", "name_*", "DESC", "ALPHA",
+        "STORE", "dest"
+    );
+
+    assert_eq!(
+        redis::Cmd::sort("key", &SortOptions::default().alpha()).get_packed_command(),
+        redis::cmd("SORT_RO")
+            .arg("key")
+            .arg("ALPHA")
+            .get_packed_command()
+    );
+    assert_eq!(
+        redis::Cmd::sort("key", &SortOptions::default().store("dest")).get_packed_command(),
+        redis::cmd("SORT")
+            .arg("key")
+            .arg("STORE")
+            .arg("dest")
+            .get_packed_command()
+    );
+}
+
 #[test]
 fn test_blocking_sorted_set_api() {
     let ctx = TestContext::new();
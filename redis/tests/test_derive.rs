@@ -0,0 +1,177 @@
+#![allow(clippy::let_unit_value)]
+
+use redis::{Commands, ConversionPathSegment, ErrorKind, FromRedisValue, ToRedisArgs, Value};
+
+use crate::support::*;
+
+mod support;
+
+#[derive(Debug, PartialEq, FromRedisValue, ToRedisArgs)]
+#[redis(rename_all = "lowercase")]
+enum Role {
+    Admin,
+    #[redis(rename = "guest")]
+    Visitor,
+}
+
+#[derive(Debug, PartialEq, FromRedisValue, ToRedisArgs)]
+struct UserId(u64);
+
+#[derive(Debug, PartialEq, FromRedisValue, ToRedisArgs)]
+struct Members(Vec<String>);
+
+#[derive(Debug, PartialEq, FromRedisValue, ToRedisArgs)]
+struct User {
+    id: UserId,
+    #[redis(rename = "user_name")]
+    name: String,
+    role: Role,
+    email: Option<String>,
+    #[redis(default)]
+    visits: u64,
+    #[redis(skip)]
+    cache: Vec<u8>,
+}
+
+fn data(s: &str) -> Value {
+    Value::Data(s.as_bytes().to_vec())
+}
+
+#[test]
+fn test_derive_to_redis_args() {
+    let user = User {
+        id: UserId(1),
+        name: "alice".to_string(),
+        role: Role::Visitor,
+        email: None,
+        visits: 3,
+        cache: vec![1, 2, 3],
+    };
+    assert!(!user.is_single_arg());
+    assert!(UserId(1).is_single_arg());
+    let members = Members(vec!["a".to_string(), "b".to_string()]);
+    assert!(!members.is_single_arg());
+    assert_eq!(members.to_redis_args(), vec![b"a".to_vec(), b"b".to_vec()]);
+    assert_eq!(
+        user.to_redis_args(),
+        vec![
+            b"id".to_vec(),
+            b"1".to_vec(),
+            b"user_name".to_vec(),
+            b"alice".to_vec(),
+            b"role".to_vec(),
+            b"guest".to_vec(),
+            b"visits".to_vec(),
+            b"3".to_vec(),
+        ]
+    );
+    assert_eq!(Role::Admin.to_redis_args(), vec![b"admin".to_vec()]);
+}
+
+#[test]
+fn test_derive_from_redis_value() {
+    let v = Value::Bulk(vec![
+        data("id"),
+        data("7"),
+        data("user_name"),
+        data("bob"),
+        data("role"),
+        data("admin"),
+        data("email"),
+        data("bob@example.com"),
+        data("unknown"),
+        data("ignored"),
+    ]);
+    assert_eq!(
+        User::from_redis_value(&v).unwrap(),
+        User {
+            id: UserId(7),
+            name: "bob".to_string(),
+            role: Role::Admin,
+            email: Some("bob@example.com".to_string()),
+            visits: 0,
+            cache: vec![],
+        }
+    );
+
+    // RESP3 maps are accepted as well.
+    let v = Value::Map(vec![
+        (data("id"), Value::Int(8)),
+        (data("user_name"), data("carol")),
+        (data("role"), data("guest")),
+        (data("visits"), Value::Int(2)),
+    ]);
+    let user = User::from_redis_value(&v).unwrap();
+    assert_eq!(user.role, Role::Visitor);
+    assert_eq!(user.email, None);
+    assert_eq!(user.visits, 2);
+
+    let missing = Value::Bulk(vec![data("id"), data("7")]);
+    let err = User::from_redis_value(&missing).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TypeError);
+
+    let bad_id = Value::Bulk(vec![data("id"), data("seven")]);
+    let err = User::from_redis_value(&bad_id).unwrap_err();
+    assert_eq!(
+        err.conversion_path(),
+        Some(&[ConversionPathSegment::Key("id".to_string())][..])
+    );
+
+    let err = Role::from_redis_value(&data("root")).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TypeError);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_derive_json_fields() {
+    #[derive(Debug, PartialEq, FromRedisValue, ToRedisArgs)]
+    struct Post {
+        title: String,
+        #[redis(json)]
+        tags: Vec<String>,
+        #[redis(json)]
+        scores: Option<Vec<f64>>,
+    }
+
+    let post = Post {
+        title: "hello".to_string(),
+        tags: vec!["a".to_string(), "b".to_string()],
+        scores: None,
+    };
+    let args = post.to_redis_args();
+    assert_eq!(
+        args,
+        vec![
+            b"title".to_vec(),
+            b"hello".to_vec(),
+            b"tags".to_vec(),
+            br#"["a","b"]"#.to_vec()
+        ]
+    );
+
+    let v = Value::Bulk(args.into_iter().map(Value::Data).collect());
+    assert_eq!(Post::from_redis_value(&v).unwrap(), post);
+}
+
+#[test]
+fn test_derive_hash_roundtrip() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let user = User {
+        id: UserId(1),
+        name: "alice".to_string(),
+        role: Role::Admin,
+        email: Some("alice@example.com".to_string()),
+        visits: 10,
+        cache: vec![],
+    };
+    redis::cmd("HSET")
+        .arg("user:1")
+        .arg(&user)
+        .query::<()>(&mut con)
+        .unwrap();
+
+    let stored: User = con.hgetall("user:1").unwrap();
+    assert_eq!(stored, user);
+}
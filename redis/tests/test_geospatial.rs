@@ -2,7 +2,9 @@
 
 use assert_approx_eq::assert_approx_eq;
 
-use redis::geo::{Coord, RadiusOptions, RadiusOrder, RadiusSearchResult, Unit};
+use redis::geo::{
+    Coord, GeoSearchOptions, GeoSearchShape, RadiusOptions, RadiusOrder, RadiusSearchResult, Unit,
+};
 use redis::{Commands, RedisResult};
 
 mod support;
@@ -195,3 +197,42 @@ fn test_georadius_by_member() {
 
     assert_eq!(names, vec!["Agrigento", "Palermo"]);
 }
+
+#[test]
+fn test_geosearch() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    assert_eq!(con.geo_add("my_gis", &[PALERMO, CATANIA, AGRIGENTO]), Ok(3));
+
+    let opts =
+        GeoSearchOptions::from_lonlat(15.0, 37.0, GeoSearchShape::Radius(200.0, Unit::Kilometers))
+            .order(RadiusOrder::Asc)
+            .with_dist()
+            .with_hash();
+    let result: Vec<RadiusSearchResult> = con.geo_search("my_gis", opts).unwrap();
+    let names: Vec<_> = result.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["Catania", "Agrigento", "Palermo"]);
+    assert_approx_eq!(result[0].dist.unwrap(), 56.4413, 0.001);
+    assert!(result[0].hash.is_some());
+    assert_eq!(result[0].coord, None);
+
+    // A box of 100x200km around Agrigento reaches Palermo, but not Catania.
+    let opts = GeoSearchOptions::from_member(
+        AGRIGENTO.2,
+        GeoSearchShape::Box(100.0, 200.0, Unit::Kilometers),
+    )
+    .order(RadiusOrder::Asc)
+    .with_coord();
+    let result: Vec<RadiusSearchResult> = con.geo_search("my_gis", opts).unwrap();
+    let names: Vec<_> = result.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["Agrigento", "Palermo"]);
+    assert_approx_eq!(result[1].coord.as_ref().unwrap().longitude, 13.361_389);
+
+    let opts =
+        GeoSearchOptions::from_member(AGRIGENTO.2, GeoSearchShape::Radius(100.0, Unit::Kilometers))
+            .store_dist();
+    assert_eq!(con.geo_search_store("nearby", "my_gis", opts), Ok(2));
+    let score: f64 = con.zscore("nearby", PALERMO.2).unwrap();
+    assert_approx_eq!(score, 91.0, 1.0);
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use redis::{from_value, ErrorKind, Value};
+use serde::Deserialize;
+
+fn data(s: &str) -> Value {
+    Value::Data(s.as_bytes().to_vec())
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct StreamInfo {
+    length: u64,
+    radix_tree_keys: u32,
+    last_generated_id: String,
+    groups: Option<u32>,
+    first_entry: (String, Vec<String>),
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Role {
+    Master,
+    Replica,
+}
+
+#[test]
+fn test_from_value_struct() {
+    let v = Value::Bulk(vec![
+        data("length"),
+        Value::Int(2),
+        data("radix-tree-keys"),
+        data("1"),
+        data("last-generated-id"),
+        data("1-1"),
+        data("groups"),
+        Value::Nil,
+        data("first-entry"),
+        Value::Bulk(vec![
+            data("1-0"),
+            Value::Bulk(vec![data("field"), data("value")]),
+        ]),
+        data("unknown-field"),
+        Value::Int(0),
+    ]);
+    assert_eq!(
+        from_value::<StreamInfo>(&v).unwrap(),
+        StreamInfo {
+            length: 2,
+            radix_tree_keys: 1,
+            last_generated_id: "1-1".to_string(),
+            groups: None,
+            first_entry: (
+                "1-0".to_string(),
+                vec!["field".to_string(), "value".to_string()]
+            ),
+        }
+    );
+
+    // RESP3 maps work the same way.
+    let v = Value::Map(vec![
+        (data("length"), Value::Int(0)),
+        (data("radix-tree-keys"), Value::Int(0)),
+        (data("last-generated-id"), data("0-0")),
+        (data("groups"), Value::Int(3)),
+        (
+            data("first-entry"),
+            Value::Bulk(vec![data("0-0"), Value::Nil]),
+        ),
+    ]);
+    let info: StreamInfo = from_value(&v).unwrap();
+    assert_eq!(info.groups, Some(3));
+    assert!(info.first_entry.1.is_empty());
+
+    let err = from_value::<StreamInfo>(&Value::Bulk(vec![data("length"), data("x")])).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TypeError);
+}
+
+#[test]
+fn test_from_value_primitives() {
+    assert_eq!(from_value::<i64>(&data("42")).unwrap(), 42);
+    assert_eq!(from_value::<f64>(&data("1.5")).unwrap(), 1.5);
+    assert!(from_value::<bool>(&data("1")).unwrap());
+    assert_eq!(from_value::<Option<i32>>(&Value::Nil).unwrap(), None);
+    assert_eq!(from_value::<&str>(&data("borrowed")).unwrap(), "borrowed");
+    assert_eq!(from_value::<String>(&Value::Okay).unwrap(), "OK");
+    assert_eq!(
+        from_value::<Vec<u8>>(&Value::Data(vec![0, 255])).unwrap(),
+        vec![0, 255]
+    );
+    assert_eq!(
+        from_value::<Vec<Role>>(&Value::Set(vec![data("master"), data("replica")])).unwrap(),
+        vec![Role::Master, Role::Replica]
+    );
+    assert_eq!(
+        from_value::<HashMap<String, i64>>(&Value::Bulk(vec![data("a"), data("1")])).unwrap(),
+        HashMap::from([("a".to_string(), 1)])
+    );
+    assert_eq!(
+        from_value::<i64>(&data("nope")).unwrap_err().kind(),
+        ErrorKind::TypeError
+    );
+}
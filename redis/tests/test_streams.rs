@@ -1,7 +1,9 @@
 #![cfg(feature = "streams")]
 
 use redis::streams::*;
-use redis::{Commands, Connection, RedisResult, ToRedisArgs};
+use redis::{
+    from_redis_value, Commands, Connection, ControlFlow, ErrorKind, RedisResult, ToRedisArgs, Value,
+};
 
 mod support;
 use crate::support::*;
@@ -88,6 +90,118 @@ fn test_cmd_options() {
     let opts = StreamReadOptions::default().noack().block(100).count(200);
 
     assert_args!(&opts, "BLOCK", "100", "COUNT", "200");
+
+    // test trim and add options
+
+    let trim = StreamTrimOptions::minid(StreamTrimmingMode::Approx, "1000-0").limit(100);
+    assert_args!(&trim, "MINID", "~", "1000-0", "LIMIT", "100");
+    let trim = StreamTrimOptions::maxlen(StreamTrimmingMode::Exact, 10);
+    assert_args!(&trim, "MAXLEN", "=", "10");
+
+    let empty = StreamAddOptions::default();
+    assert_eq!(ToRedisArgs::to_redis_args(&empty).len(), 0);
+
+    let opts = StreamAddOptions::default().nomkstream().trim(trim);
+    assert_args!(&opts, "NOMKSTREAM", "MAXLEN", "=", "10");
+
+    // test autoclaim options
+
+    let opts = StreamAutoClaimOptions::default().count(5).with_justid();
+    assert_args!(&opts, "COUNT", "5", "JUSTID");
+}
+
+#[test]
+fn test_reply_parsing() {
+    fn data(s: &str) -> Value {
+        Value::Data(s.as_bytes().to_vec())
+    }
+
+    let autoclaim = Value::Bulk(vec![
+        data("0-0"),
+        Value::Bulk(vec![
+            Value::Bulk(vec![
+                data("1-0"),
+                Value::Bulk(vec![data("hello"), data("world")]),
+            ]),
+            Value::Nil,
+        ]),
+        Value::Bulk(vec![data("2-0")]),
+    ]);
+    let reply: StreamAutoClaimReply = from_redis_value(&autoclaim).unwrap();
+    assert_eq!(reply.next_stream_id, "0-0");
+    assert_eq!(reply.claimed.len(), 1);
+    assert_eq!(reply.claimed[0].id, "1-0");
+    assert_eq!(reply.claimed[0].get("hello"), Some("world".to_string()));
+    assert_eq!(reply.deleted_ids, vec!["2-0".to_string()]);
+
+    // JUSTID replies only carry the IDs
+    let autoclaim = Value::Bulk(vec![data("3-0"), Value::Bulk(vec![data("1-0")])]);
+    let reply: StreamAutoClaimReply = from_redis_value(&autoclaim).unwrap();
+    assert_eq!(reply.next_stream_id, "3-0");
+    assert_eq!(reply.claimed[0].id, "1-0");
+    assert!(reply.claimed[0].is_empty());
+    assert!(reply.deleted_ids.is_empty());
+
+    let full = Value::Bulk(vec![
+        data("length"),
+        Value::Int(1),
+        data("last-generated-id"),
+        data("1-0"),
+        data("entries-added"),
+        Value::Int(1),
+        data("entries"),
+        Value::Bulk(vec![Value::Bulk(vec![
+            data("1-0"),
+            Value::Bulk(vec![data("hello"), data("world")]),
+        ])]),
+        data("groups"),
+        Value::Bulk(vec![Value::Bulk(vec![
+            data("name"),
+            data("g1"),
+            data("last-delivered-id"),
+            data("1-0"),
+            data("lag"),
+            Value::Nil,
+            data("pel-count"),
+            Value::Int(1),
+            data("pending"),
+            Value::Bulk(vec![Value::Bulk(vec![
+                data("1-0"),
+                data("c1"),
+                Value::Int(1700000000000),
+                Value::Int(1),
+            ])]),
+            data("consumers"),
+            Value::Bulk(vec![Value::Bulk(vec![
+                data("name"),
+                data("c1"),
+                data("seen-time"),
+                Value::Int(1700000000000),
+                data("pel-count"),
+                Value::Int(1),
+                data("pending"),
+                Value::Bulk(vec![Value::Bulk(vec![
+                    data("1-0"),
+                    Value::Int(1700000000000),
+                    Value::Int(1),
+                ])]),
+            ])]),
+        ])]),
+    ]);
+    let reply: StreamInfoStreamFullReply = from_redis_value(&full).unwrap();
+    assert_eq!(reply.length, 1);
+    assert_eq!(reply.entries_added, Some(1));
+    assert_eq!(reply.max_deleted_entry_id, None);
+    assert_eq!(reply.entries[0].id, "1-0");
+    let group = &reply.groups[0];
+    assert_eq!(group.name, "g1");
+    assert_eq!(group.lag, None);
+    assert_eq!(group.pending[0].consumer, "c1");
+    assert_eq!(group.pending[0].delivery_time, 1700000000000);
+    let consumer = &group.consumers[0];
+    assert_eq!(consumer.active_time, None);
+    assert_eq!(consumer.pending[0].consumer, "c1");
+    assert_eq!(consumer.pending[0].delivery_count, 1);
 }
 
 #[test]
@@ -435,7 +549,7 @@ fn test_xclaim() {
 
     // grab this id if > 4ms
     let reply: StreamClaimReply = con
-        .xclaim("k1", "g1", "c2", 4, &[claim.id.clone()])
+        .xclaim("k1", "g1", "c2", 4, std::slice::from_ref(&claim.id))
         .unwrap();
     assert_eq!(reply.ids.len(), 1);
     assert_eq!(reply.ids[0].id, claim.id);
@@ -461,7 +575,7 @@ fn test_xclaim() {
             "g1",
             "c3",
             4,
-            &[claim.id.clone()],
+            std::slice::from_ref(&claim.id),
             StreamClaimOptions::default().with_force(),
         )
         .unwrap();
@@ -531,6 +645,176 @@ fn test_xtrim() {
     assert_eq!(result, Ok(40));
 }
 
+#[test]
+fn test_xadd_options() {
+    // Tests the following commands....
+    // xadd_options
+    // xtrim_options
+    // xsetid
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    // NOMKSTREAM doesn't create the stream
+    let opts = StreamAddOptions::default().nomkstream();
+    let result: RedisResult<Option<String>> = con.xadd_options("k1", "*", &[("h", "w")], opts);
+    assert_eq!(result, Ok(None));
+    assert_eq!(con.exists("k1"), Ok(false));
+
+    for i in 1..=10 {
+        let opts = StreamAddOptions::default()
+            .trim(StreamTrimOptions::maxlen(StreamTrimmingMode::Exact, 5));
+        let _: String = con
+            .xadd_options("k1", format!("{i}-0"), &[("h", "w")], opts)
+            .unwrap();
+    }
+    assert_eq!(con.xlen("k1"), Ok(5));
+
+    // evict everything below 8-0
+    let trim = StreamTrimOptions::minid(StreamTrimmingMode::Exact, "8-0");
+    let result: RedisResult<usize> = con.xtrim_options("k1", trim);
+    assert_eq!(result, Ok(2));
+    assert_eq!(con.xlen("k1"), Ok(3));
+
+    let _: () = con.xsetid("k1", "100-0").unwrap();
+    let info: StreamInfoStreamReply = con.xinfo_stream("k1").unwrap();
+    assert_eq!(info.last_generated_id, "100-0");
+}
+
+#[test]
+fn test_xautoclaim() {
+    // Tests the following commands....
+    // xgroup_createconsumer
+    // xautoclaim_options
+    // xpending_idle_count
+    // xinfo_stream_full
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    xadd(&mut con);
+
+    let _: () = con.xgroup_create("k1", "g1", "0").unwrap();
+    let result: RedisResult<bool> = con.xgroup_createconsumer("k1", "g1", "c1");
+    assert_eq!(result, Ok(true));
+    let result: RedisResult<bool> = con.xgroup_createconsumer("k1", "g1", "c1");
+    assert_eq!(result, Ok(false));
+
+    // read both messages, so they end up in the PEL of c1
+    let _: StreamReadReply = con
+        .xread_options(
+            &["k1"],
+            &[">"],
+            &StreamReadOptions::default().group("g1", "c1"),
+        )
+        .unwrap();
+
+    sleep(Duration::from_millis(10));
+
+    let pending: StreamPendingCountReply = con
+        .xpending_idle_count("k1", "g1", 5, "-", "+", 10)
+        .unwrap();
+    assert_eq!(pending.ids.len(), 2);
+    let pending: StreamPendingCountReply = con
+        .xpending_idle_count("k1", "g1", 60000, "-", "+", 10)
+        .unwrap();
+    assert!(pending.ids.is_empty());
+
+    let opts = StreamAutoClaimOptions::default().count(1);
+    let reply: StreamAutoClaimReply = con
+        .xautoclaim_options("k1", "g1", "c2", 5, "0-0", opts)
+        .unwrap();
+    assert_eq!(reply.claimed.len(), 1);
+    assert_eq!(reply.claimed[0].id, "1000-0");
+    assert_eq!(reply.next_stream_id, "1000-1");
+
+    let opts = StreamAutoClaimOptions::default().with_justid();
+    let reply: StreamAutoClaimReply = con
+        .xautoclaim_options("k1", "g1", "c2", 5, reply.next_stream_id, opts)
+        .unwrap();
+    assert_eq!(reply.claimed.len(), 1);
+    assert_eq!(reply.claimed[0].id, "1000-1");
+    assert!(reply.claimed[0].is_empty());
+
+    let info: StreamInfoStreamFullReply = con.xinfo_stream_full("k1").unwrap();
+    assert_eq!(info.length, 2);
+    assert_eq!(info.entries.len(), 2);
+    assert_eq!(info.groups[0].name, "g1");
+    assert_eq!(info.groups[0].pel_count, 2);
+    let c2 = info.groups[0]
+        .consumers
+        .iter()
+        .find(|c| c.name == "c2")
+        .unwrap();
+    assert_eq!(c2.pending.len(), 2);
+    assert_eq!(c2.pending[0].consumer, "c2");
+
+    let info: StreamInfoStreamFullReply = con.xinfo_stream_full_count("k1", 1).unwrap();
+    assert_eq!(info.entries.len(), 1);
+}
+
+#[test]
+fn test_stream_consumer() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    xadd(&mut con);
+
+    let mut consumer = StreamConsumer::new("k1", "g1", "c1")
+        .start_id("0")
+        .block(10);
+
+    // a failing handler leaves the entries pending
+    let result: RedisResult<()> =
+        consumer.run(&mut con, |_| Err((ErrorKind::ClientError, "failed").into()));
+    assert!(result.is_err());
+    let pending: StreamPendingReply = con.xpending("k1", "g1").unwrap();
+    assert_eq!(pending.count(), 2);
+
+    // pending entries are processed again before new ones
+    let mut seen = vec![];
+    consumer
+        .run(&mut con, |entry| {
+            seen.push(entry.id.clone());
+            Ok(if seen.len() == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue
+            })
+        })
+        .unwrap();
+    assert_eq!(seen, vec!["1000-0", "1000-1"]);
+    let pending: StreamPendingReply = con.xpending("k1", "g1").unwrap();
+    assert_eq!(pending.count(), 0);
+}
+
+#[test]
+fn test_stream_consumer_dead_letter() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    xadd(&mut con);
+
+    let mut consumer = StreamConsumer::new("k1", "g1", "c1")
+        .start_id("0")
+        .block(10)
+        .dead_letter("k1:dead", 1);
+
+    // a fresh consumer has nothing pending and reads new entries right away
+    let batch = consumer.next_batch(&mut con).unwrap();
+    assert_eq!(batch.len(), 2);
+
+    // a restarted consumer would deliver the entries a second time
+    let mut restarted = StreamConsumer::new("k1", "g1", "c1")
+        .block(10)
+        .dead_letter("k1:dead", 1);
+    let batch = restarted.next_batch(&mut con).unwrap();
+    assert!(batch.is_empty());
+    assert_eq!(con.xlen("k1:dead"), Ok(2));
+    let dead: StreamRangeReply = con.xrange_all("k1:dead").unwrap();
+    assert_eq!(dead.ids[0].get("hello"), Some("world".to_string()));
+    let pending: StreamPendingReply = con.xpending("k1", "g1").unwrap();
+    assert_eq!(pending.count(), 0);
+}
+
 #[test]
 fn test_xgroup() {
     // Tests the following commands....
@@ -33,6 +33,81 @@ fn test_info_dict() {
     assert_eq!(d.get::<String>("key3"), None);
 }
 
+#[test]
+fn test_server_info() {
+    use redis::info::ServerInfo;
+    use redis::{ConversionPathSegment, FromRedisValue, Value};
+
+    let text = "# Server\r\n\
+        redis_version:7.2.3\r\n\
+        redis_mode:standalone\r\n\
+        tcp_port:6379\r\n\
+        \r\n\
+        # Memory\r\n\
+        used_memory:1024\r\n\
+        mem_fragmentation_ratio:1.50\r\n\
+        \r\n\
+        # Replication\r\n\
+        role:master\r\n\
+        connected_slaves:2\r\n\
+        slave1:ip=10.0.0.2,port=6380,state=wait_bgsave,offset=0,lag=1\r\n\
+        slave0:ip=10.0.0.1,port=6379,state=online,offset=42,lag=0\r\n\
+        \r\n\
+        # CPU\r\n\
+        used_cpu_sys:1.25\r\n\
+        \r\n\
+        # Commandstats\r\n\
+        cmdstat_get:calls=3,usec=6,usec_per_call=2.00,rejected_calls=0,failed_calls=1\r\n\
+        cmdstat_config|get:calls=1,usec=10,usec_per_call=10.00\r\n\
+        \r\n\
+        # Keyspace\r\n\
+        db0:keys=5,expires=1,avg_ttl=100\r\n\
+        db3:keys=1,expires=0,avg_ttl=0\r\n";
+    let info = ServerInfo::from_redis_value(&Value::Data(text.into())).unwrap();
+    assert_eq!(info.section("cpu").unwrap().get("used_cpu_sys"), Some(1.25));
+
+    let server = info.server.unwrap();
+    assert_eq!(server.redis_version, "7.2.3");
+    assert_eq!(server.tcp_port, 6379);
+    assert_eq!(server.uptime_in_seconds, 0);
+
+    let memory = info.memory.unwrap();
+    assert_eq!(memory.used_memory, 1024);
+    assert_eq!(memory.mem_fragmentation_ratio, 1.5);
+
+    let replication = info.replication.unwrap();
+    assert_eq!(replication.role, "master");
+    assert_eq!(replication.master_host, None);
+    assert_eq!(replication.replicas.len(), 2);
+    assert_eq!(replication.replicas[0].ip, "10.0.0.1");
+    assert_eq!(replication.replicas[0].offset, 42);
+    assert_eq!(replication.replicas[1].state, "wait_bgsave");
+
+    let commandstats = info.commandstats.unwrap();
+    assert_eq!(commandstats["get"].calls, 3);
+    assert_eq!(commandstats["get"].failed_calls, 1);
+    assert_eq!(commandstats["config|get"].usec_per_call, 10.0);
+
+    let keyspace = info.keyspace.unwrap();
+    assert_eq!(keyspace[&0].keys, 5);
+    assert_eq!(keyspace[&3].avg_ttl, 0);
+
+    assert!(info.clients.is_none());
+    assert!(info.stats.is_none());
+
+    let err = ServerInfo::parse("# Keyspace\ndb0:keys=many\n").unwrap_err();
+    assert_eq!(
+        err.conversion_path(),
+        Some(
+            &[
+                ConversionPathSegment::Key("keyspace".to_string()),
+                ConversionPathSegment::Key("db0".to_string()),
+                ConversionPathSegment::Key("keys".to_string()),
+            ][..]
+        )
+    );
+}
+
 #[test]
 fn test_i32() {
     use redis::{ErrorKind, FromRedisValue, Value};
@@ -61,6 +136,55 @@ fn test_u32() {
     assert_eq!(bad_i.unwrap_err().kind(), ErrorKind::TypeError);
 }
 
+#[test]
+fn test_expiry_replies() {
+    use redis::{
+        ErrorKind, ExpiryTime, FromRedisValue, HashFieldExpireStatus, HashFieldPersistStatus, Value,
+    };
+
+    let v = FromRedisValue::from_redis_value(&Value::Int(-2));
+    assert_eq!(v, Ok(ExpiryTime::Missing));
+    let v = FromRedisValue::from_redis_value(&Value::Int(-1));
+    assert_eq!(v, Ok(ExpiryTime::NoExpiry));
+    let v = FromRedisValue::from_redis_value(&Value::Int(1700000000));
+    assert_eq!(v, Ok(ExpiryTime::Expires(1700000000)));
+
+    let v = FromRedisValue::from_redis_value(&Value::Bulk(vec![
+        Value::Int(-2),
+        Value::Int(0),
+        Value::Int(1),
+        Value::Int(2),
+    ]));
+    assert_eq!(
+        v,
+        Ok(vec![
+            HashFieldExpireStatus::NoSuchField,
+            HashFieldExpireStatus::ConditionNotMet,
+            HashFieldExpireStatus::Set,
+            HashFieldExpireStatus::Deleted,
+        ])
+    );
+
+    let v = FromRedisValue::from_redis_value(&Value::Bulk(vec![
+        Value::Int(-2),
+        Value::Int(-1),
+        Value::Int(1),
+    ]));
+    assert_eq!(
+        v,
+        Ok(vec![
+            HashFieldPersistStatus::NoSuchField,
+            HashFieldPersistStatus::NoExpiry,
+            HashFieldPersistStatus::Persisted,
+        ])
+    );
+
+    let bad: Result<HashFieldExpireStatus, _> = FromRedisValue::from_redis_value(&Value::Int(3));
+    assert_eq!(bad.unwrap_err().kind(), ErrorKind::TypeError);
+    let bad: Result<ExpiryTime, _> = FromRedisValue::from_redis_value(&Value::Int(-3));
+    assert_eq!(bad.unwrap_err().kind(), ErrorKind::TypeError);
+}
+
 #[test]
 fn test_vec() {
     use redis::{FromRedisValue, Value};
@@ -378,3 +502,240 @@ fn test_types_to_redis_args() {
         .to_redis_args()
         .is_empty());
 }
+
+#[test]
+fn test_resp3_values() {
+    use redis::{ErrorKind, FromRedisValue, PushKind, Value, VerbatimFormat};
+    use std::collections::{HashMap, HashSet};
+
+    let map = Value::Map(vec![
+        (Value::Data(b"a".to_vec()), Value::Int(1)),
+        (Value::Data(b"b".to_vec()), Value::Int(2)),
+    ]);
+    let v: HashMap<String, i64> = FromRedisValue::from_redis_value(&map).unwrap();
+    assert_eq!(v.get("a"), Some(&1));
+    assert_eq!(v.get("b"), Some(&2));
+    let v: Vec<(String, i64)> = FromRedisValue::from_redis_value(&map).unwrap();
+    assert_eq!(v, vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+
+    let set = Value::Set(vec![Value::Int(1), Value::Int(2)]);
+    let v: HashSet<i64> = FromRedisValue::from_redis_value(&set).unwrap();
+    assert_eq!(v, [1, 2].into_iter().collect());
+
+    let v: f64 = FromRedisValue::from_redis_value(&Value::Double(1.5)).unwrap();
+    assert_eq!(v, 1.5);
+    let v: String = FromRedisValue::from_redis_value(&Value::Double(1.5)).unwrap();
+    assert_eq!(v, "1.5");
+    let v: i64 = FromRedisValue::from_redis_value(&Value::Double(-3.0)).unwrap();
+    assert_eq!(v, -3);
+    let v: u8 = FromRedisValue::from_redis_value(&Value::Double(255.0)).unwrap();
+    assert_eq!(v, 255);
+    for double in [1.7, f64::NAN, f64::INFINITY, 256.0, -1.0] {
+        let v: Result<u8, _> = FromRedisValue::from_redis_value(&Value::Double(double));
+        assert_eq!(v.unwrap_err().kind(), ErrorKind::TypeError);
+    }
+    let v: Result<i64, _> = FromRedisValue::from_redis_value(&Value::Double(9.3e18));
+    assert_eq!(v.unwrap_err().kind(), ErrorKind::TypeError);
+
+    let v: bool = FromRedisValue::from_redis_value(&Value::Boolean(true)).unwrap();
+    assert!(v);
+
+    let big = Value::BigNumber("123456789012345678901234567890".to_string());
+    let v: String = FromRedisValue::from_redis_value(&big).unwrap();
+    assert_eq!(v, "123456789012345678901234567890");
+    let v: u128 = FromRedisValue::from_redis_value(&big).unwrap();
+    assert_eq!(v, 123456789012345678901234567890);
+    #[cfg(feature = "num-bigint")]
+    {
+        let v: num_bigint::BigInt = FromRedisValue::from_redis_value(&big).unwrap();
+        assert_eq!(v.to_string(), "123456789012345678901234567890");
+    }
+
+    let verbatim = Value::VerbatimString {
+        format: VerbatimFormat::Text,
+        text: "hello".to_string(),
+    };
+    let v: String = FromRedisValue::from_redis_value(&verbatim).unwrap();
+    assert_eq!(v, "hello");
+
+    // RESP3 nests pairs such as `ZRANGE ... WITHSCORES` replies.
+    let pairs = Value::Bulk(vec![
+        Value::Bulk(vec![Value::Data(b"a".to_vec()), Value::Double(1.0)]),
+        Value::Bulk(vec![Value::Data(b"b".to_vec()), Value::Double(2.0)]),
+    ]);
+    let v: Vec<(String, f64)> = FromRedisValue::from_redis_value(&pairs).unwrap();
+    assert_eq!(v, vec![("a".to_string(), 1.0), ("b".to_string(), 2.0)]);
+    let v: Vec<(String, f64)> = FromRedisValue::from_owned_redis_value(pairs).unwrap();
+    assert_eq!(v, vec![("a".to_string(), 1.0), ("b".to_string(), 2.0)]);
+
+    // Flat RESP2 replies whose elements happen to be bulks of the tuple size
+    // are still paired up flat.
+    let flat = Value::Bulk(vec![
+        Value::Bulk(vec![Value::Int(1), Value::Int(2)]),
+        Value::Bulk(vec![Value::Int(3), Value::Int(4)]),
+        Value::Bulk(vec![Value::Int(5), Value::Int(6)]),
+        Value::Bulk(vec![Value::Int(7), Value::Int(8)]),
+    ]);
+    let expected = vec![(vec![1, 2], vec![3, 4]), (vec![5, 6], vec![7, 8])];
+    let v: Vec<(Vec<i64>, Vec<i64>)> = FromRedisValue::from_redis_value(&flat).unwrap();
+    assert_eq!(v, expected);
+    let v: Vec<(Vec<i64>, Vec<i64>)> = FromRedisValue::from_owned_redis_value(flat).unwrap();
+    assert_eq!(v, expected);
+
+    assert_eq!(PushKind::from("invalidate"), PushKind::Invalidate);
+    assert_eq!(PushKind::from("foo"), PushKind::Other("foo".to_string()));
+}
+
+#[test]
+fn test_owned_conversions() {
+    use redis::{FromRedisValue, Value};
+    use std::collections::{BTreeMap, HashMap, HashSet};
+
+    let v: String = FromRedisValue::from_owned_redis_value(Value::Data(b"hello".to_vec())).unwrap();
+    assert_eq!(v, "hello");
+    let v: Vec<u8> =
+        FromRedisValue::from_owned_redis_value(Value::Data(b"hello".to_vec())).unwrap();
+    assert_eq!(v, b"hello");
+    let v: Result<String, _> = FromRedisValue::from_owned_redis_value(Value::Data(vec![0xff]));
+    assert!(v.is_err());
+
+    let bulk = Value::Bulk(vec![
+        Value::Data(b"a".to_vec()),
+        Value::Data(b"1".to_vec()),
+        Value::Data(b"b".to_vec()),
+        Value::Data(b"2".to_vec()),
+    ]);
+    let v: Vec<String> = FromRedisValue::from_owned_redis_value(bulk.clone()).unwrap();
+    assert_eq!(v, vec!["a", "1", "b", "2"]);
+    let v: Vec<(String, i64)> = FromRedisValue::from_owned_redis_value(bulk.clone()).unwrap();
+    assert_eq!(v, vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+    let v: HashMap<String, i64> = FromRedisValue::from_owned_redis_value(bulk.clone()).unwrap();
+    assert_eq!(v, FromRedisValue::from_redis_value(&bulk).unwrap());
+    let v: BTreeMap<String, String> = FromRedisValue::from_owned_redis_value(bulk.clone()).unwrap();
+    assert_eq!(v, FromRedisValue::from_redis_value(&bulk).unwrap());
+    let v: HashSet<String> = FromRedisValue::from_owned_redis_value(bulk.clone()).unwrap();
+    assert_eq!(v.len(), 4);
+    let v: (String, i64, String, i64) = FromRedisValue::from_owned_redis_value(bulk).unwrap();
+    assert_eq!(v, ("a".to_string(), 1, "b".to_string(), 2));
+
+    let v: Vec<Option<Vec<u8>>> = FromRedisValue::from_owned_redis_value(Value::Bulk(vec![
+        Value::Data(b"a".to_vec()),
+        Value::Nil,
+    ]))
+    .unwrap();
+    assert_eq!(v, vec![Some(b"a".to_vec()), None]);
+
+    let map = Value::Map(vec![(Value::Data(b"a".to_vec()), Value::Int(1))]);
+    let v: HashMap<String, i64> = FromRedisValue::from_owned_redis_value(map).unwrap();
+    assert_eq!(v.get("a"), Some(&1));
+
+    let v: Value = FromRedisValue::from_owned_redis_value(Value::Okay).unwrap();
+    assert_eq!(v, Value::Okay);
+}
+
+#[test]
+fn test_conversion_error_path() {
+    use redis::{
+        from_owned_redis_value, from_redis_value, ConversionPathSegment, ErrorKind, RedisResult,
+        Value,
+    };
+    use std::collections::HashMap;
+
+    let data = |s: &str| Value::Data(s.as_bytes().to_vec());
+    let v = Value::Bulk(vec![
+        Value::Bulk(vec![data("a"), Value::Bulk(vec![data("x"), data("1")])]),
+        Value::Bulk(vec![
+            data("b"),
+            Value::Bulk(vec![data("x"), data("2"), data("y"), data("nope")]),
+        ]),
+    ]);
+
+    let check = |res: RedisResult<Vec<(String, HashMap<String, i64>)>>| {
+        let err = res.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TypeError);
+        assert_eq!(err.expected_type(), Some("i64"));
+        assert_eq!(
+            err.conversion_path().unwrap(),
+            &[
+                ConversionPathSegment::Index(1),
+                ConversionPathSegment::TuplePosition(1),
+                ConversionPathSegment::Key("y".to_string()),
+            ][..]
+        );
+        assert!(err.to_string().starts_with(
+            "Response was of incompatible type - TypeError: expected i64 at [1].1[\"y\"]: "
+        ));
+    };
+    check(from_redis_value(&v));
+    check(from_owned_redis_value(v));
+
+    // Flat tuple rows are reported by row and position.
+    let v = Value::Bulk(vec![data("a"), data("1"), data("b"), data("x")]);
+    let err = from_redis_value::<Vec<(String, u8)>>(&v).unwrap_err();
+    assert_eq!(err.expected_type(), Some("u8"));
+    assert_eq!(
+        err.conversion_path().unwrap(),
+        &[
+            ConversionPathSegment::Index(1),
+            ConversionPathSegment::TuplePosition(1)
+        ][..]
+    );
+    let err = from_owned_redis_value::<Vec<(String, u8)>>(v).unwrap_err();
+    assert_eq!(err.conversion_path().unwrap().len(), 2);
+
+    // Top level failures have no path.
+    let err = from_redis_value::<Vec<String>>(&Value::Int(1)).unwrap_err();
+    assert_eq!(err.conversion_path(), None);
+    assert_eq!(err.expected_type(), Some("Vec<String>"));
+}
+
+#[test]
+fn test_value_ref() {
+    use redis::{from_redis_value_ref, FromRedisValueRef, Value, ValueRef};
+    use std::collections::HashMap;
+
+    let data = ValueRef::Data(b"hello");
+    let v: &[u8] = FromRedisValueRef::from_redis_value_ref(&data).unwrap();
+    assert_eq!(v, b"hello");
+    let v: &str = FromRedisValueRef::from_redis_value_ref(&data).unwrap();
+    assert_eq!(v, "hello");
+    let v: String = FromRedisValueRef::from_redis_value_ref(&data).unwrap();
+    assert_eq!(v, "hello");
+    let v: Result<&str, _> = FromRedisValueRef::from_redis_value_ref(&ValueRef::Data(b"\xff"));
+    assert!(v.is_err());
+
+    // Scalars follow their `FromRedisValue` rules.
+    let v: i64 = FromRedisValueRef::from_redis_value_ref(&ValueRef::Data(b"42")).unwrap();
+    assert_eq!(v, 42);
+    let v: bool = FromRedisValueRef::from_redis_value_ref(&ValueRef::Int(1)).unwrap();
+    assert!(v);
+    let err = from_redis_value_ref::<i64>(&ValueRef::Double(1.5)).unwrap_err();
+    assert_eq!(err.kind(), redis::ErrorKind::TypeError);
+
+    let mget = ValueRef::Bulk(vec![
+        ValueRef::Data(b"a"),
+        ValueRef::Nil,
+        ValueRef::Data(b"c"),
+    ]);
+    let v: Vec<Option<&[u8]>> = FromRedisValueRef::from_redis_value_ref(&mget).unwrap();
+    assert_eq!(v, vec![Some(&b"a"[..]), None, Some(&b"c"[..])]);
+    let v: Value = FromRedisValueRef::from_redis_value_ref(&mget).unwrap();
+    assert_eq!(
+        v,
+        Value::Bulk(vec![
+            Value::Data(b"a".to_vec()),
+            Value::Nil,
+            Value::Data(b"c".to_vec()),
+        ])
+    );
+
+    let map = ValueRef::Map(vec![
+        (ValueRef::Data(b"a"), ValueRef::Int(1)),
+        (ValueRef::Data(b"b"), ValueRef::Int(2)),
+    ]);
+    let v: HashMap<&str, i64> = FromRedisValueRef::from_redis_value_ref(&map).unwrap();
+    assert_eq!(v, HashMap::from([("a", 1), ("b", 2)]));
+    let flat = ValueRef::Bulk(vec![ValueRef::Data(b"a"), ValueRef::Int(1)]);
+    let v: HashMap<&str, i64> = FromRedisValueRef::from_redis_value_ref(&flat).unwrap();
+    assert_eq!(v, HashMap::from([("a", 1)]));
+}